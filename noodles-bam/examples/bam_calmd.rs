@@ -0,0 +1,56 @@
+//! Checks the MD and NM tags of records in a BAM file against a reference sequence FASTA.
+//!
+//! Each mapped record with a missing or incorrect MD or NM tag is printed with the existing and
+//! calculated values.
+
+use std::{
+    collections::HashMap,
+    env,
+    fs::File,
+    io::{self, BufReader},
+};
+
+use noodles_bam::{self as bam, calmd};
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+    let src = args.next().expect("missing src");
+    let fasta_src = args.next().expect("missing fasta_src");
+
+    let reference_sequences: HashMap<_, _> = File::open(fasta_src)
+        .map(BufReader::new)
+        .map(fasta::Reader::new)?
+        .records()
+        .map(|result| result.map(|record| (record.reference_sequence_name().to_string(), record)))
+        .collect::<io::Result<_>>()?;
+
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    reader.read_reference_sequences()?;
+
+    for result in reader.records() {
+        let record = result?;
+
+        let reference_sequence = match *record.reference_sequence_id() {
+            Some(id) => header
+                .reference_sequences()
+                .get_index(id as usize)
+                .and_then(|(name, _)| reference_sequences.get(name))
+                .ok_or("missing reference sequence")?,
+            None => continue,
+        };
+
+        let mismatches = calmd::verify(reference_sequence, &record)?;
+
+        if !mismatches.is_empty() {
+            let read_name = record.read_name();
+            let read_name =
+                String::from_utf8_lossy(read_name.strip_suffix(b"\0").unwrap_or(read_name));
+            println!("{}\t{:?}", read_name, mismatches);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Prints the read depth of each covered position of a BAM file.
+//!
+//! The input BAM must be coordinate-sorted. Positions with no coverage are not printed. The output
+//! matches the output of `samtools depth <src>`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam::{self as bam, coverage::Depth};
+use noodles_sam as sam;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    reader.read_reference_sequences()?;
+
+    let reference_sequences = header.reference_sequences();
+
+    for result in Depth::new(reader.records()) {
+        let block = result?;
+
+        let name = reference_sequences
+            .get_index(block.reference_sequence_id())
+            .map(|(name, _)| name)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })?;
+
+        for position in i32::from(block.start())..=i32::from(block.end()) {
+            println!("{}\t{}\t{}", name, position, block.depth());
+        }
+    }
+
+    Ok(())
+}
@@ -4,74 +4,7 @@
 
 use std::{env, fmt, fs::File, io};
 
-use noodles_bam as bam;
-
-#[derive(Debug, Default)]
-struct Counts {
-    read: u64,
-    secondary: u64,
-    supplementary: u64,
-    duplicate: u64,
-    mapped: u64,
-    paired: u64,
-    read_1: u64,
-    read_2: u64,
-    proper_pair: u64,
-    mate_mapped: u64,
-    singleton: u64,
-    mate_reference_sequence_id_mismatch: u64,
-    mate_reference_sequence_id_mismatch_hq: u64,
-}
-
-fn count(counts: &mut Counts, record: &bam::Record) {
-    let flags = record.flags();
-
-    counts.read += 1;
-
-    if !flags.is_unmapped() {
-        counts.mapped += 1;
-    }
-
-    if flags.is_duplicate() {
-        counts.duplicate += 1;
-    }
-
-    if flags.is_secondary() {
-        counts.secondary += 1;
-    } else if flags.is_supplementary() {
-        counts.supplementary += 1;
-    } else if flags.is_paired() {
-        counts.paired += 1;
-
-        if flags.is_read_1() {
-            counts.read_1 += 1;
-        }
-
-        if flags.is_read_2() {
-            counts.read_2 += 1;
-        }
-
-        if !flags.is_unmapped() {
-            if flags.is_proper_pair() {
-                counts.proper_pair += 1;
-            }
-
-            if flags.is_mate_unmapped() {
-                counts.singleton += 1;
-            } else {
-                counts.mate_mapped += 1;
-
-                if record.mate_reference_sequence_id() != record.reference_sequence_id() {
-                    counts.mate_reference_sequence_id_mismatch += 1;
-
-                    if u8::from(record.mapping_quality()) >= 5 {
-                        counts.mate_reference_sequence_id_mismatch_hq += 1;
-                    }
-                }
-            }
-        }
-    }
-}
+use noodles_bam::{self as bam, stats::FlagCounts};
 
 struct PercentageFormat(u64, u64);
 
@@ -86,7 +19,7 @@ impl fmt::Display for PercentageFormat {
     }
 }
 
-fn print_stats(qc_pass_counts: &Counts, qc_fail_counts: &Counts) {
+fn print_stats(qc_pass_counts: &FlagCounts, qc_fail_counts: &FlagCounts) {
     println!(
         "{} + {} in total (QC-passed reads + QC-failed reads)",
         qc_pass_counts.read, qc_fail_counts.read
@@ -159,16 +92,16 @@ fn main() -> io::Result<()> {
     reader.read_header()?;
     reader.read_reference_sequences()?;
 
-    let mut qc_pass_counts = Counts::default();
-    let mut qc_fail_counts = Counts::default();
+    let mut qc_pass_counts = FlagCounts::default();
+    let mut qc_fail_counts = FlagCounts::default();
 
     for result in reader.records() {
         let record = result?;
 
         if record.flags().is_qc_fail() {
-            count(&mut qc_fail_counts, &record);
+            qc_fail_counts.add(&record);
         } else {
-            count(&mut qc_pass_counts, &record);
+            qc_pass_counts.add(&record);
         }
     }
 
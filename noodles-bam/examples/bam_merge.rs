@@ -0,0 +1,29 @@
+//! Merges coordinate-sorted BAM files.
+//!
+//! This is similar to the functionality of `samtools merge`. The merged BAM is written to stdout.
+//!
+//! Verify the output by piping to `samtools view -h --no-PG`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam::{self as bam, merge::Merger};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let srcs: Vec<_> = env::args().skip(1).collect();
+    assert!(!srcs.is_empty(), "missing srcs");
+
+    let readers = srcs
+        .iter()
+        .map(|src| File::open(src).map(bam::Reader::new))
+        .collect::<io::Result<_>>()?;
+
+    let merger = Merger::new(readers)?;
+
+    let stdout = io::stdout();
+    let handle = stdout.lock();
+    let mut writer = bam::Writer::new(handle);
+
+    merger.merge(&mut writer)?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Queries a BAM file with the regions in a BED file.
+//!
+//! The input BAM must have an index in the same directory.
+//!
+//! Each record that intersects at least one region is printed once with the (1-based) numbers of
+//! the BED regions it intersects. The records printed match the output of `samtools view -M -L
+//! <bed> <src>`.
+
+use std::{
+    env,
+    ffi::CStr,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use noodles::Region;
+use noodles_bam::{self as bam, bai};
+use noodles_sam as sam;
+
+fn read_bed_regions(src: PathBuf) -> Result<Vec<Region>, Box<dyn std::error::Error>> {
+    let reader = File::open(src).map(BufReader::new)?;
+    let mut regions = Vec::new();
+
+    for result in reader.lines() {
+        let line = result?;
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("track") {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+
+        let name = fields.next().ok_or("missing reference sequence name")?;
+        let start: u64 = fields.next().ok_or("missing start")?.parse()?;
+        let end: u64 = fields.next().ok_or("missing end")?.parse()?;
+
+        // BED intervals are 0-based, half-open.
+        regions.push(Region::mapped(name, start + 1, end));
+    }
+
+    Ok(regions)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).map(PathBuf::from).expect("missing src");
+    let bed_src = args.next().map(PathBuf::from).expect("missing bed");
+
+    let mut reader = File::open(&src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    let reference_sequences = header.reference_sequences();
+
+    let index = bai::read(src.with_extension("bam.bai"))?;
+
+    let regions = read_bed_regions(bed_src)?;
+    let query = reader.query_regions(reference_sequences, &index, &regions)?;
+
+    for result in query.hits() {
+        let (record, region_indices) = result?;
+
+        let read_name = CStr::from_bytes_with_nul(record.read_name())?.to_str()?;
+        let region_numbers: Vec<_> = region_indices.iter().map(|i| (i + 1).to_string()).collect();
+
+        println!("{}\t{}", read_name, region_numbers.join(","));
+    }
+
+    Ok(())
+}
@@ -32,6 +32,7 @@ pub use self::{index::Index, reader::Reader, writer::Writer};
 use std::{fs::File, io, path::Path};
 
 use noodles_bgzf::VirtualPosition;
+use noodles_csi as csi;
 
 use self::index::reference_sequence::{bin::Chunk, Bin};
 
@@ -123,36 +124,15 @@ pub fn merge_chunks(chunks: &[Chunk]) -> Vec<Chunk> {
 /// assert_eq!(actual, expected);
 /// ```
 pub fn optimize_chunks(chunks: &[Chunk], min_offset: VirtualPosition) -> Vec<Chunk> {
-    let mut chunks: Vec<_> = chunks
+    let chunks: Vec<_> = chunks
         .iter()
-        .filter(|c| c.end() > min_offset)
-        .copied()
+        .map(|chunk| csi::index::reference_sequence::bin::Chunk::new(chunk.start(), chunk.end()))
         .collect();
 
-    if chunks.is_empty() {
-        return chunks;
-    }
-
-    chunks.sort_unstable_by_key(|c| c.start());
-
-    // At worst, no chunks are merged, and the resulting list will be the same size as the input.
-    let mut merged_chunks = Vec::with_capacity(chunks.len());
-
-    // `chunks` is guaranteed to be non-empty.
-    let mut current_chunk = chunks[0];
-
-    for next_chunk in chunks.iter().skip(1) {
-        if next_chunk.start() > current_chunk.end() {
-            merged_chunks.push(current_chunk);
-            current_chunk = *next_chunk;
-        } else if current_chunk.end() < next_chunk.end() {
-            current_chunk = Chunk::new(current_chunk.start(), next_chunk.end());
-        }
-    }
-
-    merged_chunks.push(current_chunk);
-
-    merged_chunks
+    csi::binning_index::optimize_chunks(&chunks, min_offset)
+        .into_iter()
+        .map(|chunk| Chunk::new(chunk.start(), chunk.end()))
+        .collect()
 }
 
 #[cfg(test)]
@@ -5,6 +5,10 @@ pub mod reference_sequence;
 
 pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
 
+use std::io;
+
+use noodles_csi::{self as csi, binning_index::optimize_chunks, BinningIndex};
+
 /// A BAM index.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Index {
@@ -72,3 +76,97 @@ impl Index {
         self.n_no_coor
     }
 }
+
+impl BinningIndex for Index {
+    /// Returns the optimized chunks that may contain records intersecting the given interval.
+    ///
+    /// The chunks are converted to CSI chunks so that a BAM index can be used wherever a binning
+    /// index is accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::bai;
+    /// use noodles_csi::BinningIndex;
+    ///
+    /// let index = bai::Index::new(vec![bai::index::ReferenceSequence::new(Vec::new(), Vec::new(), None)], None);
+    /// assert!(index.query(0, 8, 13)?.is_empty());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn query(
+        &self,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+    ) -> io::Result<Vec<csi::index::reference_sequence::bin::Chunk>> {
+        if start < 1 || start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid interval: [{}, {}]", start, end),
+            ));
+        }
+
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let chunks: Vec<_> = reference_sequence
+            .query(start, end)
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .map(|chunk| {
+                csi::index::reference_sequence::bin::Chunk::new(chunk.start(), chunk.end())
+            })
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(start);
+
+        Ok(optimize_chunks(&chunks, min_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf::VirtualPosition;
+
+    use super::{
+        reference_sequence::{bin::Chunk, Bin},
+        *,
+    };
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let bins = vec![Bin::new(
+            4681,
+            vec![Chunk::new(
+                VirtualPosition::from(8),
+                VirtualPosition::from(13),
+            )],
+        )];
+        let intervals = vec![VirtualPosition::from(8)];
+        let index = Index::new(vec![ReferenceSequence::new(bins, intervals, None)], None);
+
+        let actual = index.query(0, 1, 1)?;
+        let expected = [csi::index::reference_sequence::bin::Chunk::new(
+            VirtualPosition::from(8),
+            VirtualPosition::from(13),
+        )];
+        assert_eq!(actual, expected);
+
+        assert!(index.query(0, 16385, 16390)?.is_empty());
+        assert!(index.query(1, 1, 1).is_err());
+        assert!(index.query(0, 0, 1).is_err());
+
+        Ok(())
+    }
+}
@@ -75,8 +75,11 @@ impl Builder {
     /// let index = bai::Index::builder().build(1);
     /// ```
     pub fn build(mut self, reference_sequence_count: usize) -> Index {
-        let last_reference_sequence_id = ReferenceSequenceId::from(reference_sequence_count as i32);
-        self.add_reference_sequences_builders_until(last_reference_sequence_id);
+        if reference_sequence_count > 0 {
+            let last_reference_sequence_id =
+                ReferenceSequenceId::from(reference_sequence_count as i32 - 1);
+            self.add_reference_sequences_builders_until(last_reference_sequence_id);
+        }
 
         let reference_sequences = self
             .reference_sequences_builders
@@ -90,3 +93,17 @@ impl Builder {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let index = Builder::default().build(0);
+        assert!(index.reference_sequences().is_empty());
+
+        let index = Builder::default().build(3);
+        assert_eq!(index.reference_sequences().len(), 3);
+    }
+}
@@ -0,0 +1,600 @@
+//! MD and NM tag calculation.
+//!
+//! This calculates the mismatched positions (`MD`) and edit distance (`NM`) of an alignment from
+//! its reference sequence, like `samtools calmd`. Tags of both BAM and SAM records can be
+//! calculated, verified against existing values, or fixed.
+//!
+//! The calculation follows `samtools calmd`: bases are compared case-insensitively, a `=` read
+//! base always matches, and `N` never matches, not even an `N` in the reference sequence.
+//! Reference skips (`N` operations) are not included in either tag.
+
+use std::{convert::TryFrom, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    record::{
+        cigar::{self, op::Kind, Op},
+        data::field::Tag,
+    },
+};
+
+use super::{
+    record::data::{field::Value, Field},
+    Record,
+};
+
+/// Calculated MD and NM tag values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tags {
+    mismatched_positions: String,
+    edit_distance: u32,
+}
+
+impl Tags {
+    /// Returns the mismatched positions (`MD`).
+    pub fn mismatched_positions(&self) -> &str {
+        &self.mismatched_positions
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+/// A difference between an existing tag value of a record and its calculated value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TagMismatch {
+    /// The mismatched positions (`MD`) are missing or differ.
+    MismatchedPositions {
+        /// The existing value, if any.
+        actual: Option<String>,
+        /// The calculated value.
+        expected: String,
+    },
+    /// The edit distance (`NM`) is missing or differs.
+    EditDistance {
+        /// The existing value, if any.
+        actual: Option<i64>,
+        /// The calculated value.
+        expected: u32,
+    },
+}
+
+/// Calculates the MD and NM tags of a BAM record.
+///
+/// The given reference sequence must be the one the record is mapped to.
+///
+/// # Errors
+///
+/// An error is returned if the record is unmapped, has no sequence, or its alignment extends past
+/// the end of the reference sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{
+///     self as bam, calmd,
+///     record::{cigar::Op, sequence::Base},
+/// };
+/// use noodles_fasta as fasta;
+/// use noodles_sam::record::{cigar::op::Kind, Flags, Position};
+///
+/// let reference_sequence = fasta::Record::new(
+///     fasta::record::Definition::new(String::from("sq0"), None),
+///     b"ACGTACGT".to_vec(),
+/// );
+///
+/// let record = bam::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(bam::record::ReferenceSequenceId::from(0))
+///     .set_position(Position::from(2))
+///     .set_cigar(vec![Op::new(Kind::Match, 4)])
+///     .set_sequence(vec![Base::C, Base::G, Base::A, Base::A])
+///     .build()?;
+///
+/// let tags = calmd::calculate(&reference_sequence, &record)?;
+/// assert_eq!(tags.mismatched_positions(), "2T1");
+/// assert_eq!(tags.edit_distance(), 1);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn calculate(reference_sequence: &fasta::Record, record: &Record) -> io::Result<Tags> {
+    if record.flags().is_unmapped() {
+        return Err(unmapped_error());
+    }
+
+    let position = i32::from(record.position());
+    let ops: Vec<_> = record.cigar().ops().map(Op::from).collect();
+    let bases: Vec<_> = record
+        .sequence()
+        .bases()
+        .map(|base| char::from(base) as u8)
+        .collect();
+
+    calculate_tags(reference_sequence.sequence(), position, &ops, &bases)
+}
+
+/// Calculates the MD and NM tags of a SAM record.
+///
+/// This is the same as [`calculate`] but for a SAM record.
+///
+/// [`calculate`]: fn.calculate.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::calmd;
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{self as sam, record::{Flags, Position}};
+///
+/// let reference_sequence = fasta::Record::new(
+///     fasta::record::Definition::new(String::from("sq0"), None),
+///     b"ACGTACGT".to_vec(),
+/// );
+///
+/// let record = sam::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_name("sq0".parse()?)
+///     .set_position(Position::from(2))
+///     .set_cigar("2M1D2M".parse()?)
+///     .set_sequence("CGAA".parse()?)
+///     .build();
+///
+/// let tags = calmd::calculate_sam(&reference_sequence, &record)?;
+/// assert_eq!(tags.mismatched_positions(), "2^T1C0");
+/// assert_eq!(tags.edit_distance(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate_sam(reference_sequence: &fasta::Record, record: &sam::Record) -> io::Result<Tags> {
+    if record.flags().is_unmapped() {
+        return Err(unmapped_error());
+    }
+
+    let position = record.position().unwrap_or_default();
+    let bases: Vec<_> = record
+        .sequence()
+        .iter()
+        .map(|&base| char::from(base) as u8)
+        .collect();
+
+    calculate_tags(
+        reference_sequence.sequence(),
+        position,
+        record.cigar(),
+        &bases,
+    )
+}
+
+/// Compares the MD and NM tags of a BAM record to their calculated values.
+///
+/// Unmapped records are not checked.
+///
+/// # Errors
+///
+/// An error is returned if the record data is invalid or the tags cannot be calculated.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, calmd};
+/// use noodles_fasta as fasta;
+///
+/// let reference_sequence = fasta::Record::new(
+///     fasta::record::Definition::new(String::from("sq0"), None),
+///     b"ACGT".to_vec(),
+/// );
+///
+/// let record = bam::Record::default();
+/// assert!(calmd::verify(&reference_sequence, &record)?.is_empty());
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn verify(reference_sequence: &fasta::Record, record: &Record) -> io::Result<Vec<TagMismatch>> {
+    if record.flags().is_unmapped() {
+        return Ok(Vec::new());
+    }
+
+    let tags = calculate(reference_sequence, record)?;
+
+    let mut mismatched_positions = None;
+    let mut edit_distance = None;
+
+    for result in record.data().fields() {
+        let field = result?;
+
+        match field.tag() {
+            Tag::MismatchedPositions => {
+                mismatched_positions = field.value().as_str().map(String::from);
+            }
+            Tag::EditDistance => edit_distance = field.value().as_int(),
+            _ => {}
+        }
+    }
+
+    Ok(compare(&tags, mismatched_positions, edit_distance))
+}
+
+/// Compares the MD and NM tags of a SAM record to their calculated values.
+///
+/// This is the same as [`verify`] but for a SAM record.
+///
+/// [`verify`]: fn.verify.html
+pub fn verify_sam(
+    reference_sequence: &fasta::Record,
+    record: &sam::Record,
+) -> io::Result<Vec<TagMismatch>> {
+    use sam::record::data::field::Value;
+
+    if record.flags().is_unmapped() {
+        return Ok(Vec::new());
+    }
+
+    let tags = calculate_sam(reference_sequence, record)?;
+
+    let mut mismatched_positions = None;
+    let mut edit_distance = None;
+
+    for field in record.data().iter() {
+        match (field.tag(), field.value()) {
+            (Tag::MismatchedPositions, Value::String(s)) => mismatched_positions = Some(s.clone()),
+            (Tag::EditDistance, Value::Int32(n)) => edit_distance = Some(i64::from(*n)),
+            _ => {}
+        }
+    }
+
+    Ok(compare(&tags, mismatched_positions, edit_distance))
+}
+
+/// Sets the MD and NM tags of a BAM record to their calculated values.
+///
+/// This returns the differences between the previous and calculated values. Unmapped records are
+/// not changed.
+///
+/// # Errors
+///
+/// An error is returned if the record data is invalid or the tags cannot be calculated.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{
+///     self as bam, calmd,
+///     record::{cigar::Op, sequence::Base},
+/// };
+/// use noodles_fasta as fasta;
+/// use noodles_sam::record::{cigar::op::Kind, Flags, Position};
+///
+/// let reference_sequence = fasta::Record::new(
+///     fasta::record::Definition::new(String::from("sq0"), None),
+///     b"ACGT".to_vec(),
+/// );
+///
+/// let mut record = bam::Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(bam::record::ReferenceSequenceId::from(0))
+///     .set_position(Position::from(1))
+///     .set_cigar(vec![Op::new(Kind::Match, 2)])
+///     .set_sequence(vec![Base::A, Base::C])
+///     .build()?;
+///
+/// let mismatches = calmd::fix(&reference_sequence, &mut record)?;
+/// assert_eq!(mismatches.len(), 2);
+///
+/// assert!(calmd::verify(&reference_sequence, &record)?.is_empty());
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn fix(
+    reference_sequence: &fasta::Record,
+    record: &mut Record,
+) -> io::Result<Vec<TagMismatch>> {
+    let mismatches = verify(reference_sequence, record)?;
+
+    if mismatches.is_empty() {
+        return Ok(mismatches);
+    }
+
+    let tags = calculate(reference_sequence, record)?;
+
+    record.insert_data_field(Field::new(
+        Tag::MismatchedPositions,
+        Value::String(tags.mismatched_positions),
+    ))?;
+
+    record.insert_data_field(Field::new(
+        Tag::EditDistance,
+        Value::Int32(tags.edit_distance as i32),
+    ))?;
+
+    Ok(mismatches)
+}
+
+/// Sets the MD and NM tags of a SAM record to their calculated values.
+///
+/// This is the same as [`fix`] but for a SAM record.
+///
+/// [`fix`]: fn.fix.html
+pub fn fix_sam(
+    reference_sequence: &fasta::Record,
+    record: &mut sam::Record,
+) -> io::Result<Vec<TagMismatch>> {
+    use sam::record::data::{field::Value, Field};
+
+    let mismatches = verify_sam(reference_sequence, record)?;
+
+    if mismatches.is_empty() {
+        return Ok(mismatches);
+    }
+
+    let tags = calculate_sam(reference_sequence, record)?;
+
+    let data = record.data_mut();
+
+    data.insert(Field::new(
+        Tag::MismatchedPositions,
+        Value::String(tags.mismatched_positions),
+    ));
+
+    data.insert(Field::new(
+        Tag::EditDistance,
+        Value::Int32(tags.edit_distance as i32),
+    ));
+
+    Ok(mismatches)
+}
+
+fn unmapped_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "record is unmapped")
+}
+
+fn compare(
+    tags: &Tags,
+    mismatched_positions: Option<String>,
+    edit_distance: Option<i64>,
+) -> Vec<TagMismatch> {
+    let mut mismatches = Vec::new();
+
+    if mismatched_positions.as_deref() != Some(tags.mismatched_positions()) {
+        mismatches.push(TagMismatch::MismatchedPositions {
+            actual: mismatched_positions,
+            expected: tags.mismatched_positions.clone(),
+        });
+    }
+
+    if edit_distance != Some(i64::from(tags.edit_distance)) {
+        mismatches.push(TagMismatch::EditDistance {
+            actual: edit_distance,
+            expected: tags.edit_distance,
+        });
+    }
+
+    mismatches
+}
+
+// Calculates the MD and NM tags of an alignment starting at the 1-based `position`.
+fn calculate_tags(
+    reference_sequence: &[u8],
+    position: i32,
+    ops: &[Op],
+    bases: &[u8],
+) -> io::Result<Tags> {
+    if bases.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record sequence is missing",
+        ));
+    }
+
+    if cigar::query_len(ops.iter().copied()) as usize != bases.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "CIGAR read length does not match sequence length",
+        ));
+    }
+
+    let out_of_bounds = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "alignment is out of reference sequence bounds",
+        )
+    };
+
+    let mut reference_position = position
+        .checked_sub(1)
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(out_of_bounds)?;
+    let mut read_position = 0;
+
+    let mut mismatched_positions = String::new();
+    let mut edit_distance = 0;
+    let mut match_count = 0;
+
+    for op in ops {
+        let len = op.len() as usize;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                let reference_bases = reference_sequence
+                    .get(reference_position..reference_position + len)
+                    .ok_or_else(out_of_bounds)?;
+
+                let read_bases = &bases[read_position..read_position + len];
+
+                for (&r, &b) in reference_bases.iter().zip(read_bases) {
+                    if is_match(r, b) {
+                        match_count += 1;
+                    } else {
+                        mismatched_positions.push_str(&match_count.to_string());
+                        mismatched_positions.push(r.to_ascii_uppercase() as char);
+                        match_count = 0;
+                        edit_distance += 1;
+                    }
+                }
+            }
+            Kind::Insertion => edit_distance += len as u32,
+            Kind::Deletion => {
+                let reference_bases = reference_sequence
+                    .get(reference_position..reference_position + len)
+                    .ok_or_else(out_of_bounds)?;
+
+                mismatched_positions.push_str(&match_count.to_string());
+                mismatched_positions.push('^');
+
+                for &r in reference_bases {
+                    mismatched_positions.push(r.to_ascii_uppercase() as char);
+                }
+
+                match_count = 0;
+                edit_distance += len as u32;
+            }
+            _ => {}
+        }
+
+        if op.kind().consumes_reference() {
+            reference_position += len;
+        }
+
+        if op.kind().consumes_read() {
+            read_position += len;
+        }
+    }
+
+    mismatched_positions.push_str(&match_count.to_string());
+
+    Ok(Tags {
+        mismatched_positions,
+        edit_distance,
+    })
+}
+
+fn is_match(reference_base: u8, read_base: u8) -> bool {
+    if read_base == b'=' {
+        return true;
+    }
+
+    let r = reference_base.to_ascii_uppercase();
+    let b = read_base.to_ascii_uppercase();
+
+    r == b && r != b'N'
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{Flags, Position};
+
+    use crate::record::{cigar::Op, sequence::Base, ReferenceSequenceId};
+
+    use super::*;
+
+    fn reference_sequence() -> fasta::Record {
+        fasta::Record::new(
+            fasta::record::Definition::new(String::from("sq0"), None),
+            b"ACGTACGTNNacgt".to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_calculate_tags() -> io::Result<()> {
+        let reference_sequence = reference_sequence();
+        let reference_sequence = reference_sequence.sequence();
+
+        // 1S3M1I2M2D2M
+        let ops = [
+            cigar::Op::new(Kind::SoftClip, 1),
+            cigar::Op::new(Kind::Match, 3),
+            cigar::Op::new(Kind::Insertion, 1),
+            cigar::Op::new(Kind::Match, 2),
+            cigar::Op::new(Kind::Deletion, 2),
+            cigar::Op::new(Kind::Match, 2),
+        ];
+        let tags = calculate_tags(reference_sequence, 1, &ops, b"TACATTTGT")?;
+        assert_eq!(tags.mismatched_positions(), "2G1A0^CG0T0N0");
+        assert_eq!(tags.edit_distance(), 7);
+
+        // 2M3N2M with `=` and lowercase bases
+        let ops = [
+            cigar::Op::new(Kind::Match, 2),
+            cigar::Op::new(Kind::Skip, 3),
+            cigar::Op::new(Kind::Match, 2),
+        ];
+        let tags = calculate_tags(reference_sequence, 1, &ops, b"=cCG")?;
+        assert_eq!(tags.mismatched_positions(), "4");
+        assert_eq!(tags.edit_distance(), 0);
+
+        // N never matches.
+        let ops = [cigar::Op::new(Kind::Match, 3)];
+        let tags = calculate_tags(reference_sequence, 8, &ops, b"TNN")?;
+        assert_eq!(tags.mismatched_positions(), "1N0N0");
+        assert_eq!(tags.edit_distance(), 2);
+
+        assert!(calculate_tags(reference_sequence, 13, &ops, b"GTA").is_err());
+        assert!(calculate_tags(reference_sequence, 0, &ops, b"ACG").is_err());
+        assert!(calculate_tags(reference_sequence, 1, &ops, b"").is_err());
+        assert!(calculate_tags(reference_sequence, 1, &ops, b"AC").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_and_fix() -> io::Result<()> {
+        let reference_sequence = reference_sequence();
+
+        let mut record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(ReferenceSequenceId::from(0))
+            .set_position(Position::from(1))
+            .set_cigar(vec![Op::new(Kind::Match, 4)])
+            .set_sequence(vec![Base::A, Base::C, Base::C, Base::T])
+            .set_data(vec![
+                Field::new(Tag::MismatchedPositions, Value::String(String::from("4"))),
+                Field::new(Tag::EditDistance, Value::UInt8(1)),
+            ])
+            .build()?;
+
+        let expected = [TagMismatch::MismatchedPositions {
+            actual: Some(String::from("4")),
+            expected: String::from("2G1"),
+        }];
+
+        assert_eq!(verify(&reference_sequence, &record)?, expected);
+        assert_eq!(fix(&reference_sequence, &mut record)?, expected);
+        assert!(verify(&reference_sequence, &record)?.is_empty());
+        assert!(fix(&reference_sequence, &mut record)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_and_fix_sam() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = reference_sequence();
+
+        let mut record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::from(2))
+            .set_cigar("3M".parse()?)
+            .set_sequence("CGA".parse()?)
+            .build();
+
+        let expected = [
+            TagMismatch::MismatchedPositions {
+                actual: None,
+                expected: String::from("2T0"),
+            },
+            TagMismatch::EditDistance {
+                actual: None,
+                expected: 1,
+            },
+        ];
+
+        assert_eq!(verify_sam(&reference_sequence, &record)?, expected);
+        assert_eq!(fix_sam(&reference_sequence, &mut record)?, expected);
+        assert_eq!(record.data().to_string(), "MD:Z:2T0\tNM:i:1");
+        assert!(verify_sam(&reference_sequence, &record)?.is_empty());
+
+        Ok(())
+    }
+}
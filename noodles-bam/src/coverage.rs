@@ -0,0 +1,406 @@
+//! BAM record coverage.
+//!
+//! Coverage is computed from coordinate-sorted records without building pileups. [`Depth`] emits
+//! per-base depths as runs of positions with the same depth ([`Block`]s), which can be reduced to
+//! fixed-size windows using [`Windows`] or to summaries of a list of regions using
+//! [`summarize_regions`].
+//!
+//! [`Depth`]: struct.Depth.html
+//! [`Block`]: struct.Block.html
+//! [`Windows`]: struct.Windows.html
+//! [`summarize_regions`]: fn.summarize_regions.html
+
+mod block;
+mod region_summary;
+mod windows;
+
+pub use self::{
+    block::Block,
+    region_summary::{summarize_regions, RegionSummary},
+    windows::{Window, Windows},
+};
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io,
+};
+
+use noodles_sam::record::{cigar::op::Kind, Flags, Position};
+
+use super::{sort::CoordinateSortCheck, Record};
+
+/// A per-base depth iterator over coordinate-sorted records.
+///
+/// Each item is a [`Block`] of consecutive positions with the same nonzero depth. Positions with
+/// no coverage are not emitted.
+///
+/// Only the start and end of each aligned segment are tracked, so memory use is proportional to
+/// the number of records that overlap the current position, not their lengths.
+///
+/// [`Block`]: struct.Block.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{self as bam, coverage::Depth};
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// reader.read_header()?;
+/// reader.read_reference_sequences()?;
+///
+/// let depth = Depth::new(reader.records()).set_min_mapping_quality(20);
+///
+/// for result in depth {
+///     let block = result?;
+///
+///     println!(
+///         "{}\t{}\t{}\t{}",
+///         block.reference_sequence_id(),
+///         i32::from(block.start()),
+///         i32::from(block.end()),
+///         block.depth()
+///     );
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Depth<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    records: I,
+    sort_check: CoordinateSortCheck,
+    reference_sequence_id: usize,
+    // The change in depth at each position not yet emitted.
+    deltas: BTreeMap<i32, i64>,
+    // The depth at `position`.
+    depth: i64,
+    position: i32,
+    blocks: VecDeque<Block>,
+    is_eof: bool,
+    flag_filter: Flags,
+    min_mapping_quality: u8,
+    include_deletions: bool,
+}
+
+impl<I> Depth<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    /// Creates a depth iterator.
+    ///
+    /// By default, unmapped, secondary, QC fail, and duplicate records are skipped; there is no
+    /// mapping quality threshold; and deletions do not count toward depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, coverage::Depth};
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let mut depth = Depth::new(records.into_iter());
+    ///
+    /// assert!(depth.next().is_none());
+    /// ```
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            sort_check: CoordinateSortCheck::default(),
+            reference_sequence_id: 0,
+            deltas: BTreeMap::new(),
+            depth: 0,
+            position: 0,
+            blocks: VecDeque::new(),
+            is_eof: false,
+            flag_filter: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            min_mapping_quality: 0,
+            include_deletions: false,
+        }
+    }
+
+    /// Sets the flags of records to skip.
+    ///
+    /// A record is skipped if any of its flags are in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, coverage::Depth};
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let depth = Depth::new(records.into_iter()).set_flag_filter(Flags::UNMAPPED);
+    /// ```
+    pub fn set_flag_filter(mut self, flag_filter: Flags) -> Self {
+        self.flag_filter = flag_filter;
+        self
+    }
+
+    /// Sets the minimum mapping quality of records.
+    ///
+    /// Records with a lower mapping quality are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, coverage::Depth};
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let depth = Depth::new(records.into_iter()).set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets whether deletions count toward depth.
+    ///
+    /// Reference skips (`N`) never count toward depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, coverage::Depth};
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let depth = Depth::new(records.into_iter()).set_include_deletions(true);
+    /// ```
+    pub fn set_include_deletions(mut self, include_deletions: bool) -> Self {
+        self.include_deletions = include_deletions;
+        self
+    }
+
+    fn is_filtered(&self, record: &Record) -> bool {
+        record.flags().intersects(self.flag_filter)
+            || u8::from(record.mapping_quality()) < self.min_mapping_quality
+    }
+
+    fn add_record(&mut self, record: &Record) -> io::Result<()> {
+        self.sort_check.add(record, io::ErrorKind::InvalidData)?;
+
+        let reference_sequence_id = match *record.reference_sequence_id() {
+            Some(id) => id as usize,
+            None => return Ok(()),
+        };
+
+        let start = i32::from(record.position());
+
+        if self.is_filtered(record) {
+            return Ok(());
+        }
+
+        if reference_sequence_id == self.reference_sequence_id {
+            // No later record can change the depth before its start.
+            self.flush(Some(start));
+        } else {
+            self.flush(None);
+            self.reference_sequence_id = reference_sequence_id;
+        }
+
+        let mut position = start;
+
+        for op in record.cigar().ops() {
+            let len = op.len() as i32;
+
+            match op.kind() {
+                Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                    self.add_segment(position, len);
+                    position += len;
+                }
+                Kind::Deletion => {
+                    if self.include_deletions {
+                        self.add_segment(position, len);
+                    }
+
+                    position += len;
+                }
+                Kind::Skip => position += len,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_segment(&mut self, start: i32, len: i32) {
+        if len > 0 {
+            self.add_delta(start, 1);
+            self.add_delta(start + len, -1);
+        }
+    }
+
+    fn add_delta(&mut self, position: i32, delta: i64) {
+        let value = self.deltas.entry(position).or_insert(0);
+        *value += delta;
+
+        if *value == 0 {
+            self.deltas.remove(&position);
+        }
+    }
+
+    // Emits blocks for the positions before `end`, or all positions if `end` is `None`.
+    fn flush(&mut self, end: Option<i32>) {
+        while let Some((&position, &delta)) = self.deltas.iter().next() {
+            if let Some(end) = end {
+                if position >= end {
+                    break;
+                }
+            }
+
+            self.deltas.remove(&position);
+
+            if self.depth > 0 && self.position < position {
+                self.blocks.push_back(Block {
+                    reference_sequence_id: self.reference_sequence_id,
+                    start: Position::from(self.position),
+                    end: Position::from(position - 1),
+                    depth: self.depth as u32,
+                });
+            }
+
+            self.depth += delta;
+            self.position = position;
+        }
+    }
+}
+
+impl<I> Iterator for Depth<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.blocks.pop_front() {
+                return Some(Ok(block));
+            }
+
+            if self.is_eof {
+                return None;
+            }
+
+            match self.records.next() {
+                Some(Ok(record)) => {
+                    if let Err(e) = self.add_record(&record) {
+                        return Some(Err(e));
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.flush(None);
+                    self.is_eof = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use noodles_sam::record::MappingQuality;
+
+    use crate::record::aligned_record_builder;
+
+    use super::*;
+
+    pub fn block(reference_sequence_id: usize, start: i32, end: i32, depth: u32) -> Block {
+        Block {
+            reference_sequence_id,
+            start: Position::from(start),
+            end: Position::from(end),
+            depth,
+        }
+    }
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            // 4M2D2M
+            aligned_record_builder(
+                0,
+                1,
+                &[(Kind::Match, 4), (Kind::Deletion, 2), (Kind::Match, 2)],
+            )
+            .build()?,
+            // 1S3M3N2M
+            aligned_record_builder(
+                0,
+                3,
+                &[
+                    (Kind::SoftClip, 1),
+                    (Kind::Match, 3),
+                    (Kind::Skip, 3),
+                    (Kind::Match, 2),
+                ],
+            )
+            .build()?,
+            aligned_record_builder(0, 20, &[(Kind::Match, 2)]).build()?,
+            aligned_record_builder(1, 5, &[(Kind::Match, 2)]).build()?,
+        ];
+
+        let actual: Vec<_> =
+            Depth::new(records.clone().into_iter().map(Ok)).collect::<io::Result<_>>()?;
+
+        let expected = [
+            block(0, 1, 2, 1),
+            block(0, 3, 4, 2),
+            block(0, 5, 5, 1),
+            block(0, 7, 10, 1),
+            block(0, 20, 21, 1),
+            block(1, 5, 6, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let actual: Vec<_> = Depth::new(records.into_iter().map(Ok))
+            .set_include_deletions(true)
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            block(0, 1, 2, 1),
+            block(0, 3, 5, 2),
+            block(0, 6, 10, 1),
+            block(0, 20, 21, 1),
+            block(1, 5, 6, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> Result<(), Box<dyn std::error::Error>> {
+        let mut duplicate = aligned_record_builder(0, 1, &[(Kind::Match, 4)]).build()?;
+        duplicate.set_flags(Flags::DUPLICATE);
+
+        let mut low_mapping_quality = aligned_record_builder(0, 2, &[(Kind::Match, 4)]).build()?;
+        low_mapping_quality.set_mapping_quality(MappingQuality::from(5));
+
+        let records = vec![
+            duplicate,
+            low_mapping_quality,
+            aligned_record_builder(0, 3, &[(Kind::Match, 4)]).build()?,
+        ];
+
+        let actual: Vec<_> = Depth::new(records.into_iter().map(Ok))
+            .set_min_mapping_quality(10)
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(actual, [block(0, 3, 6, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            aligned_record_builder(0, 8, &[(Kind::Match, 4)]).build()?,
+            aligned_record_builder(0, 3, &[(Kind::Match, 4)]).build()?,
+        ];
+
+        let mut depth = Depth::new(records.into_iter().map(Ok));
+        assert!(depth.next().unwrap().is_err());
+
+        Ok(())
+    }
+}
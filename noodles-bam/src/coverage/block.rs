@@ -0,0 +1,48 @@
+use noodles_sam::record::Position;
+
+/// A run of consecutive reference positions with the same depth.
+///
+/// Per-base depths are the depth of the block at each position in `start..=end`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub(super) reference_sequence_id: usize,
+    pub(super) start: Position,
+    pub(super) end: Position,
+    pub(super) depth: u32,
+}
+
+impl Block {
+    /// Returns the reference sequence ID of the block.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the 1-based start position of the block.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the 1-based end position (inclusive) of the block.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the number of positions in the block.
+    pub fn len(&self) -> u32 {
+        (i32::from(self.end) - i32::from(self.start) + 1) as u32
+    }
+
+    /// Returns whether the block has no positions.
+    ///
+    /// Blocks emitted by [`Depth`] are never empty.
+    ///
+    /// [`Depth`]: struct.Depth.html
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the depth at each position of the block.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
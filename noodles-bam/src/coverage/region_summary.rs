@@ -0,0 +1,253 @@
+use std::io;
+
+use noodles::Region;
+use noodles_sam::header::ReferenceSequences;
+
+use crate::reader::{resolve_region, Interval, Intervals};
+
+use super::Block;
+
+/// A depth summary of a region.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RegionSummary {
+    len: u64,
+    depth_sum: u64,
+    covered_len: u64,
+    min_covered_depth: Option<u32>,
+    max_depth: u32,
+}
+
+impl RegionSummary {
+    /// Returns the number of positions in the region.
+    ///
+    /// Positions past the end of the reference sequence are not counted.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the region has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the sum of the depths of all positions in the region.
+    pub fn depth_sum(&self) -> u64 {
+        self.depth_sum
+    }
+
+    /// Returns the number of positions in the region with a depth > 0.
+    pub fn covered_len(&self) -> u64 {
+        self.covered_len
+    }
+
+    /// Returns the minimum depth of the positions in the region.
+    pub fn min_depth(&self) -> u32 {
+        if self.covered_len < self.len {
+            0
+        } else {
+            self.min_covered_depth.unwrap_or_default()
+        }
+    }
+
+    /// Returns the maximum depth of the positions in the region.
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// Returns the mean depth of the positions in the region.
+    ///
+    /// This is 0 if the region is empty.
+    pub fn mean_depth(&self) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            self.depth_sum as f64 / self.len as f64
+        }
+    }
+
+    fn add(&mut self, len: u64, depth: u32) {
+        self.depth_sum += len * u64::from(depth);
+        self.covered_len += len;
+        self.max_depth = self.max_depth.max(depth);
+        self.min_covered_depth = Some(
+            self.min_covered_depth
+                .map(|n| n.min(depth))
+                .unwrap_or(depth),
+        );
+    }
+}
+
+/// Summarizes the depth of each of a list of regions.
+///
+/// The blocks must be coordinate-sorted, e.g., as emitted by [`Depth`]. Regions may overlap and
+/// are returned in the given order.
+///
+/// [`Depth`]: struct.Depth.html
+///
+/// # Errors
+///
+/// An error is returned if a region is not mapped or its reference sequence does not exist.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles::Region;
+/// use noodles_bam::{self as bam, coverage::{self, Depth}};
+/// use noodles_sam as sam;
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// let header: sam::Header = reader.read_header()?.parse()?;
+/// reader.read_reference_sequences()?;
+///
+/// let regions = [Region::mapped("sq0", 8, 13), Region::mapped("sq1", 21, 34)];
+///
+/// let depth = Depth::new(reader.records());
+/// let summaries = coverage::summarize_regions(depth, header.reference_sequences(), &regions)?;
+///
+/// for (region, summary) in regions.iter().zip(&summaries) {
+///     println!("{}\t{}", region, summary.mean_depth());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn summarize_regions<I>(
+    blocks: I,
+    reference_sequences: &ReferenceSequences,
+    regions: &[Region],
+) -> io::Result<Vec<RegionSummary>>
+where
+    I: IntoIterator<Item = io::Result<Block>>,
+{
+    let mut summaries = vec![RegionSummary::default(); regions.len()];
+    let mut intervals = Vec::with_capacity(regions.len());
+
+    for (index, region) in regions.iter().enumerate() {
+        let (reference_sequence_id, start, end) = resolve_region(reference_sequences, region)?;
+
+        let reference_sequence_len = reference_sequences
+            .get_index(reference_sequence_id)
+            .map(|(_, reference_sequence)| reference_sequence.len().max(0) as u64)
+            .unwrap_or_default();
+
+        let start = start.max(1);
+        let end = end.min(reference_sequence_len);
+
+        summaries[index].len = (end + 1).saturating_sub(start);
+
+        // An inverted region or one that starts past the end of the reference sequence is empty
+        // and cannot overlap any block.
+        if start > end {
+            continue;
+        }
+
+        intervals.push(Interval {
+            reference_sequence_id,
+            start,
+            end,
+            index,
+        });
+    }
+
+    let intervals = Intervals::new(intervals);
+
+    for result in blocks {
+        let block = result?;
+
+        let start = i32::from(block.start) as u64;
+        let end = i32::from(block.end) as u64;
+
+        for interval in intervals.overlapping(block.reference_sequence_id, start, end) {
+            let len = end.min(interval.end) - start.max(interval.start) + 1;
+            summaries[interval.index].add(len, block.depth);
+        }
+    }
+
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::header::ReferenceSequence;
+
+    use super::{super::tests::block, *};
+
+    #[test]
+    fn test_summarize_regions() -> io::Result<()> {
+        let mut reference_sequences = ReferenceSequences::default();
+        reference_sequences.insert(
+            String::from("sq0"),
+            ReferenceSequence::new(String::from("sq0"), 20),
+        );
+        reference_sequences.insert(
+            String::from("sq1"),
+            ReferenceSequence::new(String::from("sq1"), 8),
+        );
+
+        let blocks = vec![block(0, 3, 6, 2), block(0, 7, 7, 1), block(1, 1, 8, 3)];
+
+        let regions = [
+            Region::mapped("sq0", 1, 4),
+            Region::mapped("sq1", 5, 13),
+            Region::mapped("sq0", 3, 7),
+            Region::mapped("sq0", 10, 20),
+        ];
+
+        let summaries =
+            summarize_regions(blocks.into_iter().map(Ok), &reference_sequences, &regions)?;
+
+        let actual: Vec<_> = summaries
+            .iter()
+            .map(|s| {
+                (
+                    s.len(),
+                    s.depth_sum(),
+                    s.covered_len(),
+                    s.min_depth(),
+                    s.max_depth(),
+                )
+            })
+            .collect();
+
+        let expected = [
+            (4, 4, 2, 0, 2),
+            (4, 12, 4, 3, 3),
+            (5, 9, 5, 1, 2),
+            (11, 0, 0, 0, 0),
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(summaries[0].mean_depth(), 1.0);
+        assert_eq!(summaries[3].mean_depth(), 0.0);
+
+        let regions = [Region::mapped("sq2", 1, 4)];
+        assert!(summarize_regions(Vec::new(), &reference_sequences, &regions).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summarize_regions_with_empty_regions() -> io::Result<()> {
+        let mut reference_sequences = ReferenceSequences::default();
+        reference_sequences.insert(
+            String::from("sq0"),
+            ReferenceSequence::new(String::from("sq0"), 20),
+        );
+
+        // The last block overhangs the end of the reference sequence.
+        let blocks = vec![block(0, 3, 12, 1), block(0, 18, 25, 2)];
+
+        let regions = [Region::mapped("sq0", 10, 5), Region::mapped("sq0", 22, 30)];
+
+        let summaries =
+            summarize_regions(blocks.into_iter().map(Ok), &reference_sequences, &regions)?;
+
+        for summary in &summaries {
+            assert_eq!(summary.len(), 0);
+            assert_eq!(summary.depth_sum(), 0);
+            assert_eq!(summary.covered_len(), 0);
+            assert_eq!(summary.mean_depth(), 0.0);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use std::io;
+
+use noodles_sam::{header::ReferenceSequences, record::Position};
+
+use super::Block;
+
+/// The mean depth of a fixed-size window of a reference sequence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Window {
+    reference_sequence_id: usize,
+    start: Position,
+    end: Position,
+    mean_depth: f64,
+}
+
+impl Window {
+    /// Returns the reference sequence ID of the window.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the 1-based start position of the window.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the 1-based end position (inclusive) of the window.
+    ///
+    /// The last window of a reference sequence may be shorter than the window size.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the mean depth of the positions in the window.
+    pub fn mean_depth(&self) -> f64 {
+        self.mean_depth
+    }
+}
+
+/// An iterator over fixed-size windows of depth blocks.
+///
+/// Every window of every reference sequence is emitted, including windows with no coverage. The
+/// blocks must be coordinate-sorted, e.g., as emitted by [`Depth`].
+///
+/// [`Depth`]: struct.Depth.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{
+///     self as bam,
+///     coverage::{Depth, Windows},
+/// };
+/// use noodles_sam as sam;
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// let header: sam::Header = reader.read_header()?.parse()?;
+/// reader.read_reference_sequences()?;
+///
+/// let depth = Depth::new(reader.records());
+/// let windows = Windows::new(depth, header.reference_sequences(), 1000);
+///
+/// for result in windows {
+///     let window = result?;
+///     println!("{:?}", window);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Windows<I>
+where
+    I: Iterator<Item = io::Result<Block>>,
+{
+    blocks: I,
+    pending_block: Option<Block>,
+    reference_sequence_lengths: Vec<i32>,
+    window_size: i32,
+    reference_sequence_id: usize,
+    start: i32,
+}
+
+impl<I> Windows<I>
+where
+    I: Iterator<Item = io::Result<Block>>,
+{
+    /// Creates a windows iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{
+    ///     self as bam,
+    ///     coverage::{Depth, Windows},
+    /// };
+    /// use noodles_sam::header::ReferenceSequences;
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let depth = Depth::new(records.into_iter());
+    /// let mut windows = Windows::new(depth, &ReferenceSequences::default(), 1000);
+    ///
+    /// assert!(windows.next().is_none());
+    /// ```
+    pub fn new(blocks: I, reference_sequences: &ReferenceSequences, window_size: usize) -> Self {
+        assert!(window_size > 0, "window size must be > 0");
+
+        let reference_sequence_lengths = reference_sequences
+            .values()
+            .map(|reference_sequence| reference_sequence.len())
+            .collect();
+
+        Self {
+            blocks,
+            pending_block: None,
+            reference_sequence_lengths,
+            window_size: window_size.min(i32::MAX as usize) as i32,
+            reference_sequence_id: 0,
+            start: 1,
+        }
+    }
+
+    fn next_window(&mut self) -> io::Result<Option<Window>> {
+        let len = loop {
+            match self
+                .reference_sequence_lengths
+                .get(self.reference_sequence_id)
+            {
+                Some(&len) if len > 0 => break len,
+                Some(_) => self.reference_sequence_id += 1,
+                None => return Ok(None),
+            }
+        };
+
+        let start = self.start;
+        let end = start.saturating_add(self.window_size - 1).min(len);
+
+        let mut sum = 0;
+
+        loop {
+            if self.pending_block.is_none() {
+                self.pending_block = self.blocks.next().transpose()?;
+            }
+
+            let block = match self.pending_block {
+                Some(block) => block,
+                None => break,
+            };
+
+            let block_start = i32::from(block.start);
+            let block_end = i32::from(block.end);
+
+            if block.reference_sequence_id < self.reference_sequence_id
+                || (block.reference_sequence_id == self.reference_sequence_id && block_end < start)
+            {
+                // The block is past the end of its reference sequence.
+                self.pending_block = None;
+                continue;
+            }
+
+            if block.reference_sequence_id > self.reference_sequence_id || block_start > end {
+                break;
+            }
+
+            let overlap_len = block_end.min(end) - block_start.max(start) + 1;
+            sum += u64::from(block.depth) * overlap_len as u64;
+
+            if block_end > end {
+                break;
+            }
+
+            self.pending_block = None;
+        }
+
+        let window = Window {
+            reference_sequence_id: self.reference_sequence_id,
+            start: Position::from(start),
+            end: Position::from(end),
+            mean_depth: sum as f64 / f64::from(end - start + 1),
+        };
+
+        if end >= len {
+            self.reference_sequence_id += 1;
+            self.start = 1;
+        } else {
+            self.start = end + 1;
+        }
+
+        Ok(Some(window))
+    }
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator<Item = io::Result<Block>>,
+{
+    type Item = io::Result<Window>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_window().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::header::ReferenceSequence;
+
+    use super::{super::tests::block, *};
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reference_sequences = ReferenceSequences::default();
+        reference_sequences.insert(
+            String::from("sq0"),
+            ReferenceSequence::new(String::from("sq0"), 10),
+        );
+        reference_sequences.insert(
+            String::from("sq1"),
+            ReferenceSequence::new(String::from("sq1"), 4),
+        );
+
+        let blocks = vec![block(0, 3, 6, 2), block(0, 7, 7, 1), block(1, 4, 4, 3)];
+        let windows = Windows::new(blocks.into_iter().map(Ok), &reference_sequences, 4);
+
+        let actual: Vec<_> = windows
+            .map(|result| {
+                result.map(|window| {
+                    (
+                        window.reference_sequence_id(),
+                        i32::from(window.start()),
+                        i32::from(window.end()),
+                        window.mean_depth(),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (0, 1, 4, 1.0),
+            (0, 5, 8, 1.25),
+            (0, 9, 10, 0.0),
+            (1, 1, 4, 0.75),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
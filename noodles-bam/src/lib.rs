@@ -50,9 +50,15 @@
 //! ```
 
 pub mod bai;
+pub mod calmd;
+pub mod coverage;
+pub mod merge;
+pub mod pileup;
 pub mod reader;
 pub mod record;
-mod writer;
+pub mod sort;
+pub mod stats;
+pub mod writer;
 
 pub use self::{reader::Reader, record::Record, writer::Writer};
 
@@ -0,0 +1,833 @@
+//! BAM merging.
+//!
+//! Coordinate-sorted BAM files are merged into a single coordinate-sorted stream. The input
+//! headers are reconciled into a single header:
+//!
+//!   * Reference sequences (`@SQ`) are unified by name. A reference sequence that appears in more
+//!     than one input must have the same length in each, and the inputs must agree on the order of
+//!     the reference sequences they share.
+//!   * Read groups (`@RG`) and programs (`@PG`) are combined. Identical records are kept once. If
+//!     different records have the same ID, the later one is renamed, and its `RG` or `PG` data
+//!     fields are rewritten to match.
+//!   * Comments (`@CO`) are combined, skipping duplicates.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+use noodles_sam::{
+    self as sam,
+    header::{header::SortOrder, ReferenceSequence},
+    record::data::field::Tag,
+};
+
+use super::{
+    bai,
+    record::{
+        data::{field::Value, Field},
+        ReferenceSequenceId,
+    },
+    sort::{CoordinateSortCheck, Entry, Key, RecordKey},
+    Reader, Record, Writer,
+};
+
+/// A BAM merger.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{self as bam, merge::Merger};
+///
+/// let readers = vec![
+///     File::open("sample.1.bam").map(bam::Reader::new)?,
+///     File::open("sample.2.bam").map(bam::Reader::new)?,
+/// ];
+///
+/// let merger = Merger::new(readers)?;
+///
+/// let mut writer = File::create("sample.bam").map(bam::Writer::new)?;
+/// merger.merge(&mut writer)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Merger<R>
+where
+    R: Read,
+{
+    header: sam::Header,
+    inputs: Vec<Input<R>>,
+}
+
+struct Input<R>
+where
+    R: Read,
+{
+    reader: Reader<R>,
+    // The merged reference sequence ID for each input reference sequence ID.
+    reference_sequence_ids: Vec<i32>,
+    // Renamed read group and program IDs.
+    read_group_ids: HashMap<String, String>,
+    program_ids: HashMap<String, String>,
+    sort_check: CoordinateSortCheck,
+}
+
+impl<R> Merger<R>
+where
+    R: Read,
+{
+    /// Creates a BAM merger.
+    ///
+    /// This reads the header and reference sequences from each reader and reconciles them into
+    /// a single header. The readers must be positioned at the start of their streams.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a header is invalid, an input is not coordinate-sorted, or the
+    /// reference sequences of the inputs are incompatible.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, merge::Merger};
+    /// let readers = vec![File::open("sample.1.bam").map(bam::Reader::new)?];
+    /// let merger = Merger::new(readers)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(readers: Vec<Reader<R>>) -> io::Result<Self> {
+        let mut header = sam::Header::default();
+        let mut inputs = Vec::with_capacity(readers.len());
+
+        let mut readers = readers;
+        let mut input_headers = Vec::with_capacity(readers.len());
+
+        for reader in &mut readers {
+            let input_header: sam::Header = reader
+                .read_header()?
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let reference_sequences = reader.read_reference_sequences()?;
+
+            if let Some(hd) = input_header.header() {
+                match hd.sort_order() {
+                    None | Some(SortOrder::Coordinate) => {}
+                    Some(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "input is not coordinate-sorted",
+                        ))
+                    }
+                }
+
+                if header.header().is_none() {
+                    *header.header_mut() = Some(hd.clone());
+                }
+            }
+
+            input_headers.push((input_header, reference_sequences));
+        }
+
+        let reference_sequence_ids = merge_reference_sequences(&mut header, &input_headers)?;
+
+        for ((reader, (input_header, _)), reference_sequence_ids) in readers
+            .into_iter()
+            .zip(input_headers)
+            .zip(reference_sequence_ids)
+        {
+            let read_group_ids = merge_read_groups(&mut header, &input_header);
+            let program_ids = merge_programs(&mut header, &input_header);
+
+            for comment in input_header.comments() {
+                if !header.comments().contains(comment) {
+                    header.comments_mut().push(comment.clone());
+                }
+            }
+
+            inputs.push(Input {
+                reader,
+                reference_sequence_ids,
+                read_group_ids,
+                program_ids,
+                sort_check: CoordinateSortCheck::default(),
+            });
+        }
+
+        let hd = header
+            .header_mut()
+            .get_or_insert_with(sam::header::header::Header::default);
+
+        *hd.sort_order_mut() = Some(SortOrder::Coordinate);
+        *hd.group_order_mut() = None;
+        *hd.subsort_order_mut() = None;
+
+        Ok(Self { header, inputs })
+    }
+
+    /// Returns the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, merge::Merger};
+    /// let readers = vec![File::open("sample.1.bam").map(bam::Reader::new)?];
+    /// let merger = Merger::new(readers)?;
+    /// let header = merger.header();
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+
+    /// Returns a mutable reference to the merged header.
+    ///
+    /// This can be used to, e.g., add a program (`@PG`) before merging. Reference sequences must
+    /// not be changed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, merge::Merger};
+    /// use noodles_sam::header::Program;
+    ///
+    /// let readers = vec![File::open("sample.1.bam").map(bam::Reader::new)?];
+    /// let mut merger = Merger::new(readers)?;
+    ///
+    /// merger
+    ///     .header_mut()
+    ///     .programs_mut()
+    ///     .insert(String::from("merge"), Program::new(String::from("merge")));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn header_mut(&mut self) -> &mut sam::Header {
+        &mut self.header
+    }
+
+    /// Merges the inputs and writes the merged header and records to the given writer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, merge::Merger};
+    ///
+    /// let readers = vec![File::open("sample.1.bam").map(bam::Reader::new)?];
+    /// let merger = Merger::new(readers)?;
+    ///
+    /// let mut writer = File::create("sample.bam").map(bam::Writer::new)?;
+    /// merger.merge(&mut writer)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn merge<W>(self, writer: &mut Writer<W>) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let header = self.header;
+        let mut inputs = self.inputs;
+
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let mut heap = BinaryHeap::with_capacity(inputs.len());
+
+        for (index, input) in inputs.iter_mut().enumerate() {
+            if let Some((key, record)) = input.next(&header)? {
+                heap.push(Reverse(Entry { key, index, record }));
+            }
+        }
+
+        while let Some(Reverse(entry)) = heap.pop() {
+            writer.write_record(&entry.record)?;
+
+            let index = entry.index;
+
+            if let Some((key, record)) = inputs[index].next(&header)? {
+                heap.push(Reverse(Entry { key, index, record }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merges the inputs, writes the merged header and records to the given writer, and returns
+    /// the BAM index (BAI) built by the writer.
+    ///
+    /// The writer must be built to build an index (see [`writer::Builder::set_build_index`]).
+    /// The writer is finished after the records are written.
+    ///
+    /// [`writer::Builder::set_build_index`]: ../writer/struct.Builder.html#method.set_build_index
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the writer is not building an index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, bai, merge::Merger};
+    ///
+    /// let readers = vec![File::open("sample.1.bam").map(bam::Reader::new)?];
+    /// let merger = Merger::new(readers)?;
+    ///
+    /// let mut writer = File::create("sample.bam")
+    ///     .map(|file| bam::Writer::builder(file).set_build_index(true).build())?;
+    /// let index = merger.merge_with_index(&mut writer)?;
+    ///
+    /// let mut index_writer = File::create("sample.bam.bai").map(bai::Writer::new)?;
+    /// index_writer.write_header()?;
+    /// index_writer.write_index(&index)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn merge_with_index<W>(self, writer: &mut Writer<W>) -> io::Result<bai::Index>
+    where
+        W: Write,
+    {
+        self.merge(writer)?;
+
+        writer.try_finish_with_index()?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "writer is not building an index",
+            )
+        })
+    }
+}
+
+impl<R> Input<R>
+where
+    R: Read,
+{
+    fn next(&mut self, header: &sam::Header) -> io::Result<Option<(RecordKey, Record)>> {
+        let mut record = Record::default();
+
+        if self.reader.read_record(&mut record)? == 0 {
+            return Ok(None);
+        }
+
+        self.update_record(&mut record)?;
+        self.sort_check.add(&record, io::ErrorKind::InvalidData)?;
+
+        let key = RecordKey::new(Key::Coordinate, header, &record)?;
+
+        Ok(Some((key, record)))
+    }
+
+    fn update_record(&self, record: &mut Record) -> io::Result<()> {
+        let reference_sequence_id =
+            self.map_reference_sequence_id(record.reference_sequence_id())?;
+        record.set_reference_sequence_id(reference_sequence_id);
+
+        let mate_reference_sequence_id =
+            self.map_reference_sequence_id(record.mate_reference_sequence_id())?;
+        record.set_mate_reference_sequence_id(mate_reference_sequence_id);
+
+        if self.read_group_ids.is_empty() && self.program_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut renamed_fields = Vec::new();
+
+        for result in record.data().fields() {
+            let field = result?;
+
+            let ids = match field.tag() {
+                Tag::ReadGroup => &self.read_group_ids,
+                Tag::Program => &self.program_ids,
+                _ => continue,
+            };
+
+            if let Value::String(id) = field.value() {
+                if let Some(new_id) = ids.get(id) {
+                    let value = Value::String(new_id.clone());
+                    renamed_fields.push(Field::new(field.tag().clone(), value));
+                }
+            }
+        }
+
+        for field in renamed_fields {
+            record.insert_data_field(field)?;
+        }
+
+        Ok(())
+    }
+
+    fn map_reference_sequence_id(
+        &self,
+        reference_sequence_id: ReferenceSequenceId,
+    ) -> io::Result<ReferenceSequenceId> {
+        match *reference_sequence_id {
+            Some(id) => usize::try_from(id)
+                .ok()
+                .and_then(|i| self.reference_sequence_ids.get(i))
+                .map(|&merged_id| ReferenceSequenceId::from(merged_id))
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence id")
+                }),
+            None => Ok(reference_sequence_id),
+        }
+    }
+}
+
+// Unifies the reference sequences of the inputs and adds them to the merged header.
+//
+// The merged order is consistent with the order of every input, preferring the order of first
+// appearance. This returns the merged ID of each input reference sequence for each input.
+fn merge_reference_sequences(
+    header: &mut sam::Header,
+    inputs: &[(sam::Header, Vec<ReferenceSequence>)],
+) -> io::Result<Vec<Vec<i32>>> {
+    // Reference sequences are first collected in order of first appearance.
+    let mut reference_sequences: Vec<ReferenceSequence> = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut input_indices = Vec::with_capacity(inputs.len());
+
+    for (input_header, input_reference_sequences) in inputs {
+        let mut ids = Vec::with_capacity(input_reference_sequences.len());
+
+        for reference_sequence in input_reference_sequences {
+            let name = reference_sequence.name();
+
+            let i = match indices.get(name) {
+                Some(&i) => {
+                    if reference_sequences[i].len() != reference_sequence.len() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("reference sequence length mismatch: {}", name),
+                        ));
+                    }
+
+                    i
+                }
+                None => {
+                    // Prefer the header record, which may have additional fields.
+                    let reference_sequence = input_header
+                        .reference_sequences()
+                        .get(name)
+                        .filter(|rs| rs.len() == reference_sequence.len())
+                        .unwrap_or(reference_sequence)
+                        .clone();
+
+                    let i = reference_sequences.len();
+                    indices.insert(name.into(), i);
+                    reference_sequences.push(reference_sequence);
+
+                    i
+                }
+            };
+
+            ids.push(i);
+        }
+
+        input_indices.push(ids);
+    }
+
+    let order = topological_order(reference_sequences.len(), &input_indices).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "reference sequence order mismatch",
+        )
+    })?;
+
+    let mut merged_ids = vec![0; reference_sequences.len()];
+    let mut reference_sequences: Vec<_> = reference_sequences.into_iter().map(Some).collect();
+
+    for (merged_id, &i) in order.iter().enumerate() {
+        merged_ids[i] =
+            i32::try_from(merged_id).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if let Some(reference_sequence) = reference_sequences[i].take() {
+            header
+                .reference_sequences_mut()
+                .insert(reference_sequence.name().into(), reference_sequence);
+        }
+    }
+
+    Ok(input_indices
+        .into_iter()
+        .map(|ids| ids.into_iter().map(|i| merged_ids[i]).collect())
+        .collect())
+}
+
+// Returns an order of the nodes `0..n` that preserves the order of every list, breaking ties by
+// the lowest node index. This returns `None` if the lists conflict.
+fn topological_order(n: usize, lists: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let mut successors = vec![Vec::new(); n];
+    let mut in_degrees = vec![0; n];
+
+    for list in lists {
+        for pair in list.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+
+            if !successors[a].contains(&b) {
+                successors[a].push(b);
+                in_degrees[b] += 1;
+            }
+        }
+    }
+
+    let mut queue: BinaryHeap<_> = (0..n)
+        .filter(|&i| in_degrees[i] == 0)
+        .map(Reverse)
+        .collect();
+
+    let mut order = Vec::with_capacity(n);
+
+    while let Some(Reverse(i)) = queue.pop() {
+        order.push(i);
+
+        for &j in &successors[i] {
+            in_degrees[j] -= 1;
+
+            if in_degrees[j] == 0 {
+                queue.push(Reverse(j));
+            }
+        }
+    }
+
+    if order.len() == n {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+// Adds the input read groups to the merged header and returns the renamed IDs.
+fn merge_read_groups(
+    header: &mut sam::Header,
+    input_header: &sam::Header,
+) -> HashMap<String, String> {
+    let mut ids = HashMap::new();
+
+    for (id, read_group) in input_header.read_groups() {
+        let read_groups = header.read_groups_mut();
+
+        match read_groups.get(id) {
+            None => {
+                read_groups.insert(id.clone(), read_group.clone());
+            }
+            Some(merged_read_group) if merged_read_group == read_group => {}
+            Some(_) => {
+                let new_id = sam::header::unique_id(read_groups, id);
+
+                let mut read_group = read_group.clone();
+                *read_group.id_mut() = new_id.clone();
+                read_groups.insert(new_id.clone(), read_group);
+
+                ids.insert(id.clone(), new_id);
+            }
+        }
+    }
+
+    ids
+}
+
+// Adds the input programs to the merged header and returns the renamed IDs.
+//
+// Previous program IDs (`PP`) are rewritten to follow renamed programs.
+fn merge_programs(header: &mut sam::Header, input_header: &sam::Header) -> HashMap<String, String> {
+    let mut ids = HashMap::new();
+    let mut inserted_programs = Vec::new();
+
+    for (id, program) in input_header.programs() {
+        let mut program = program.clone();
+        rename_previous_id(&mut program, &ids);
+
+        let programs = header.programs_mut();
+
+        let new_id = match programs.get(id) {
+            None => id.clone(),
+            Some(merged_program) if *merged_program == program => continue,
+            Some(_) => {
+                let new_id = sam::header::unique_id(programs, id);
+                *program.id_mut() = new_id.clone();
+                ids.insert(id.clone(), new_id.clone());
+                new_id
+            }
+        };
+
+        programs.insert(new_id.clone(), program);
+        inserted_programs.push((new_id, id));
+    }
+
+    // A previous program ID can refer to a program that is listed later in the input header, so
+    // the previous IDs of the inserted programs are renamed from the input program once all IDs
+    // are assigned.
+    for (new_id, id) in inserted_programs {
+        let mut previous_id = input_header.programs()[id].previous_id().map(String::from);
+
+        if let Some(renamed_id) = previous_id.as_ref().and_then(|id| ids.get(id)) {
+            previous_id = Some(renamed_id.clone());
+        }
+
+        if let Some(program) = header.programs_mut().get_mut(&new_id) {
+            *program.previous_id_mut() = previous_id;
+        }
+    }
+
+    ids
+}
+
+fn rename_previous_id(program: &mut sam::header::Program, ids: &HashMap<String, String>) {
+    let previous_id = program.previous_id_mut();
+
+    if let Some(new_id) = previous_id.as_ref().and_then(|id| ids.get(id)) {
+        *previous_id = Some(new_id.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::header::{Program, ReadGroup};
+
+    use crate::record::aligned_record_builder;
+
+    use super::*;
+
+    fn build_bam(header: &sam::Header, records: &[Record]) -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for record in records {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        Ok(writer.get_ref().clone())
+    }
+
+    fn build_record(
+        name: &str,
+        reference_sequence_id: i32,
+        position: i32,
+        read_group: &str,
+    ) -> Result<Record, Box<dyn std::error::Error>> {
+        let record = aligned_record_builder(reference_sequence_id, position, &[])
+            .set_read_name(name.parse()?)
+            .set_data(vec![Field::new(
+                Tag::ReadGroup,
+                Value::String(String::from(read_group)),
+            )])
+            .build()?;
+
+        Ok(record)
+    }
+
+    fn read_group(record: &Record) -> io::Result<Option<String>> {
+        for result in record.data().fields() {
+            let field = result?;
+
+            if let (Tag::ReadGroup, Value::String(id)) = (field.tag(), field.value()) {
+                return Ok(Some(id.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let header_1 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq2"), 21))
+            .add_read_group(
+                ReadGroup::builder()
+                    .set_id("rg0")
+                    .set_library("lb0")
+                    .build(),
+            )
+            .add_program(Program::new(String::from("pg0")))
+            .build();
+
+        let header_2 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 13))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq2"), 21))
+            .add_read_group(
+                ReadGroup::builder()
+                    .set_id("rg0")
+                    .set_library("lb1")
+                    .build(),
+            )
+            .add_program(Program::new(String::from("pg0")))
+            .build();
+
+        let bam_1 = build_bam(
+            &header_1,
+            &[
+                build_record("r0", 0, 5, "rg0")?,
+                build_record("r1", 1, 3, "rg0")?,
+            ],
+        )?;
+
+        let bam_2 = build_bam(
+            &header_2,
+            &[
+                build_record("r2", 0, 2, "rg0")?,
+                build_record("r3", 1, 1, "rg0")?,
+                build_record("r4", 2, 1, "rg0")?,
+            ],
+        )?;
+
+        let readers = vec![Reader::new(&bam_1[..]), Reader::new(&bam_2[..])];
+        let merger = Merger::new(readers)?;
+
+        let header = merger.header().clone();
+
+        let names: Vec<_> = header.reference_sequences().keys().collect();
+        assert_eq!(names, ["sq0", "sq1", "sq2"]);
+
+        let read_group_ids: Vec<_> = header.read_groups().keys().collect();
+        assert_eq!(read_group_ids, ["rg0", "rg0.1"]);
+        assert_eq!(header.read_groups()["rg0.1"].library(), Some("lb1"));
+
+        let program_ids: Vec<_> = header.programs().keys().collect();
+        assert_eq!(program_ids, ["pg0"]);
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        let index = merger.merge_with_index(&mut writer)?;
+
+        assert_eq!(index.reference_sequences().len(), 3);
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        let actual_header: sam::Header = reader.read_header()?.parse()?;
+        assert_eq!(
+            actual_header.header().and_then(|hd| hd.sort_order()),
+            Some(SortOrder::Coordinate)
+        );
+        reader.read_reference_sequences()?;
+
+        let mut actual = Vec::new();
+
+        for result in reader.records() {
+            let record = result?;
+            actual.push((
+                i32::from(record.reference_sequence_id()),
+                i32::from(record.position()),
+                read_group(&record)?,
+            ));
+        }
+
+        let expected = [
+            (0, 2, Some(String::from("rg0.1"))),
+            (0, 5, Some(String::from("rg0"))),
+            (1, 1, Some(String::from("rg0.1"))),
+            (2, 1, Some(String::from("rg0.1"))),
+            (2, 3, Some(String::from("rg0"))),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_incompatible_reference_sequences() -> io::Result<()> {
+        let header_1 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build();
+        let header_2 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 13))
+            .build();
+
+        let bam_1 = build_bam(&header_1, &[])?;
+        let bam_2 = build_bam(&header_2, &[])?;
+        let readers = vec![Reader::new(&bam_1[..]), Reader::new(&bam_2[..])];
+        assert!(Merger::new(readers).is_err());
+
+        let header_2 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 13))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build();
+        let header_3 = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 13))
+            .build();
+
+        let bam_2 = build_bam(&header_2, &[])?;
+        let bam_3 = build_bam(&header_3, &[])?;
+        let readers = vec![
+            Reader::new(&bam_1[..]),
+            Reader::new(&bam_3[..]),
+            Reader::new(&bam_2[..]),
+        ];
+        assert!(Merger::new(readers).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_programs() {
+        let mut header = sam::Header::builder()
+            .add_program(Program::new(String::from("pg0")))
+            .build();
+
+        let mut pg1 = Program::new(String::from("pg1"));
+        *pg1.previous_id_mut() = Some(String::from("pg0"));
+
+        let input_header = sam::Header::builder()
+            .add_program(pg1)
+            .add_program(Program::builder().set_id("pg0").set_name("noodles").build())
+            .build();
+
+        let ids = merge_programs(&mut header, &input_header);
+
+        assert_eq!(ids.get("pg0"), Some(&String::from("pg0.1")));
+
+        let programs = header.programs();
+        let program_ids: Vec<_> = programs.keys().collect();
+        assert_eq!(program_ids, ["pg0", "pg1", "pg0.1"]);
+        assert_eq!(programs["pg1"].previous_id(), Some("pg0.1"));
+        assert_eq!(programs["pg0.1"].id(), "pg0.1");
+    }
+
+    #[test]
+    fn test_merge_programs_with_chained_renames() {
+        let mut header = sam::Header::builder()
+            .add_program(Program::new(String::from("pg0")))
+            .build();
+
+        let mut pg0_1 = Program::new(String::from("pg0.1"));
+        *pg0_1.previous_id_mut() = Some(String::from("pg0"));
+
+        let input_header = sam::Header::builder()
+            .add_program(Program::builder().set_id("pg0").set_name("noodles").build())
+            .add_program(pg0_1)
+            .build();
+
+        let ids = merge_programs(&mut header, &input_header);
+
+        assert_eq!(ids.get("pg0"), Some(&String::from("pg0.1")));
+        assert_eq!(ids.get("pg0.1"), Some(&String::from("pg0.1.1")));
+
+        let programs = header.programs();
+        let program_ids: Vec<_> = programs.keys().collect();
+        assert_eq!(program_ids, ["pg0", "pg0.1", "pg0.1.1"]);
+        assert_eq!(programs["pg0.1"].previous_id(), None);
+        assert_eq!(programs["pg0.1.1"].previous_id(), Some("pg0.1"));
+    }
+
+    #[test]
+    fn test_topological_order() {
+        assert_eq!(
+            topological_order(3, &[vec![0, 2], vec![0, 1, 2]]),
+            Some(vec![0, 1, 2])
+        );
+        assert_eq!(
+            topological_order(4, &[vec![0, 1], vec![2, 3]]),
+            Some(vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            topological_order(3, &[vec![2, 1], vec![0]]),
+            Some(vec![0, 2, 1])
+        );
+        assert_eq!(topological_order(2, &[vec![0, 1], vec![1, 0]]), None);
+    }
+}
@@ -0,0 +1,575 @@
+//! BAM record pileup.
+//!
+//! A pileup is a per-position view of coordinate-sorted records. For each reference position
+//! covered by at least one record, the pileup emits a [`Column`] with an [`Alignment`] for each
+//! record that overlaps the position.
+//!
+//! [`Column`]: struct.Column.html
+//! [`Alignment`]: struct.Alignment.html
+
+mod alignment;
+mod column;
+
+pub use self::{alignment::Alignment, column::Column};
+
+use std::{io, rc::Rc};
+
+use noodles_sam::record::{cigar::op::Kind, Flags, Position};
+
+use super::{sort::CoordinateSortCheck, Record};
+
+// The default maximum number of records in a column, the same as `samtools mpileup`.
+const DEFAULT_MAX_DEPTH: usize = 8000;
+
+const MISSING_QUALITY_SCORE: u8 = 255;
+
+/// A pileup iterator over coordinate-sorted records.
+///
+/// The records can come from any iterator of records, e.g., [`bam::Reader::records`] or
+/// [`bam::Reader::query`].
+///
+/// [`bam::Reader::records`]: ../struct.Reader.html#method.records
+/// [`bam::Reader::query`]: ../struct.Reader.html#method.query
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{self as bam, pileup::Pileup};
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// reader.read_header()?;
+/// reader.read_reference_sequences()?;
+///
+/// let pileup = Pileup::new(reader.records()).set_min_mapping_quality(20);
+///
+/// for result in pileup {
+///     let column = result?;
+///     let position = i32::from(column.position());
+///     println!("{}\t{}\t{}", column.reference_sequence_id(), position, column.depth());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Pileup<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    records: I,
+    next_record: Option<Record>,
+    sort_check: CoordinateSortCheck,
+    reads: Vec<Read>,
+    reference_sequence_id: usize,
+    position: i32,
+    flag_filter: Flags,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    max_depth: usize,
+}
+
+impl<I> Pileup<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    /// Creates a pileup iterator.
+    ///
+    /// By default, unmapped, secondary, QC fail, and duplicate records are skipped; there are no
+    /// mapping or base quality thresholds; and the maximum depth is 8000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, pileup::Pileup};
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let mut pileup = Pileup::new(records.into_iter());
+    ///
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            next_record: None,
+            sort_check: CoordinateSortCheck::default(),
+            reads: Vec::new(),
+            reference_sequence_id: 0,
+            position: 0,
+            flag_filter: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            min_mapping_quality: 0,
+            min_base_quality: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the flags of records to skip.
+    ///
+    /// A record is skipped if any of its flags are in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, pileup::Pileup};
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let pileup = Pileup::new(records.into_iter()).set_flag_filter(Flags::UNMAPPED);
+    /// ```
+    pub fn set_flag_filter(mut self, flag_filter: Flags) -> Self {
+        self.flag_filter = flag_filter;
+        self
+    }
+
+    /// Sets the minimum mapping quality of records.
+    ///
+    /// Records with a lower mapping quality are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, pileup::Pileup};
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let pileup = Pileup::new(records.into_iter()).set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the minimum base quality of aligned bases.
+    ///
+    /// Aligned bases with a lower quality score are left out of a column. Deletions, reference
+    /// skips, and bases without quality scores are always kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, pileup::Pileup};
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let pileup = Pileup::new(records.into_iter()).set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the maximum number of overlapping records.
+    ///
+    /// Once this many records overlap a position, records that start at that position are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, pileup::Pileup};
+    /// let records = vec![Ok(bam::Record::default())];
+    /// let pileup = Pileup::new(records.into_iter()).set_max_depth(250);
+    /// ```
+    pub fn set_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // Returns the next record that passes the filters without consuming it.
+    fn peek_record(&mut self) -> io::Result<Option<&Record>> {
+        while self.next_record.is_none() {
+            let record = match self.records.next() {
+                Some(result) => result?,
+                None => return Ok(None),
+            };
+
+            self.sort_check.add(&record, io::ErrorKind::InvalidData)?;
+
+            if record.reference_sequence_id().is_none() || self.is_filtered(&record) {
+                continue;
+            }
+
+            self.next_record = Some(record);
+        }
+
+        Ok(self.next_record.as_ref())
+    }
+
+    fn is_filtered(&self, record: &Record) -> bool {
+        record.flags().intersects(self.flag_filter)
+            || u8::from(record.mapping_quality()) < self.min_mapping_quality
+            || record.cigar().reference_len() == 0
+    }
+
+    // Adds the records that start at the current position.
+    fn add_reads(&mut self) -> io::Result<()> {
+        loop {
+            let reference_sequence_id = self.reference_sequence_id;
+            let position = self.position;
+
+            let starts_here = match self.peek_record()? {
+                Some(record) => {
+                    *record.reference_sequence_id() == Some(reference_sequence_id as i32)
+                        && i32::from(record.position()) == position
+                }
+                None => false,
+            };
+
+            if !starts_here {
+                return Ok(());
+            }
+
+            if let Some(record) = self.next_record.take() {
+                if self.reads.len() < self.max_depth {
+                    self.reads.push(Read::new(record));
+                }
+            }
+        }
+    }
+
+    fn next_column(&mut self) -> io::Result<Option<Column>> {
+        if self.reads.is_empty() {
+            // Jump to the start of the next record.
+            let (reference_sequence_id, position) = match self.peek_record()? {
+                Some(record) => (
+                    record.reference_sequence_id().unwrap_or_default() as usize,
+                    i32::from(record.position()),
+                ),
+                None => return Ok(None),
+            };
+
+            self.reference_sequence_id = reference_sequence_id;
+            self.position = position;
+        }
+
+        self.add_reads()?;
+
+        let min_base_quality = self.min_base_quality;
+
+        let alignments = self
+            .reads
+            .iter()
+            .filter_map(|read| read.alignment(min_base_quality))
+            .collect();
+
+        let column = Column {
+            reference_sequence_id: self.reference_sequence_id,
+            position: Position::from(self.position),
+            alignments,
+        };
+
+        for read in &mut self.reads {
+            read.advance();
+        }
+
+        self.reads.retain(|read| !read.is_done());
+        self.position += 1;
+
+        Ok(Some(column))
+    }
+}
+
+impl<I> Iterator for Pileup<I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Column>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_column().transpose()
+    }
+}
+
+// A record that overlaps the current position with a cursor over its CIGAR operations.
+struct Read {
+    record: Rc<Record>,
+    ops: Vec<(Kind, u32)>,
+    // The index of the current reference-consuming operation.
+    op_index: usize,
+    // The offset into the current operation.
+    op_offset: u32,
+    // The read position at the start of the current operation.
+    read_position: usize,
+}
+
+impl Read {
+    fn new(record: Record) -> Self {
+        let ops = record
+            .cigar()
+            .ops()
+            .map(|op| (op.kind(), op.len()))
+            .collect();
+
+        let mut read = Self {
+            record: Rc::new(record),
+            ops,
+            op_index: 0,
+            op_offset: 0,
+            read_position: 0,
+        };
+
+        read.skip_non_reference_ops();
+
+        read
+    }
+
+    fn is_done(&self) -> bool {
+        self.op_index >= self.ops.len()
+    }
+
+    // Moves the cursor to the next reference position.
+    fn advance(&mut self) {
+        let (kind, len) = self.ops[self.op_index];
+
+        self.op_offset += 1;
+
+        if self.op_offset >= len {
+            if kind.consumes_read() {
+                self.read_position += len as usize;
+            }
+
+            self.op_index += 1;
+            self.op_offset = 0;
+
+            self.skip_non_reference_ops();
+        }
+    }
+
+    fn skip_non_reference_ops(&mut self) {
+        while let Some(&(kind, len)) = self.ops.get(self.op_index) {
+            if kind.consumes_reference() && len > 0 {
+                break;
+            }
+
+            if kind.consumes_read() {
+                self.read_position += len as usize;
+            }
+
+            self.op_index += 1;
+        }
+    }
+
+    fn alignment(&self, min_base_quality: u8) -> Option<Alignment> {
+        let (kind, len) = self.ops[self.op_index];
+
+        let indel = if self.op_offset + 1 == len {
+            self.next_indel(kind)
+        } else {
+            0
+        };
+
+        let mut alignment = Alignment {
+            record: self.record.clone(),
+            read_position: None,
+            base: None,
+            quality_score: None,
+            indel,
+            is_deletion: false,
+            is_reference_skip: false,
+        };
+
+        match kind {
+            Kind::Deletion => alignment.is_deletion = true,
+            Kind::Skip => alignment.is_reference_skip = true,
+            _ => {
+                let read_position = self.read_position + self.op_offset as usize;
+
+                let quality_score = self
+                    .record
+                    .quality_scores()
+                    .get(read_position)
+                    .copied()
+                    .filter(|&n| n != MISSING_QUALITY_SCORE);
+
+                if let Some(n) = quality_score {
+                    if n < min_base_quality {
+                        return None;
+                    }
+                }
+
+                alignment.read_position = Some(read_position);
+                alignment.base = self.record.sequence().get(read_position).copied();
+                alignment.quality_score = quality_score;
+            }
+        }
+
+        Some(alignment)
+    }
+
+    // Returns the length of the insertion (positive) or deletion (negative) that follows the
+    // current operation.
+    fn next_indel(&self, kind: Kind) -> i32 {
+        let next_op = self.ops[self.op_index + 1..]
+            .iter()
+            .find(|(k, _)| *k != Kind::Pad);
+
+        match next_op {
+            Some(&(Kind::Insertion, len)) => len as i32,
+            Some(&(Kind::Deletion, len)) if kind != Kind::Deletion => -(len as i32),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::MappingQuality;
+
+    use crate::record::{aligned_record_builder, sequence::Base};
+
+    use super::*;
+
+    fn build_record(
+        reference_sequence_id: i32,
+        position: i32,
+        cigar: &[(Kind, u32)],
+        sequence: &[Base],
+    ) -> io::Result<Record> {
+        let quality_scores = (0..sequence.len() as u8).map(|n| n * 10).collect();
+
+        aligned_record_builder(reference_sequence_id, position, cigar)
+            .set_mapping_quality(MappingQuality::from(30))
+            .set_sequence(sequence.to_vec())
+            .set_quality_scores(quality_scores)
+            .build()
+    }
+
+    fn summarize(column: &Column) -> (usize, i32, String) {
+        let bases = column
+            .alignments()
+            .iter()
+            .map(|alignment| {
+                if alignment.is_deletion() {
+                    '*'
+                } else if alignment.is_reference_skip() {
+                    '>'
+                } else {
+                    alignment
+                        .base()
+                        .map(|b| b.to_string().chars().next().unwrap_or('?'))
+                        .unwrap_or('?')
+                }
+            })
+            .collect();
+
+        (
+            column.reference_sequence_id(),
+            i32::from(column.position()),
+            bases,
+        )
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        use Base::{A, C, G, T};
+
+        let records = vec![
+            // 1S2M1I1M1D1M
+            build_record(
+                0,
+                2,
+                &[
+                    (Kind::SoftClip, 1),
+                    (Kind::Match, 2),
+                    (Kind::Insertion, 1),
+                    (Kind::Match, 1),
+                    (Kind::Deletion, 1),
+                    (Kind::Match, 1),
+                ],
+                &[T, A, C, G, T, A],
+            )?,
+            build_record(0, 3, &[(Kind::Match, 2)], &[G, G])?,
+            build_record(
+                0,
+                9,
+                &[(Kind::Match, 1), (Kind::Skip, 1), (Kind::Match, 1)],
+                &[C, C],
+            )?,
+            build_record(1, 1, &[(Kind::Match, 1)], &[T])?,
+        ];
+
+        let pileup = Pileup::new(records.into_iter().map(Ok));
+        let columns: Vec<_> = pileup.collect::<io::Result<_>>()?;
+
+        let actual: Vec<_> = columns.iter().map(summarize).collect();
+        let expected = [
+            (0, 2, String::from("A")),
+            (0, 3, String::from("CG")),
+            (0, 4, String::from("TG")),
+            (0, 5, String::from("*")),
+            (0, 6, String::from("A")),
+            (0, 9, String::from("C")),
+            (0, 10, String::from(">")),
+            (0, 11, String::from("C")),
+            (1, 1, String::from("T")),
+        ];
+        assert_eq!(actual, expected);
+
+        let alignment = &columns[1].alignments()[0];
+        assert_eq!(alignment.read_position(), Some(2));
+        assert_eq!(alignment.quality_score(), Some(20));
+        assert_eq!(alignment.indel(), 1);
+        assert!(!alignment.is_reverse_complemented());
+
+        let alignment = &columns[2].alignments()[0];
+        assert_eq!(alignment.read_position(), Some(4));
+        assert_eq!(alignment.indel(), -1);
+
+        let alignment = &columns[3].alignments()[0];
+        assert_eq!(alignment.read_position(), None);
+        assert_eq!(alignment.quality_score(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> io::Result<()> {
+        use Base::{A, C};
+
+        let build_records = || -> io::Result<Vec<Record>> {
+            let mut duplicate = build_record(0, 1, &[(Kind::Match, 2)], &[A, C])?;
+            duplicate.set_flags(Flags::DUPLICATE);
+
+            let mut low_mapping_quality = build_record(0, 1, &[(Kind::Match, 2)], &[A, C])?;
+            low_mapping_quality.set_mapping_quality(MappingQuality::from(5));
+
+            Ok(vec![
+                build_record(0, 1, &[(Kind::Match, 2)], &[A, C])?,
+                duplicate,
+                low_mapping_quality,
+                build_record(0, 1, &[(Kind::Match, 2)], &[A, C])?,
+            ])
+        };
+
+        let depths = |pileup: Pileup<_>| -> io::Result<Vec<usize>> {
+            pileup
+                .map(|result| result.map(|column| column.depth()))
+                .collect()
+        };
+
+        let pileup = Pileup::new(build_records()?.into_iter().map(Ok));
+        assert_eq!(depths(pileup)?, [3, 3]);
+
+        let pileup =
+            Pileup::new(build_records()?.into_iter().map(Ok)).set_flag_filter(Flags::empty());
+        assert_eq!(depths(pileup)?, [4, 4]);
+
+        let pileup = Pileup::new(build_records()?.into_iter().map(Ok)).set_min_mapping_quality(10);
+        assert_eq!(depths(pileup)?, [2, 2]);
+
+        // The quality scores are 0 and 10.
+        let pileup = Pileup::new(build_records()?.into_iter().map(Ok)).set_min_base_quality(5);
+        assert_eq!(depths(pileup)?, [0, 3]);
+
+        let pileup = Pileup::new(build_records()?.into_iter().map(Ok)).set_max_depth(2);
+        assert_eq!(depths(pileup)?, [2, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> io::Result<()> {
+        let records = vec![
+            build_record(0, 5, &[(Kind::Match, 1)], &[Base::A])?,
+            build_record(0, 2, &[(Kind::Match, 1)], &[Base::A])?,
+        ];
+
+        let mut pileup = Pileup::new(records.into_iter().map(Ok));
+        assert!(pileup.next().transpose().is_err());
+
+        Ok(())
+    }
+}
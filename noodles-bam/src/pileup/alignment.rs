@@ -0,0 +1,69 @@
+use std::rc::Rc;
+
+use crate::{record::sequence::Base, Record};
+
+/// A pileup alignment.
+///
+/// This is the alignment of a single record at a pileup column's reference position.
+#[derive(Clone, Debug)]
+pub struct Alignment {
+    pub(super) record: Rc<Record>,
+    pub(super) read_position: Option<usize>,
+    pub(super) base: Option<Base>,
+    pub(super) quality_score: Option<u8>,
+    pub(super) indel: i32,
+    pub(super) is_deletion: bool,
+    pub(super) is_reference_skip: bool,
+}
+
+impl Alignment {
+    /// Returns the aligned record.
+    pub fn record(&self) -> &Record {
+        &self.record
+    }
+
+    /// Returns the 0-based position in the read that is aligned to the reference position.
+    ///
+    /// This is `None` if the reference position is deleted from or skipped in the read.
+    pub fn read_position(&self) -> Option<usize> {
+        self.read_position
+    }
+
+    /// Returns the read base at the reference position.
+    ///
+    /// This is `None` if the reference position is deleted from or skipped in the read.
+    pub fn base(&self) -> Option<Base> {
+        self.base
+    }
+
+    /// Returns the quality score of the read base at the reference position.
+    ///
+    /// This is `None` if the reference position is deleted from or skipped in the read or if the
+    /// record is missing quality scores.
+    pub fn quality_score(&self) -> Option<u8> {
+        self.quality_score
+    }
+
+    /// Returns the length of the indel that immediately follows the reference position.
+    ///
+    /// This is positive for an insertion, negative for a deletion, and 0 if there is no indel
+    /// after the reference position.
+    pub fn indel(&self) -> i32 {
+        self.indel
+    }
+
+    /// Returns whether the reference position is deleted from the read (`D`).
+    pub fn is_deletion(&self) -> bool {
+        self.is_deletion
+    }
+
+    /// Returns whether the reference position is skipped in the read (`N`).
+    pub fn is_reference_skip(&self) -> bool {
+        self.is_reference_skip
+    }
+
+    /// Returns whether the read is aligned to the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.record.flags().is_reverse_complemented()
+    }
+}
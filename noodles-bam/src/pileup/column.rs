@@ -0,0 +1,37 @@
+use noodles_sam::record::Position;
+
+use super::Alignment;
+
+/// A pileup column.
+///
+/// A column is the list of alignments of the records that overlap a single reference position.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub(super) reference_sequence_id: usize,
+    pub(super) position: Position,
+    pub(super) alignments: Vec<Alignment>,
+}
+
+impl Column {
+    /// Returns the reference sequence ID of the column.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the 1-based reference position of the column.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the alignments that overlap the reference position.
+    ///
+    /// Alignments are in the order their records were read.
+    pub fn alignments(&self) -> &[Alignment] {
+        &self.alignments
+    }
+
+    /// Returns the number of alignments in the column.
+    pub fn depth(&self) -> usize {
+        self.alignments.len()
+    }
+}
@@ -2,9 +2,15 @@
 
 mod query;
 mod records;
+mod regions_query;
 mod unmapped_records;
 
-pub use self::{query::Query, records::Records, unmapped_records::UnmappedRecords};
+pub use self::{
+    query::Query,
+    records::Records,
+    regions_query::{Hits, RegionsQuery},
+    unmapped_records::UnmappedRecords,
+};
 
 use std::{
     ffi::CStr,
@@ -14,8 +20,10 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt};
 use noodles::Region;
 use noodles_bgzf::{self as bgzf, VirtualPosition};
+use noodles_csi::{binning_index::optimize_chunks, BinningIndex};
 use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
 
+pub(crate) use self::regions_query::{Interval, Intervals};
 use super::{bai, Record, MAGIC_NUMBER};
 
 /// A BAM reader.
@@ -276,6 +284,9 @@ where
 
     /// Returns an iterator over records that intersect the given region.
     ///
+    /// The index can be any binning index, e.g., a BAM index (BAI) or a coordinate-sorted index
+    /// (CSI).
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -298,37 +309,84 @@ where
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn query(
+    pub fn query<I>(
         &mut self,
         reference_sequences: &ReferenceSequences,
-        index: &bai::Index,
+        index: &I,
         region: &Region,
-    ) -> io::Result<Query<'_, R>> {
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex,
+    {
         let (i, start, end) = resolve_region(reference_sequences, region)?;
+        let chunks = index.query(i, start, end)?;
+        Ok(Query::new(self, chunks, i, start, end))
+    }
 
-        let index_reference_sequence = index.reference_sequences().get(i).ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "could not find reference in index: {} >= {}",
-                    i,
-                    reference_sequences.len()
-                ),
-            )
-        })?;
-
-        let query_bins = index_reference_sequence.query(start, end);
-
-        let chunks: Vec<_> = query_bins
-            .iter()
-            .flat_map(|bin| bin.chunks())
-            .cloned()
-            .collect();
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged before reading, so each compressed block is read at
+    /// most once, and a record that intersects more than one region is only returned once.
+    /// Records are returned in file order. Use [`RegionsQuery::hits`] to also get the regions
+    /// each record intersects.
+    ///
+    /// [`RegionsQuery::hits`]: struct.RegionsQuery.html#method.hits
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header: sam::Header = reader.read_header()?.parse()?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// let index = bai::read("sample.bam.bai")?;
+    /// let regions = [
+    ///     Region::mapped("sq0", 17711, 28657),
+    ///     Region::mapped("sq1", 46368, 75025),
+    /// ];
+    /// let query = reader.query_regions(&reference_sequences, &index, &regions)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<I>(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<RegionsQuery<'_, R>>
+    where
+        I: BinningIndex,
+    {
+        let mut chunks = Vec::new();
+        let mut intervals = Vec::with_capacity(regions.len());
+
+        for (region_index, region) in regions.iter().enumerate() {
+            let (i, start, end) = resolve_region(reference_sequences, region)?;
+            chunks.extend(index.query(i, start, end)?);
+
+            intervals.push(Interval {
+                reference_sequence_id: i,
+                start,
+                end,
+                index: region_index,
+            });
+        }
 
-        let min_offset = index_reference_sequence.min_offset(start);
-        let merged_chunks = bai::optimize_chunks(&chunks, min_offset);
+        // Each region's chunks are already pruned by its own minimum offset in
+        // `BinningIndex::query`. A global minimum offset would drop chunks of regions that
+        // precede it, so the chunks of all regions are only merged here.
+        let chunks = optimize_chunks(&chunks, VirtualPosition::default());
 
-        Ok(Query::new(self, merged_chunks, i, start, end))
+        Ok(RegionsQuery::new(self, chunks, Intervals::new(intervals)))
     }
 
     /// Returns an iterator of unmapped records after querying for the unmapped region.
@@ -416,7 +474,7 @@ fn bytes_with_nul_to_string(buf: &[u8]) -> io::Result<String> {
         })
 }
 
-fn resolve_region(
+pub(crate) fn resolve_region(
     reference_sequences: &ReferenceSequences,
     region: &Region,
 ) -> io::Result<(usize, u64, u64)> {
@@ -1,8 +1,9 @@
 use std::io::{self, Read, Seek};
 
 use noodles_bgzf::VirtualPosition;
+use noodles_csi::index::reference_sequence::bin::Chunk;
 
-use crate::{bai::index::reference_sequence::bin::Chunk, Record};
+use crate::Record;
 
 use super::Reader;
 
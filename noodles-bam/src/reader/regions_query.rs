@@ -0,0 +1,395 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf::VirtualPosition;
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use crate::Record;
+
+use super::Reader;
+
+enum State {
+    Seek,
+    Read(VirtualPosition),
+    End,
+}
+
+// A resolved query region.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Interval {
+    pub reference_sequence_id: usize,
+    pub start: u64,
+    pub end: u64,
+    // The index of the region in the given list of regions.
+    pub index: usize,
+}
+
+// A list of intervals that can be searched for overlaps.
+pub(crate) struct Intervals {
+    // Sorted by (reference sequence ID, start).
+    intervals: Vec<Interval>,
+    // The maximum end of `intervals[..=i]` on the same reference sequence.
+    max_ends: Vec<u64>,
+}
+
+impl Intervals {
+    pub fn new(mut intervals: Vec<Interval>) -> Self {
+        intervals.sort_by_key(|interval| (interval.reference_sequence_id, interval.start));
+
+        let mut max_ends: Vec<u64> = Vec::with_capacity(intervals.len());
+
+        for (i, interval) in intervals.iter().enumerate() {
+            let max_end = match i.checked_sub(1) {
+                Some(j) if intervals[j].reference_sequence_id == interval.reference_sequence_id => {
+                    max_ends[j].max(interval.end)
+                }
+                _ => interval.end,
+            };
+
+            max_ends.push(max_end);
+        }
+
+        Self {
+            intervals,
+            max_ends,
+        }
+    }
+
+    // Returns the intervals that overlap the given 1-based, inclusive interval.
+    pub fn overlapping(
+        &self,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+    ) -> impl Iterator<Item = &Interval> {
+        // The number of intervals that start at or before the end of the given interval.
+        let n = self.intervals.partition_point(|interval| {
+            (interval.reference_sequence_id, interval.start) <= (reference_sequence_id, end)
+        });
+
+        (0..n)
+            .rev()
+            .take_while(move |&j| {
+                self.intervals[j].reference_sequence_id == reference_sequence_id
+                    && self.max_ends[j] >= start
+            })
+            .map(move |j| &self.intervals[j])
+            .filter(move |interval| interval.end >= start)
+    }
+}
+
+/// An iterator over records of a BAM reader that intersect any of a list of regions.
+///
+/// The chunks of all regions are merged, so each compressed block is read at most once, and each
+/// record is returned at most once, in file order.
+///
+/// This is created by calling [`bam::Reader::query_regions`].
+///
+/// [`bam::Reader::query_regions`]: struct.Reader.html#method.query_regions
+pub struct RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<R>,
+    chunks: Vec<Chunk>,
+    intervals: Intervals,
+    i: usize,
+    state: State,
+    record: Record,
+}
+
+impl<'a, R> RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>, chunks: Vec<Chunk>, intervals: Intervals) -> Self {
+        Self {
+            reader,
+            chunks,
+            intervals,
+            i: 0,
+            state: State::Seek,
+            record: Record::default(),
+        }
+    }
+
+    /// Converts this iterator to one that also returns the regions each record intersects.
+    ///
+    /// Each item is a record and the indices of the regions, as given to
+    /// [`bam::Reader::query_regions`], that the record intersects. The indices are sorted.
+    ///
+    /// [`bam::Reader::query_regions`]: struct.Reader.html#method.query_regions
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header: sam::Header = reader.read_header()?.parse()?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// let index = bai::read("sample.bam.bai")?;
+    /// let regions = [
+    ///     Region::mapped("sq0", 8, 13),
+    ///     Region::mapped("sq0", 10, 21),
+    /// ];
+    /// let query = reader.query_regions(&reference_sequences, &index, &regions)?;
+    ///
+    /// for result in query.hits() {
+    ///     let (record, region_indices) = result?;
+    ///     println!("{:?} {:?}", record, region_indices);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn hits(self) -> Hits<'a, R> {
+        Hits { inner: self }
+    }
+
+    fn next_chunk(&mut self) -> io::Result<Option<VirtualPosition>> {
+        if self.i >= self.chunks.len() {
+            return Ok(None);
+        }
+
+        let chunk = self.chunks[self.i];
+        self.reader.seek(chunk.start())?;
+
+        self.i += 1;
+
+        Ok(Some(chunk.end()))
+    }
+
+    fn read_record(&mut self) -> Option<io::Result<Record>> {
+        match self.reader.read_record(&mut self.record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    // Returns the indices of the regions that intersect the given record.
+    fn region_indices(&self, record: &Record) -> Vec<usize> {
+        let reference_sequence_id = match *record.reference_sequence_id() {
+            Some(id) => id as usize,
+            None => return Vec::new(),
+        };
+
+        let record_start = i32::from(record.position()) as u64;
+        let record_reference_len = u64::from(record.cigar().reference_len()).max(1);
+        let record_end = record_start + record_reference_len - 1;
+
+        let mut indices: Vec<_> = self
+            .intervals
+            .overlapping(reference_sequence_id, record_start, record_end)
+            .map(|interval| interval.index)
+            .collect();
+
+        indices.sort_unstable();
+
+        indices
+    }
+
+    fn next_hit(&mut self) -> Option<io::Result<(Record, Vec<usize>)>> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.next_chunk() {
+                        Ok(Some(chunk_end)) => State::Read(chunk_end),
+                        Ok(None) => State::End,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                State::Read(chunk_end) => match self.read_record() {
+                    Some(result) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        match result {
+                            Ok(record) => {
+                                let region_indices = self.region_indices(&record);
+
+                                if !region_indices.is_empty() {
+                                    return Some(Ok((record, region_indices)));
+                                }
+                            }
+                            Err(e) => return Some(Err(e)),
+                        }
+                    }
+                    None => {
+                        self.state = State::Seek;
+                    }
+                },
+                State::End => return None,
+            }
+        }
+    }
+}
+
+impl<'a, R> Iterator for RegionsQuery<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_hit()
+            .map(|result| result.map(|(record, _)| record))
+    }
+}
+
+/// An iterator over records of a BAM reader that intersect any of a list of regions and the
+/// indices of the regions each record intersects.
+///
+/// This is created by calling [`bam::reader::RegionsQuery::hits`].
+///
+/// [`bam::reader::RegionsQuery::hits`]: struct.RegionsQuery.html#method.hits
+pub struct Hits<'a, R>
+where
+    R: Read + Seek,
+{
+    inner: RegionsQuery<'a, R>,
+}
+
+impl<'a, R> Iterator for Hits<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<(Record, Vec<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_hit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles::Region;
+    use noodles_sam::{self as sam, header::ReferenceSequence, record::cigar::op::Kind};
+
+    use crate::{record::aligned_record_builder, Writer};
+
+    use super::*;
+
+    #[test]
+    fn test_hits() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 1000))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 1000))
+            .build();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for record in &[
+            aligned_record_builder(0, 1, &[(Kind::Match, 4)])
+                .set_read_name("r0".parse()?)
+                .build()?,
+            aligned_record_builder(0, 8, &[(Kind::Match, 4)])
+                .set_read_name("r1".parse()?)
+                .build()?,
+            aligned_record_builder(0, 13, &[(Kind::Match, 10)])
+                .set_read_name("r2".parse()?)
+                .build()?,
+            aligned_record_builder(0, 55, &[(Kind::Match, 4)])
+                .set_read_name("r3".parse()?)
+                .build()?,
+            aligned_record_builder(1, 5, &[(Kind::Match, 4)])
+                .set_read_name("r4".parse()?)
+                .build()?,
+        ] {
+            writer.write_record(record)?;
+        }
+
+        let index = writer.try_finish_with_index()?.expect("missing index");
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(Cursor::new(data));
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let reference_sequences = header.reference_sequences();
+        let regions = [
+            Region::mapped("sq1", 1, 100),
+            Region::mapped("sq0", 10, 14),
+            Region::mapped("sq0", 5, 9),
+            Region::mapped("sq0", 20, 30),
+        ];
+
+        let query = reader.query_regions(reference_sequences, &index, &regions)?;
+        let actual: Vec<_> = query
+            .hits()
+            .map(|result| {
+                result.map(|(record, indices)| {
+                    let name = record.read_name();
+                    let name = String::from_utf8_lossy(&name[..name.len() - 1]).into_owned();
+                    (name, indices)
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (String::from("r1"), vec![1, 2]),
+            (String::from("r2"), vec![1, 3]),
+            (String::from("r4"), vec![0]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let query = reader.query_regions(reference_sequences, &index, &regions[1..3])?;
+        assert_eq!(query.count(), 2);
+
+        let regions = [Region::mapped("sq2", 1, 100)];
+        assert!(reader
+            .query_regions(reference_sequences, &index, &regions)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_regions_prunes_chunks_by_min_offset() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 100000))
+            .build();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        // r0 spans the first two linear index windows and is placed in a higher-level bin that
+        // also overlaps r1.
+        for record in &[
+            aligned_record_builder(0, 16380, &[(Kind::Match, 10)])
+                .set_read_name("r0".parse()?)
+                .build()?,
+            aligned_record_builder(0, 40001, &[(Kind::Match, 4)])
+                .set_read_name("r1".parse()?)
+                .build()?,
+        ] {
+            writer.write_record(record)?;
+        }
+
+        let index = writer.try_finish_with_index()?.expect("missing index");
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(Cursor::new(data));
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let reference_sequences = header.reference_sequences();
+        let regions = [Region::mapped("sq0", 40001, 40004)];
+        let query = reader.query_regions(reference_sequences, &index, &regions)?;
+
+        let min_offset = index.reference_sequences()[0].intervals()[2];
+        assert_eq!(query.chunks.len(), 1);
+        assert_eq!(query.chunks[0].start(), min_offset);
+
+        Ok(())
+    }
+}
@@ -1,24 +1,36 @@
 //! BAM record and fields.
 
+mod builder;
 pub mod cigar;
+mod convert;
 pub mod data;
 mod quality_scores;
 mod reference_sequence_id;
 pub mod sequence;
 
 pub use self::{
-    cigar::Cigar, data::Data, quality_scores::QualityScores,
+    builder::Builder, cigar::Cigar, data::Data, quality_scores::QualityScores,
     reference_sequence_id::ReferenceSequenceId, sequence::Sequence,
 };
 
 use std::{
-    ffi::CStr,
-    fmt, mem,
+    convert::TryFrom,
+    ffi::{CStr, CString},
+    fmt, io, mem,
     ops::{Deref, DerefMut},
 };
 
-use byteorder::{ByteOrder, LittleEndian};
-use noodles_sam as sam;
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+use noodles_sam::{self as sam, record::data::field::Tag};
+
+// § 4.2.1 BIN field calculation (2020-04-30)
+const UNMAPPED_BIN: u16 = 4680;
+
+// § 4.2.3 SEQ and QUAL encoding (2020-04-30)
+const NULL_QUALITY_SCORE: u8 = 255;
+
+// § 4.2 The BAM format: `cigar` (2020-04-30). The op length is stored in the upper 28 bits.
+const MAX_CIGAR_OP_LEN: u32 = 1 << 28;
 
 /// A BAM record.
 ///
@@ -39,11 +51,35 @@ use noodles_sam as sam;
 ///
 /// Additionally, it encodes the BAM index bin (`bin`).
 ///
-/// A `bam::Record` wraps a raw byte buffer, and the fields should be considered immutable.
+/// A `bam::Record` wraps a raw byte buffer. Fields can be changed using the `set_*` methods, which
+/// keep the buffer consistent, e.g., updating the lengths and recalculating the index bin. A new
+/// record can be built using [`Record::builder`].
+///
+/// [`Record::builder`]: #method.builder
 #[derive(Clone, Eq, PartialEq)]
 pub struct Record(Vec<u8>);
 
 impl Record {
+    /// Returns a builder to create a record from each of its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_flags(Flags::PAIRED | Flags::READ_1)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.flags(), Flags::PAIRED | Flags::READ_1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
     pub(crate) fn resize(&mut self, new_len: usize) {
         self.0.resize(new_len, Default::default());
     }
@@ -241,7 +277,7 @@ impl Record {
         let offset = 32
             + (self.l_read_name() as usize)
             + mem::size_of::<u32>() * (self.n_cigar_op() as usize);
-        let len = ((self.l_seq() + 1) / 2) as usize;
+        let len = self.encoded_sequence_len();
 
         let bytes = &self.0[offset..offset + len];
         let base_count = self.l_seq() as usize;
@@ -263,7 +299,7 @@ impl Record {
         let offset = 32
             + (self.l_read_name() as usize)
             + mem::size_of::<u32>() * (self.n_cigar_op() as usize)
-            + ((self.l_seq() + 1) / 2) as usize;
+            + self.encoded_sequence_len();
         let len = l_seq as usize;
 
         let bytes = &self.0[offset..offset + len];
@@ -285,13 +321,447 @@ impl Record {
         let offset = 32
             + (self.l_read_name() as usize)
             + mem::size_of::<u32>() * (self.n_cigar_op() as usize)
-            + ((self.l_seq() + 1) / 2) as usize
+            + self.encoded_sequence_len()
             + l_seq as usize;
         let len = self.block_size() as usize;
 
         let bytes = &self.0[offset..len];
         Data::new(bytes)
     }
+
+    /// Sets the reference sequence ID of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, record::ReferenceSequenceId};
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_reference_sequence_id(ReferenceSequenceId::from(1));
+    ///
+    /// assert_eq!(*record.reference_sequence_id(), Some(1));
+    /// ```
+    pub fn set_reference_sequence_id(&mut self, reference_sequence_id: ReferenceSequenceId) {
+        let offset = 0;
+        LittleEndian::write_i32(&mut self.0[offset..], i32::from(reference_sequence_id));
+    }
+
+    /// Sets the start position of this record.
+    ///
+    /// This also recalculates the index bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Position;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_position(Position::from(8));
+    ///
+    /// assert_eq!(*record.position(), Some(8));
+    /// assert_eq!(record.bin(), 4681);
+    /// ```
+    pub fn set_position(&mut self, position: sam::record::Position) {
+        let offset = 4;
+        LittleEndian::write_i32(&mut self.0[offset..], i32::from(position) - 1);
+        self.update_bin();
+    }
+
+    /// Sets the mapping quality of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::MappingQuality;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_mapping_quality(MappingQuality::from(13));
+    ///
+    /// assert_eq!(*record.mapping_quality(), Some(13));
+    /// ```
+    pub fn set_mapping_quality(&mut self, mapping_quality: sam::record::MappingQuality) {
+        let offset = 9;
+        self.0[offset] = u8::from(mapping_quality);
+    }
+
+    /// Sets the SAM flags of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_flags(Flags::PAIRED | Flags::DUPLICATE);
+    ///
+    /// assert_eq!(record.flags(), Flags::PAIRED | Flags::DUPLICATE);
+    /// ```
+    pub fn set_flags(&mut self, flags: sam::record::Flags) {
+        let offset = 14;
+        LittleEndian::write_u16(&mut self.0[offset..], u16::from(flags));
+    }
+
+    /// Sets the reference sequence ID of the mate of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, record::ReferenceSequenceId};
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_mate_reference_sequence_id(ReferenceSequenceId::from(1));
+    ///
+    /// assert_eq!(*record.mate_reference_sequence_id(), Some(1));
+    /// ```
+    pub fn set_mate_reference_sequence_id(&mut self, reference_sequence_id: ReferenceSequenceId) {
+        let offset = 20;
+        LittleEndian::write_i32(&mut self.0[offset..], i32::from(reference_sequence_id));
+    }
+
+    /// Sets the start position of the mate of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Position;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_mate_position(Position::from(13));
+    ///
+    /// assert_eq!(*record.mate_position(), Some(13));
+    /// ```
+    pub fn set_mate_position(&mut self, mate_position: sam::record::Position) {
+        let offset = 24;
+        LittleEndian::write_i32(&mut self.0[offset..], i32::from(mate_position) - 1);
+    }
+
+    /// Sets the template length of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_template_len(144);
+    ///
+    /// assert_eq!(record.template_len(), 144);
+    /// ```
+    pub fn set_template_len(&mut self, template_len: i32) {
+        let offset = 28;
+        LittleEndian::write_i32(&mut self.0[offset..], template_len);
+    }
+
+    /// Sets the read name of this record.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the read name contains a NUL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    ///
+    /// let mut record = bam::Record::default();
+    /// let read_name = "r0".parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    /// record.set_read_name(&read_name)?;
+    ///
+    /// assert_eq!(record.read_name(), b"r0\x00");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_read_name(&mut self, read_name: &sam::record::ReadName) -> io::Result<()> {
+        let c_read_name = CString::new(read_name.as_ref())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let buf = c_read_name.as_bytes_with_nul();
+
+        let l_read_name =
+            u8::try_from(buf.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let start = 32;
+        let end = start + self.l_read_name() as usize;
+        self.0.splice(start..end, buf.iter().copied());
+
+        let offset = 8;
+        self.0[offset] = l_read_name;
+
+        Ok(())
+    }
+
+    /// Sets the CIGAR operations of this record.
+    ///
+    /// This also recalculates the index bin.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if there are more CIGAR operations than can be stored in a BAM record
+    /// or if an operation length is >= 2^28.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::cigar::Op};
+    /// use noodles_sam::record::{cigar::op::Kind, Position};
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_position(Position::from(1));
+    /// record.set_cigar(&[Op::new(Kind::Match, 36), Op::new(Kind::SoftClip, 8)])?;
+    ///
+    /// assert_eq!(record.cigar().to_string(), "36M8S");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_cigar(&mut self, ops: &[cigar::Op]) -> io::Result<()> {
+        let n_cigar_op =
+            u16::try_from(ops.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut buf = Vec::with_capacity(mem::size_of::<u32>() * ops.len());
+
+        for op in ops {
+            if op.len() >= MAX_CIGAR_OP_LEN {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid CIGAR op length: {}", op.len()),
+                ));
+            }
+
+            let value = op.len() << 4 | op.kind() as u32;
+            buf.write_u32::<LittleEndian>(value)?;
+        }
+
+        let start = self.cigar_offset();
+        let end = start + mem::size_of::<u32>() * (self.n_cigar_op() as usize);
+        self.0.splice(start..end, buf);
+
+        let offset = 12;
+        LittleEndian::write_u16(&mut self.0[offset..], n_cigar_op);
+
+        self.update_bin();
+
+        Ok(())
+    }
+
+    /// Sets the sequence of this record.
+    ///
+    /// If the length of the sequence changes, the quality scores are reset to missing (`0xff`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, record::sequence::Base};
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_sequence(&[Base::A, Base::C, Base::G]);
+    ///
+    /// assert_eq!(record.sequence().to_string(), "ACG");
+    /// assert_eq!(*record.quality_scores(), [0xff, 0xff, 0xff]);
+    /// ```
+    pub fn set_sequence(&mut self, bases: &[sequence::Base]) {
+        let l_seq = self.l_seq() as usize;
+
+        let buf: Vec<_> = bases
+            .chunks(2)
+            .map(|chunk| {
+                let l = chunk[0];
+                let r = chunk.get(1).copied().unwrap_or(sequence::Base::Eq);
+                (l as u8) << 4 | (r as u8)
+            })
+            .collect();
+
+        let start = self.sequence_offset();
+        let end = start + self.encoded_sequence_len();
+
+        if bases.len() == l_seq {
+            self.0.splice(start..end, buf);
+        } else {
+            let quality_scores = vec![NULL_QUALITY_SCORE; bases.len()];
+            self.0
+                .splice(start..end + l_seq, buf.into_iter().chain(quality_scores));
+
+            let offset = 16;
+            LittleEndian::write_u32(&mut self.0[offset..], bases.len() as u32);
+        }
+    }
+
+    /// Sets the quality scores of this record.
+    ///
+    /// The scores are raw Phred quality scores, i.e., they are not offset. If the list of scores
+    /// is empty, the quality scores are set to missing (`0xff`).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the number of scores is not the same as the length of the
+    /// sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::sequence::Base};
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_sequence(&[Base::A, Base::C]);
+    /// record.set_quality_scores(&[45, 35])?;
+    ///
+    /// assert_eq!(*record.quality_scores(), [45, 35]);
+    /// assert!(record.set_quality_scores(&[45]).is_err());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_quality_scores(&mut self, quality_scores: &[u8]) -> io::Result<()> {
+        let l_seq = self.l_seq() as usize;
+        let start = self.quality_scores_offset();
+        let dst = &mut self.0[start..start + l_seq];
+
+        if quality_scores.is_empty() {
+            for score in dst.iter_mut() {
+                *score = NULL_QUALITY_SCORE;
+            }
+        } else if quality_scores.len() == l_seq {
+            dst.copy_from_slice(quality_scores);
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "quality scores length does not match sequence length",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the optional data fields of this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::data::{field::Value, Field}};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_data(&[Field::new(Tag::AlignmentHitCount, Value::UInt8(1))])?;
+    ///
+    /// let fields: Vec<_> = record.data().fields().collect::<io::Result<_>>()?;
+    /// assert_eq!(fields, [Field::new(Tag::AlignmentHitCount, Value::UInt8(1))]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_data(&mut self, fields: &[data::Field]) -> io::Result<()> {
+        let mut buf = Vec::new();
+
+        for field in fields {
+            data::writer::write_field(&mut buf, field)?;
+        }
+
+        let start = self.data_offset();
+        self.0.truncate(start);
+        self.0.extend(buf);
+
+        Ok(())
+    }
+
+    /// Inserts a data field into this record.
+    ///
+    /// If a field with the same tag exists, it is replaced in place, and the previous field is
+    /// returned. Otherwise, the field is appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::data::{field::Value, Field}};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let mut record = bam::Record::default();
+    ///
+    /// let field = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+    /// assert!(record.insert_data_field(field)?.is_none());
+    ///
+    /// let field = Field::new(Tag::ReadGroup, Value::String(String::from("rg1")));
+    /// let previous_field = record.insert_data_field(field)?;
+    /// assert_eq!(
+    ///     previous_field,
+    ///     Some(Field::new(Tag::ReadGroup, Value::String(String::from("rg0"))))
+    /// );
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn insert_data_field(&mut self, field: data::Field) -> io::Result<Option<data::Field>> {
+        let mut fields: Vec<_> = self.data().fields().collect::<io::Result<_>>()?;
+
+        let previous_field = match fields.iter_mut().find(|f| f.tag() == field.tag()) {
+            Some(f) => Some(mem::replace(f, field)),
+            None => {
+                fields.push(field);
+                None
+            }
+        };
+
+        self.set_data(&fields)?;
+
+        Ok(previous_field)
+    }
+
+    /// Removes the data field with the given tag from this record.
+    ///
+    /// The removed field is returned, if it exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::data::{field::Value, Field}};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_data(&[Field::new(Tag::AlignmentHitCount, Value::UInt8(1))])?;
+    ///
+    /// assert!(record.remove_data_field(&Tag::AlignmentHitCount)?.is_some());
+    /// assert!(record.data().is_empty());
+    /// assert!(record.remove_data_field(&Tag::AlignmentHitCount)?.is_none());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn remove_data_field(&mut self, tag: &Tag) -> io::Result<Option<data::Field>> {
+        let mut fields: Vec<_> = self.data().fields().collect::<io::Result<_>>()?;
+
+        match fields.iter().position(|f| f.tag() == tag) {
+            Some(i) => {
+                let field = fields.remove(i);
+                self.set_data(&fields)?;
+                Ok(Some(field))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn cigar_offset(&self) -> usize {
+        32 + (self.l_read_name() as usize)
+    }
+
+    fn sequence_offset(&self) -> usize {
+        self.cigar_offset() + mem::size_of::<u32>() * (self.n_cigar_op() as usize)
+    }
+
+    fn quality_scores_offset(&self) -> usize {
+        self.sequence_offset() + self.encoded_sequence_len()
+    }
+
+    // Bases are packed two per byte.
+    fn encoded_sequence_len(&self) -> usize {
+        (self.l_seq() as usize).div_ceil(2)
+    }
+
+    fn data_offset(&self) -> usize {
+        self.quality_scores_offset() + self.l_seq() as usize
+    }
+
+    fn update_bin(&mut self) {
+        let bin = calculate_bin(self.position(), self.cigar().reference_len());
+        let offset = 10;
+        LittleEndian::write_u16(&mut self.0[offset..], bin);
+    }
 }
 
 impl Default for Record {
@@ -357,6 +827,63 @@ impl From<Vec<u8>> for Record {
     }
 }
 
+// § 4.2.1 BIN field calculation (2020-04-30)
+pub(crate) fn calculate_bin(position: sam::record::Position, reference_len: u32) -> u16 {
+    match *position {
+        Some(pos) => {
+            // 0-based, [start, end)
+            let start = pos - 1;
+            let end = start + (reference_len.max(1) as i32);
+            region_to_bin(start, end) as u16
+        }
+        None => UNMAPPED_BIN,
+    }
+}
+
+// § 5.3 C source code for computing bin number and overlapping bins (2020-04-30)
+// 0-based, [start, end)
+fn region_to_bin(start: i32, mut end: i32) -> i32 {
+    end -= 1;
+
+    if start >> 14 == end >> 14 {
+        ((1 << 15) - 1) / 7 + (start >> 14)
+    } else if start >> 17 == end >> 17 {
+        ((1 << 12) - 1) / 7 + (start >> 17)
+    } else if start >> 20 == end >> 20 {
+        ((1 << 9) - 1) / 7 + (start >> 20)
+    } else if start >> 23 == end >> 23 {
+        ((1 << 6) - 1) / 7 + (start >> 23)
+    } else if start >> 26 == end >> 26 {
+        1 + (start >> 26)
+    } else {
+        0
+    }
+}
+
+// Returns a builder for a mapped record with the given alignment.
+//
+// This is shared by tests that need records at specific positions. Other fields can be set on the
+// returned builder.
+#[cfg(test)]
+pub(crate) fn aligned_record_builder(
+    reference_sequence_id: i32,
+    position: i32,
+    cigar: &[(sam::record::cigar::op::Kind, u32)],
+) -> Builder {
+    use sam::record::{Flags, Position};
+
+    Record::builder()
+        .set_flags(Flags::empty())
+        .set_reference_sequence_id(ReferenceSequenceId::from(reference_sequence_id))
+        .set_position(Position::from(position))
+        .set_cigar(
+            cigar
+                .iter()
+                .map(|&(kind, len)| cigar::Op::new(kind, len))
+                .collect(),
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -547,4 +1074,100 @@ mod tests {
         assert_eq!(*record.data(), expected);
         Ok(())
     }
+
+    #[test]
+    fn test_setters() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam::record::{cigar::op::Kind, MappingQuality, Position};
+
+        use self::{
+            cigar::Op,
+            data::{field::Value, Field},
+            sequence::Base,
+        };
+
+        let mut record = build_record()?;
+
+        record.set_reference_sequence_id(ReferenceSequenceId::from(2));
+        record.set_mapping_quality(MappingQuality::from(255));
+        record.set_flags(sam::record::Flags::UNMAPPED);
+        record.set_mate_reference_sequence_id(ReferenceSequenceId::from(-1));
+        record.set_mate_position(Position::from(0));
+        record.set_template_len(0);
+
+        record.set_read_name(&"noodles-bam:1".parse()?)?;
+        record.set_cigar(&[Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 5)])?;
+        record.set_sequence(&[Base::A, Base::C, Base::G, Base::T, Base::N, Base::A]);
+        record.set_quality_scores(&[45, 35, 43, 50, 0, 1])?;
+        record.insert_data_field(Field::new(
+            Tag::ReadGroup,
+            Value::String(String::from("rg0")),
+        ))?;
+        record.set_position(Position::from(8));
+
+        assert_eq!(*record.reference_sequence_id(), Some(2));
+        assert_eq!(*record.position(), Some(8));
+        assert!(record.mapping_quality().is_none());
+        assert_eq!(record.bin(), 4681);
+        assert_eq!(record.flags(), sam::record::Flags::UNMAPPED);
+        assert!(record.mate_reference_sequence_id().is_none());
+        assert!(record.mate_position().is_none());
+        assert_eq!(record.template_len(), 0);
+        assert_eq!(record.read_name(), b"noodles-bam:1\x00");
+        assert_eq!(record.cigar().to_string(), "1S5M");
+        assert_eq!(record.sequence().to_string(), "ACGTNA");
+        assert_eq!(*record.quality_scores(), [45, 35, 43, 50, 0, 1]);
+
+        let fields: Vec<_> = record.data().fields().collect::<io::Result<_>>()?;
+        assert_eq!(
+            fields,
+            [
+                Field::new(Tag::EditDistance, Value::UInt8(0)),
+                Field::new(Tag::Program, Value::String(String::from("SNAP"))),
+                Field::new(Tag::ReadGroup, Value::String(String::from("rg0"))),
+            ]
+        );
+
+        record.set_sequence(&[Base::A]);
+        assert_eq!(*record.quality_scores(), [0xff]);
+        assert_eq!(record.data().fields().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_cigar_with_invalid_op_len() -> io::Result<()> {
+        use sam::record::cigar::op::Kind;
+
+        use super::cigar::Op;
+
+        let mut record = Record::default();
+        record.set_cigar(&[Op::new(Kind::Match, (1 << 28) - 1)])?;
+        assert_eq!(record.cigar().to_string(), "268435455M");
+
+        assert!(matches!(
+            record.set_cigar(&[Op::new(Kind::Match, 1 << 28)]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_bin() {
+        use sam::record::Position;
+
+        assert_eq!(calculate_bin(Position::from(0), 0), UNMAPPED_BIN);
+        assert_eq!(calculate_bin(Position::from(8), 6), 4681);
+        assert_eq!(calculate_bin(Position::from(8), 0), 4681);
+        assert_eq!(calculate_bin(Position::from(16384), 1), 4681);
+        assert_eq!(calculate_bin(Position::from(16384), 2), 585);
+    }
+
+    #[test]
+    fn test_region_to_bin() {
+        // [8, 13]
+        assert_eq!(region_to_bin(7, 13), 4681);
+        // [63245986, 63245986]
+        assert_eq!(region_to_bin(63245985, 63255986), 8541);
+    }
 }
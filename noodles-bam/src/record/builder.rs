@@ -0,0 +1,411 @@
+use std::io;
+
+use noodles_sam::record::{Flags, MappingQuality, Position, ReadName};
+
+use super::{cigar::Op, data::Field, sequence::Base, Record, ReferenceSequenceId};
+
+/// A BAM record builder.
+#[derive(Debug)]
+pub struct Builder {
+    reference_sequence_id: ReferenceSequenceId,
+    position: Position,
+    mapping_quality: MappingQuality,
+    flags: Flags,
+    mate_reference_sequence_id: ReferenceSequenceId,
+    mate_position: Position,
+    template_len: i32,
+    read_name: ReadName,
+    cigar: Vec<Op>,
+    sequence: Vec<Base>,
+    quality_scores: Vec<u8>,
+    data: Vec<Field>,
+}
+
+impl Builder {
+    /// Creates a BAM record builder.
+    ///
+    /// Typically, [`bam::Record::builder`] is used instead of calling
+    /// [`bam::record::Builder::new`].
+    ///
+    /// [`bam::Record::builder`]: struct.Record.html#method.builder
+    /// [`bam::record::Builder::new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Record::builder();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a BAM record reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::ReferenceSequenceId};
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_reference_sequence_id(ReferenceSequenceId::from(1))
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.reference_sequence_id(), Some(1));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_reference_sequence_id(mut self, reference_sequence_id: ReferenceSequenceId) -> Self {
+        self.reference_sequence_id = reference_sequence_id;
+        self
+    }
+
+    /// Sets a BAM record position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Position;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_position(Position::from(8))
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.position(), Some(8));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets a BAM record mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::MappingQuality;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_mapping_quality(MappingQuality::from(13))
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.mapping_quality(), Some(13));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_mapping_quality(mut self, mapping_quality: MappingQuality) -> Self {
+        self.mapping_quality = mapping_quality;
+        self
+    }
+
+    /// Sets BAM record flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_flags(Flags::PAIRED | Flags::READ_1)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.flags(), Flags::PAIRED | Flags::READ_1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_flags(mut self, flags: Flags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets a BAM record mate reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::ReferenceSequenceId};
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_mate_reference_sequence_id(ReferenceSequenceId::from(1))
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.mate_reference_sequence_id(), Some(1));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_mate_reference_sequence_id(
+        mut self,
+        mate_reference_sequence_id: ReferenceSequenceId,
+    ) -> Self {
+        self.mate_reference_sequence_id = mate_reference_sequence_id;
+        self
+    }
+
+    /// Sets a BAM record mate position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Position;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_mate_position(Position::from(13))
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.mate_position(), Some(13));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_mate_position(mut self, mate_position: Position) -> Self {
+        self.mate_position = mate_position;
+        self
+    }
+
+    /// Sets a BAM record template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let record = bam::Record::builder().set_template_len(144).build()?;
+    /// assert_eq!(record.template_len(), 144);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_template_len(mut self, template_len: i32) -> Self {
+        self.template_len = template_len;
+        self
+    }
+
+    /// Sets a BAM record read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    ///
+    /// let read_name = "r0".parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    /// let record = bam::Record::builder().set_read_name(read_name).build()?;
+    ///
+    /// assert_eq!(record.read_name(), b"r0\x00");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_read_name(mut self, read_name: ReadName) -> Self {
+        self.read_name = read_name;
+        self
+    }
+
+    /// Sets BAM record CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::cigar::Op};
+    /// use noodles_sam::record::cigar::op::Kind;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_cigar(vec![Op::new(Kind::Match, 36), Op::new(Kind::SoftClip, 8)])
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.cigar().to_string(), "36M8S");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_cigar(mut self, cigar: Vec<Op>) -> Self {
+        self.cigar = cigar;
+        self
+    }
+
+    /// Sets a BAM record sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::sequence::Base};
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_sequence(vec![Base::A, Base::C, Base::G, Base::T])
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.sequence().to_string(), "ACGT");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_sequence(mut self, sequence: Vec<Base>) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Sets BAM record quality scores.
+    ///
+    /// The scores are raw Phred quality scores. If no scores are set, the quality scores of the
+    /// record are missing (`0xff`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::sequence::Base};
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_sequence(vec![Base::N, Base::D])
+    ///     .set_quality_scores(vec![45, 35])
+    ///     .build()?;
+    ///
+    /// assert_eq!(*record.quality_scores(), [45, 35]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_quality_scores(mut self, quality_scores: Vec<u8>) -> Self {
+        self.quality_scores = quality_scores;
+        self
+    }
+
+    /// Sets BAM record optional data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, record::data::{field::Value, Field}};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// let record = bam::Record::builder()
+    ///     .set_data(vec![Field::new(Tag::AlignmentHitCount, Value::UInt8(1))])
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.data().fields().count(), 1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_data(mut self, data: Vec<Field>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Builds a BAM record.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a field cannot be encoded, e.g., the number of quality scores does
+    /// not match the length of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let record = bam::Record::builder().build()?;
+    /// assert_eq!(record, bam::Record::default());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn build(self) -> io::Result<Record> {
+        let mut record = Record::default();
+
+        record.set_reference_sequence_id(self.reference_sequence_id);
+        record.set_mapping_quality(self.mapping_quality);
+        record.set_flags(self.flags);
+        record.set_mate_reference_sequence_id(self.mate_reference_sequence_id);
+        record.set_mate_position(self.mate_position);
+        record.set_template_len(self.template_len);
+        record.set_read_name(&self.read_name)?;
+        record.set_cigar(&self.cigar)?;
+        record.set_sequence(&self.sequence);
+        record.set_quality_scores(&self.quality_scores)?;
+        record.set_data(&self.data)?;
+
+        // The bin is calculated using the position and CIGAR, so the position is set last.
+        record.set_position(self.position);
+
+        Ok(record)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            reference_sequence_id: ReferenceSequenceId::default(),
+            position: Position::default(),
+            mapping_quality: MappingQuality::default(),
+            flags: Flags::UNMAPPED,
+            mate_reference_sequence_id: ReferenceSequenceId::default(),
+            mate_position: Position::default(),
+            template_len: Default::default(),
+            read_name: ReadName::default(),
+            cigar: Vec::new(),
+            sequence: Vec::new(),
+            quality_scores: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{cigar::op::Kind, data::field::Tag};
+
+    use crate::record::data::field::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_default() -> io::Result<()> {
+        let record = Builder::new().build()?;
+        assert_eq!(record, Record::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Builder::new()
+            .set_reference_sequence_id(ReferenceSequenceId::from(1))
+            .set_position(Position::from(8))
+            .set_mapping_quality(MappingQuality::from(13))
+            .set_flags(Flags::PAIRED | Flags::READ_1)
+            .set_mate_reference_sequence_id(ReferenceSequenceId::from(1))
+            .set_mate_position(Position::from(21))
+            .set_template_len(19)
+            .set_read_name("r0".parse()?)
+            .set_cigar(vec![Op::new(Kind::Match, 4)])
+            .set_sequence(vec![Base::A, Base::C, Base::G])
+            .set_quality_scores(vec![45, 35, 43])
+            .set_data(vec![Field::new(Tag::AlignmentHitCount, Value::UInt8(1))])
+            .build()?;
+
+        assert_eq!(*record.reference_sequence_id(), Some(1));
+        assert_eq!(*record.position(), Some(8));
+        assert_eq!(*record.mapping_quality(), Some(13));
+        assert_eq!(record.bin(), 4681);
+        assert_eq!(record.flags(), Flags::PAIRED | Flags::READ_1);
+        assert_eq!(*record.mate_reference_sequence_id(), Some(1));
+        assert_eq!(*record.mate_position(), Some(21));
+        assert_eq!(record.template_len(), 19);
+        assert_eq!(record.read_name(), b"r0\x00");
+        assert_eq!(record.cigar().to_string(), "4M");
+        assert_eq!(record.sequence().to_string(), "ACG");
+        assert_eq!(*record.quality_scores(), [45, 35, 43]);
+
+        let fields: Vec<_> = record.data().fields().collect::<io::Result<_>>()?;
+        assert_eq!(
+            fields,
+            [Field::new(Tag::AlignmentHitCount, Value::UInt8(1))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_invalid_quality_scores() {
+        let result = Builder::new()
+            .set_sequence(vec![Base::A, Base::C])
+            .set_quality_scores(vec![45])
+            .build();
+
+        assert!(result.is_err());
+    }
+}
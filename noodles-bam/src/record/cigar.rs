@@ -7,7 +7,7 @@ pub use self::{op::Op, ops::Ops};
 
 use std::{fmt, ops::Deref};
 
-use noodles_sam::record::cigar::op::Kind;
+use noodles_sam::record::cigar::{self, AlignedPairs};
 
 /// BAM record CIGAR.
 pub struct Cigar<'a>(&'a [u8]);
@@ -71,14 +71,216 @@ impl<'a> Cigar<'a> {
     /// assert_eq!(cigar.reference_len(), 40);
     /// ```
     pub fn reference_len(&self) -> u32 {
-        self.ops()
-            .filter_map(|op| match op.kind() {
-                Kind::Match | Kind::Deletion | Kind::Skip | Kind::SeqMatch | Kind::SeqMismatch => {
-                    Some(op.len())
-                }
-                _ => None,
-            })
-            .sum()
+        cigar::reference_len(self.ops())
+    }
+
+    /// Calculates the read length.
+    ///
+    /// This sums the lengths of the CIGAR operations that consume the read, i.e., alignment
+    /// matches (`M`), insertions (`I`), soft clips (`S`), sequence matches (`=`), and sequence
+    /// mismatches (`X`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// // 36M4D8S
+    /// let data = [0x40, 0x02, 0x00, 0x00, 0x43, 0x00, 0x00, 0x00, 0x84, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.query_len(), 44);
+    /// ```
+    pub fn query_len(&self) -> u32 {
+        cigar::query_len(self.ops())
+    }
+
+    /// Returns the length of the soft clip at the start of the read.
+    ///
+    /// Hard clips (`H`) before the soft clip are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x25, 0x00, 0x00, 0x00, // 2H
+    ///     0x34, 0x00, 0x00, 0x00, // 3S
+    ///     0x80, 0x00, 0x00, 0x00, // 8M
+    ///     0x54, 0x00, 0x00, 0x00, // 5S
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.leading_soft_clip_len(), 3);
+    /// ```
+    pub fn leading_soft_clip_len(&self) -> u32 {
+        cigar::soft_clip_len(self.ops())
+    }
+
+    /// Returns the length of the soft clip at the end of the read.
+    ///
+    /// Hard clips (`H`) after the soft clip are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x25, 0x00, 0x00, 0x00, // 2H
+    ///     0x34, 0x00, 0x00, 0x00, // 3S
+    ///     0x80, 0x00, 0x00, 0x00, // 8M
+    ///     0x54, 0x00, 0x00, 0x00, // 5S
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.trailing_soft_clip_len(), 5);
+    /// ```
+    pub fn trailing_soft_clip_len(&self) -> u32 {
+        cigar::soft_clip_len(self.ops().rev())
+    }
+
+    /// Calculates the start position of the alignment if the clipped bases were aligned.
+    ///
+    /// This is the 1-based alignment start minus the lengths of the soft and hard clips at the
+    /// start of the read. The result may be less than 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x25, 0x00, 0x00, 0x00, // 2H
+    ///     0x34, 0x00, 0x00, 0x00, // 3S
+    ///     0x80, 0x00, 0x00, 0x00, // 8M
+    ///     0x54, 0x00, 0x00, 0x00, // 5S
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.unclipped_start(13), 8);
+    /// ```
+    pub fn unclipped_start(&self, alignment_start: i32) -> i32 {
+        alignment_start - cigar::clip_len(self.ops()) as i32
+    }
+
+    /// Calculates the end position of the alignment if the clipped bases were aligned.
+    ///
+    /// This is the 1-based, inclusive alignment end plus the lengths of the soft and hard clips at
+    /// the end of the read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x25, 0x00, 0x00, 0x00, // 2H
+    ///     0x34, 0x00, 0x00, 0x00, // 3S
+    ///     0x80, 0x00, 0x00, 0x00, // 8M
+    ///     0x54, 0x00, 0x00, 0x00, // 5S
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.unclipped_end(13), 25);
+    /// ```
+    pub fn unclipped_end(&self, alignment_start: i32) -> i32 {
+        let alignment_end = alignment_start + self.reference_len() as i32 - 1;
+        alignment_end + cigar::clip_len(self.ops().rev()) as i32
+    }
+
+    /// Returns an iterator over the aligned read offset and reference position pairs.
+    ///
+    /// Read offsets are 0-based and include soft clipped bases. `alignment_start` and the
+    /// reference positions are 1-based. See [`sam::record::cigar::AlignedPairs`] for the pairs
+    /// emitted by each operation.
+    ///
+    /// [`sam::record::cigar::AlignedPairs`]: ../../noodles_sam/record/cigar/struct.AlignedPairs.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x14, 0x00, 0x00, 0x00, // 1S
+    ///     0x20, 0x00, 0x00, 0x00, // 2M
+    ///     0x11, 0x00, 0x00, 0x00, // 1I
+    ///     0x12, 0x00, 0x00, 0x00, // 1D
+    ///     0x10, 0x00, 0x00, 0x00, // 1M
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// let pairs: Vec<_> = cigar.aligned_pairs(8).collect();
+    ///
+    /// assert_eq!(pairs, [
+    ///     (Some(0), None),
+    ///     (Some(1), Some(8)),
+    ///     (Some(2), Some(9)),
+    ///     (Some(3), None),
+    ///     (None, Some(10)),
+    ///     (Some(4), Some(11)),
+    /// ]);
+    /// ```
+    pub fn aligned_pairs(&self, alignment_start: i32) -> AlignedPairs<Ops<'_>> {
+        AlignedPairs::new(self.ops(), alignment_start)
+    }
+
+    /// Returns the reference position aligned to the given read offset.
+    ///
+    /// The read offset is 0-based and includes soft clipped bases. `alignment_start` and the
+    /// returned position are 1-based. This returns `None` if the read offset is in an insertion
+    /// or soft clip or is past the end of the read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x14, 0x00, 0x00, 0x00, // 1S
+    ///     0x20, 0x00, 0x00, 0x00, // 2M
+    ///     0x11, 0x00, 0x00, 0x00, // 1I
+    ///     0x12, 0x00, 0x00, 0x00, // 1D
+    ///     0x10, 0x00, 0x00, 0x00, // 1M
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.reference_position(8, 0), None);
+    /// assert_eq!(cigar.reference_position(8, 2), Some(9));
+    /// assert_eq!(cigar.reference_position(8, 4), Some(11));
+    /// ```
+    pub fn reference_position(&self, alignment_start: i32, read_offset: usize) -> Option<i32> {
+        cigar::reference_position(self.ops(), alignment_start, read_offset)
+    }
+
+    /// Returns the read offset aligned to the given reference position.
+    ///
+    /// `alignment_start` and the reference position are 1-based. The returned read offset is
+    /// 0-based and includes soft clipped bases. This returns `None` if the reference position is
+    /// in a deletion or skipped region or is outside the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Cigar;
+    ///
+    /// let data = [
+    ///     0x14, 0x00, 0x00, 0x00, // 1S
+    ///     0x20, 0x00, 0x00, 0x00, // 2M
+    ///     0x11, 0x00, 0x00, 0x00, // 1I
+    ///     0x12, 0x00, 0x00, 0x00, // 1D
+    ///     0x10, 0x00, 0x00, 0x00, // 1M
+    /// ];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.read_offset(8, 9), Some(2));
+    /// assert_eq!(cigar.read_offset(8, 10), None);
+    /// assert_eq!(cigar.read_offset(8, 11), Some(4));
+    /// ```
+    pub fn read_offset(&self, alignment_start: i32, reference_position: i32) -> Option<usize> {
+        cigar::read_offset(self.ops(), alignment_start, reference_position)
     }
 }
 
@@ -121,4 +323,42 @@ mod tests {
         assert_eq!(ops.next(), Some(Op::try_from(0x362).unwrap()));
         assert_eq!(ops.next(), None);
     }
+
+    #[test]
+    fn test_coordinates() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam as sam;
+
+        let sam_cigar: sam::record::Cigar = "2H1S1=1P1X2N1I1M3H".parse()?;
+
+        let data: Vec<u8> = sam_cigar
+            .iter()
+            .map(|op| u32::from(Op::new(op.kind(), op.len())))
+            .flat_map(|n| n.to_le_bytes().to_vec())
+            .collect();
+        let cigar = Cigar::new(&data);
+
+        assert_eq!(cigar.query_len(), sam_cigar.query_len());
+        assert_eq!(cigar.leading_soft_clip_len(), 1);
+        assert_eq!(cigar.trailing_soft_clip_len(), 0);
+        assert_eq!(cigar.unclipped_start(5), 2);
+        assert_eq!(cigar.unclipped_end(5), 12);
+
+        assert!(cigar.aligned_pairs(5).eq(sam_cigar.aligned_pairs(5)));
+
+        for read_offset in 0..6 {
+            assert_eq!(
+                cigar.reference_position(5, read_offset),
+                sam_cigar.reference_position(5, read_offset)
+            );
+        }
+
+        for reference_position in 4..11 {
+            assert_eq!(
+                cigar.read_offset(5, reference_position),
+                sam_cigar.read_offset(5, reference_position)
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use std::{convert::TryFrom, error, fmt};
 
 use byteorder::{ByteOrder, LittleEndian};
-use noodles_sam::record::cigar::op::Kind;
+use noodles_sam::{self as sam, record::cigar::op::Kind};
 
 /// A BAM record CIGAR operation.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -133,6 +133,12 @@ impl From<Op> for u32 {
     }
 }
 
+impl From<Op> for sam::record::cigar::Op {
+    fn from(op: Op) -> Self {
+        Self::new(op.kind(), op.len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
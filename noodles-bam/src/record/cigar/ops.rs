@@ -10,11 +10,13 @@ use super::Op;
 pub struct Ops<'a> {
     cigar: &'a [u8],
     i: usize,
+    j: usize,
 }
 
 impl<'a> Ops<'a> {
     pub(crate) fn new(cigar: &'a [u8]) -> Self {
-        Self { cigar, i: 0 }
+        let j = cigar.len() / mem::size_of::<u32>();
+        Self { cigar, i: 0, j }
     }
 }
 
@@ -22,20 +24,32 @@ impl<'a> Iterator for Ops<'a> {
     type Item = Op;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let size = mem::size_of::<u32>();
-        let start = self.i * size;
-
-        if start < self.cigar.len() {
-            let end = start + size;
-
-            let data = &self.cigar[start..end];
-            let op = Op::try_from(data).unwrap();
-
+        if self.i < self.j {
+            let op = read_op(self.cigar, self.i);
             self.i += 1;
-
             Some(op)
         } else {
             None
         }
     }
 }
+
+impl<'a> DoubleEndedIterator for Ops<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.i < self.j {
+            self.j -= 1;
+            Some(read_op(self.cigar, self.j))
+        } else {
+            None
+        }
+    }
+}
+
+fn read_op(cigar: &[u8], i: usize) -> Op {
+    let size = mem::size_of::<u32>();
+    let start = i * size;
+    let end = start + size;
+
+    let data = &cigar[start..end];
+    Op::try_from(data).unwrap()
+}
@@ -0,0 +1,376 @@
+use std::{convert::TryFrom, io, str};
+
+use noodles_sam::{
+    self as sam,
+    header::ReferenceSequences,
+    record::{
+        data::field::Value as SamValue, Cigar, Data, MateReferenceSequenceName, QualityScores,
+        ReadName, ReferenceSequenceName, Sequence,
+    },
+};
+
+use super::{data::field::Value, Record, ReferenceSequenceId, NULL_QUALITY_SCORE};
+
+impl Record {
+    /// Converts this BAM record to a SAM record.
+    ///
+    /// Reference sequence IDs are resolved to names using the given reference sequences. A mate
+    /// on the same reference sequence as the record is set to `=`.
+    ///
+    /// Because SAM only has one integer type, BAM data field integer values (`c`, `C`, `s`, `S`,
+    /// `i`, and `I`) are converted to 32-bit integers (`i`). Array values keep their subtype.
+    ///
+    /// SAM integers range from -2^31 to 2^32 - 1. An unsigned 32-bit integer (`I`) value greater
+    /// than `i32::MAX` is converted to a [`sam::record::data::field::Value::UInt32`], which is
+    /// written back to BAM as `I`.
+    ///
+    /// [`sam::record::data::field::Value::UInt32`]: ../../noodles_sam/record/data/field/enum.Value.html#variant.UInt32
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a reference sequence ID is not in the reference sequences or if
+    /// any field is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::header::ReferenceSequences;
+    ///
+    /// let record = bam::Record::default();
+    /// let sam_record = record.try_into_sam_record(&ReferenceSequences::default())?;
+    ///
+    /// assert!(sam_record.read_name().is_empty());
+    /// assert!(sam_record.reference_sequence_name().is_none());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_into_sam_record(
+        &self,
+        reference_sequences: &ReferenceSequences,
+    ) -> io::Result<sam::Record> {
+        let mut builder = sam::Record::builder()
+            .set_read_name(read_name(self.read_name())?)
+            .set_flags(self.flags())
+            .set_position(self.position())
+            .set_mapping_quality(self.mapping_quality())
+            .set_cigar(cigar(self))
+            .set_mate_position(self.mate_position())
+            .set_template_len(self.template_len())
+            .set_sequence(sequence(self))
+            .set_quality_scores(quality_scores(self)?)
+            .set_data(data(self)?);
+
+        let reference_sequence_id = self.reference_sequence_id();
+
+        if let Some(name) = reference_sequence_name(reference_sequences, reference_sequence_id)? {
+            let reference_sequence_name = name
+                .parse::<ReferenceSequenceName>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            builder = builder.set_reference_sequence_name(reference_sequence_name);
+        }
+
+        let mate_reference_sequence_id = self.mate_reference_sequence_id();
+
+        let mate_reference_sequence_name = if mate_reference_sequence_id.is_some()
+            && mate_reference_sequence_id == reference_sequence_id
+        {
+            MateReferenceSequenceName::Eq
+        } else {
+            match reference_sequence_name(reference_sequences, mate_reference_sequence_id)? {
+                Some(name) => MateReferenceSequenceName::Some(name.into()),
+                None => MateReferenceSequenceName::None,
+            }
+        };
+
+        builder = builder.set_mate_reference_sequence_name(mate_reference_sequence_name);
+
+        Ok(builder.build())
+    }
+}
+
+fn reference_sequence_name(
+    reference_sequences: &ReferenceSequences,
+    reference_sequence_id: ReferenceSequenceId,
+) -> io::Result<Option<&str>> {
+    match *reference_sequence_id {
+        Some(id) => usize::try_from(id)
+            .ok()
+            .and_then(|i| reference_sequences.get_index(i))
+            .map(|(name, _)| Some(name.as_str()))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence id")
+            }),
+        None => Ok(None),
+    }
+}
+
+fn read_name(buf: &[u8]) -> io::Result<ReadName> {
+    let buf = buf.strip_suffix(b"\0").unwrap_or(buf);
+
+    str::from_utf8(buf)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .and_then(|s| {
+            s.parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+}
+
+fn cigar(record: &Record) -> Cigar {
+    let ops: Vec<_> = record
+        .cigar()
+        .ops()
+        .map(|op| sam::record::cigar::Op::new(op.kind(), op.len()))
+        .collect();
+
+    Cigar::from(ops)
+}
+
+fn sequence(record: &Record) -> Sequence {
+    let bases: Vec<_> = record
+        .sequence()
+        .bases()
+        .map(sam::record::sequence::Base::from)
+        .collect();
+
+    Sequence::from(bases)
+}
+
+fn quality_scores(record: &Record) -> io::Result<QualityScores> {
+    let raw_quality_scores = record.quality_scores();
+
+    if raw_quality_scores.iter().all(|&n| n == NULL_QUALITY_SCORE) {
+        return Ok(QualityScores::default());
+    }
+
+    raw_quality_scores
+        .iter()
+        .map(|&n| {
+            sam::record::quality_scores::Score::try_from(n)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect::<io::Result<Vec<_>>>()
+        .map(QualityScores::from)
+}
+
+fn data(record: &Record) -> io::Result<Data> {
+    let mut fields = Vec::new();
+
+    for result in record.data().fields() {
+        let field = result?;
+        let value = value(field.value().clone());
+        fields.push(sam::record::data::Field::new(field.tag().clone(), value));
+    }
+
+    Ok(Data::from(fields))
+}
+
+fn value(value: Value) -> SamValue {
+    match value {
+        Value::Char(c) => SamValue::Char(c),
+        Value::Int8(n) => SamValue::Int32(i32::from(n)),
+        Value::UInt8(n) => SamValue::Int32(i32::from(n)),
+        Value::Int16(n) => SamValue::Int32(i32::from(n)),
+        Value::UInt16(n) => SamValue::Int32(i32::from(n)),
+        Value::Int32(n) => SamValue::Int32(n),
+        Value::UInt32(n) => i32::try_from(n)
+            .map(SamValue::Int32)
+            .unwrap_or(SamValue::UInt32(n)),
+        Value::Float(n) => SamValue::Float(n),
+        Value::String(s) => SamValue::String(s),
+        Value::Hex(s) => SamValue::Hex(s),
+        Value::Int8Array(values) => SamValue::Int8Array(values),
+        Value::UInt8Array(values) => SamValue::UInt8Array(values),
+        Value::Int16Array(values) => SamValue::Int16Array(values),
+        Value::UInt16Array(values) => SamValue::UInt16Array(values),
+        Value::Int32Array(values) => SamValue::Int32Array(values),
+        Value::UInt32Array(values) => SamValue::UInt32Array(values),
+        Value::FloatArray(values) => SamValue::FloatArray(values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::{
+        header::ReferenceSequence,
+        record::{data::field::Tag, Flags, Position},
+    };
+
+    use crate::writer::record::write_sam_record;
+
+    use super::*;
+
+    fn build_reference_sequences() -> ReferenceSequences {
+        vec![
+            (
+                String::from("sq0"),
+                ReferenceSequence::new(String::from("sq0"), 8),
+            ),
+            (
+                String::from("sq1"),
+                ReferenceSequence::new(String::from("sq1"), 13),
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn round_trip(
+        reference_sequences: &ReferenceSequences,
+        sam_record: &sam::Record,
+    ) -> io::Result<sam::Record> {
+        let mut buf = Vec::new();
+        write_sam_record(&mut buf, reference_sequences, sam_record)?;
+
+        // Skip block_size.
+        let record = Record::from(buf[4..].to_vec());
+        record.try_into_sam_record(reference_sequences)
+    }
+
+    #[test]
+    fn test_try_into_sam_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::data::Field;
+
+        let reference_sequences = build_reference_sequences();
+
+        let data = Data::from(vec![
+            Field::new(Tag::AlignmentHitCount, SamValue::Int32(1)),
+            Field::new(Tag::Other(String::from("X0")), SamValue::Char('n')),
+            Field::new(Tag::Other(String::from("X1")), SamValue::Float(0.5)),
+            Field::new(Tag::Comment, SamValue::String(String::from("noodles"))),
+            Field::new(
+                Tag::Other(String::from("X2")),
+                SamValue::Hex(String::from("CAFE")),
+            ),
+            Field::new(
+                Tag::Other(String::from("X3")),
+                SamValue::Int8Array(vec![-1, 0, 1]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X4")),
+                SamValue::UInt8Array(vec![0, 255]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X5")),
+                SamValue::Int16Array(vec![-256, 256]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X6")),
+                SamValue::UInt16Array(vec![0, 65535]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X7")),
+                SamValue::Int32Array(vec![i32::MIN, i32::MAX]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X8")),
+                SamValue::UInt32Array(vec![0, u32::MAX]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X9")),
+                SamValue::FloatArray(vec![0.0, 1.5]),
+            ),
+            Field::new(Tag::Other(String::from("XU")), SamValue::UInt32(u32::MAX)),
+        ]);
+
+        let sam_record = sam::Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::PAIRED | Flags::READ_1)
+            .set_reference_sequence_name("sq1".parse()?)
+            .set_position(Position::from(5))
+            .set_mapping_quality(sam::record::MappingQuality::from(13))
+            .set_cigar("2M1I1S".parse()?)
+            .set_mate_reference_sequence_name(MateReferenceSequenceName::Some(String::from("sq0")))
+            .set_mate_position(Position::from(3))
+            .set_template_len(-8)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data(data)
+            .build();
+
+        assert_eq!(round_trip(&reference_sequences, &sam_record)?, sam_record);
+
+        let sam_record = sam::Record::builder()
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::from(1))
+            .set_mate_reference_sequence_name(MateReferenceSequenceName::Eq)
+            .set_mate_position(Position::from(5))
+            .set_sequence("NN".parse()?)
+            .build();
+
+        assert_eq!(round_trip(&reference_sequences, &sam_record)?, sam_record);
+
+        let sam_record = sam::Record::default();
+        assert_eq!(round_trip(&reference_sequences, &sam_record)?, sam_record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_sam_record_with_invalid_reference_sequence_id() -> io::Result<()> {
+        let reference_sequences = build_reference_sequences();
+
+        let sam_record = sam::Record::builder()
+            .set_reference_sequence_name("sq1".parse().unwrap())
+            .build();
+
+        let mut buf = Vec::new();
+        write_sam_record(&mut buf, &reference_sequences, &sam_record)?;
+        let record = Record::from(buf[4..].to_vec());
+
+        let mut reference_sequences = reference_sequences;
+        reference_sequences.pop();
+
+        assert!(record.try_into_sam_record(&reference_sequences).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!(value(Value::Int8(-1)), SamValue::Int32(-1));
+        assert_eq!(value(Value::UInt8(255)), SamValue::Int32(255));
+        assert_eq!(value(Value::Int16(-256)), SamValue::Int32(-256));
+        assert_eq!(value(Value::UInt16(65535)), SamValue::Int32(65535));
+        assert_eq!(value(Value::UInt32(13)), SamValue::Int32(13));
+        assert_eq!(
+            value(Value::UInt32(i32::MAX as u32)),
+            SamValue::Int32(i32::MAX)
+        );
+        assert_eq!(
+            value(Value::UInt32(i32::MAX as u32 + 1)),
+            SamValue::UInt32(i32::MAX as u32 + 1)
+        );
+        assert_eq!(value(Value::UInt32(u32::MAX)), SamValue::UInt32(u32::MAX));
+    }
+
+    #[test]
+    fn test_try_into_sam_record_with_uint32_data_field_value_greater_than_i32_max() -> io::Result<()>
+    {
+        let reference_sequences = build_reference_sequences();
+
+        let mut buf = Vec::new();
+        write_sam_record(&mut buf, &reference_sequences, &sam::Record::default())?;
+
+        // XU:I:2147483648
+        buf.extend_from_slice(b"XUI");
+        buf.extend_from_slice(&(i32::MAX as u32 + 1).to_le_bytes());
+
+        let record = Record::from(buf.split_off(4));
+        let sam_record = record.try_into_sam_record(&reference_sequences)?;
+
+        let tag = Tag::Other(String::from("XU"));
+        assert_eq!(
+            sam_record.data().get(&tag),
+            Some(&SamValue::UInt32(i32::MAX as u32 + 1))
+        );
+
+        // It is written back as an unsigned 32-bit integer.
+        let mut actual = Vec::new();
+        write_sam_record(&mut actual, &reference_sequences, &sam_record)?;
+        assert_eq!(actual.split_off(4), &record[..]);
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod field;
 pub mod reader;
+pub(crate) mod writer;
 
 pub use self::{field::Field, reader::Reader};
 
@@ -326,6 +326,29 @@ impl Value {
         self.as_uint32().is_some()
     }
 
+    /// Returns the value as a 64-bit integer if it is any single-value integer type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::data::field::Value;
+    /// assert_eq!(Value::UInt8(8).as_int(), Some(8));
+    /// assert_eq!(Value::Int16(-13).as_int(), Some(-13));
+    /// assert_eq!(Value::UInt32(21).as_int(), Some(21));
+    /// assert_eq!(Value::Float(0.0).as_int(), None);
+    /// ```
+    pub fn as_int(&self) -> Option<i64> {
+        match *self {
+            Self::Int8(n) => Some(i64::from(n)),
+            Self::UInt8(n) => Some(i64::from(n)),
+            Self::Int16(n) => Some(i64::from(n)),
+            Self::UInt16(n) => Some(i64::from(n)),
+            Self::Int32(n) => Some(i64::from(n)),
+            Self::UInt32(n) => Some(i64::from(n)),
+            _ => None,
+        }
+    }
+
     /// Returns the value as a single-precision floating-point if it is a single-precision
     /// float-point.
     ///
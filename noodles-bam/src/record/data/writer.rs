@@ -0,0 +1,190 @@
+use std::{
+    ffi::CString,
+    io::{self, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{field::Value, Field};
+
+pub(crate) fn write_field<W>(writer: &mut W, field: &Field) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(field.tag().as_ref().as_bytes())?;
+
+    let value = field.value();
+    writer.write_u8(char::from(value.ty()) as u8)?;
+
+    if let Some(subtype) = value.subtype() {
+        writer.write_u8(char::from(subtype) as u8)?;
+    }
+
+    write_value(writer, value)
+}
+
+fn write_value<W>(writer: &mut W, value: &Value) -> io::Result<()>
+where
+    W: Write,
+{
+    match value {
+        Value::Char(c) => writer.write_u8(*c as u8),
+        Value::Int8(n) => writer.write_i8(*n),
+        Value::UInt8(n) => writer.write_u8(*n),
+        Value::Int16(n) => writer.write_i16::<LittleEndian>(*n),
+        Value::UInt16(n) => writer.write_u16::<LittleEndian>(*n),
+        Value::Int32(n) => writer.write_i32::<LittleEndian>(*n),
+        Value::UInt32(n) => writer.write_u32::<LittleEndian>(*n),
+        Value::Float(n) => writer.write_f32::<LittleEndian>(*n),
+        Value::String(s) | Value::Hex(s) => {
+            let c_str = CString::new(s.as_bytes())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            writer.write_all(c_str.as_bytes_with_nul())
+        }
+        Value::Int8Array(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_i8(n)?;
+            }
+
+            Ok(())
+        }
+        Value::UInt8Array(values) => {
+            write_array_len(writer, values.len())?;
+            writer.write_all(values)
+        }
+        Value::Int16Array(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_i16::<LittleEndian>(n)?;
+            }
+
+            Ok(())
+        }
+        Value::UInt16Array(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_u16::<LittleEndian>(n)?;
+            }
+
+            Ok(())
+        }
+        Value::Int32Array(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_i32::<LittleEndian>(n)?;
+            }
+
+            Ok(())
+        }
+        Value::UInt32Array(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_u32::<LittleEndian>(n)?;
+            }
+
+            Ok(())
+        }
+        Value::FloatArray(values) => {
+            write_array_len(writer, values.len())?;
+
+            for &n in values {
+                writer.write_f32::<LittleEndian>(n)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn write_array_len<W>(writer: &mut W, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    if len > i32::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid array length",
+        ));
+    }
+
+    writer.write_u32::<LittleEndian>(len as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::data::field::Tag;
+
+    use super::{super::Reader, *};
+
+    #[test]
+    fn test_write_field() -> io::Result<()> {
+        let fields = [
+            Field::new(Tag::Other(String::from("X0")), Value::Char('n')),
+            Field::new(Tag::Other(String::from("X1")), Value::Int8(-1)),
+            Field::new(Tag::Other(String::from("X2")), Value::UInt8(255)),
+            Field::new(Tag::Other(String::from("X3")), Value::Int16(-256)),
+            Field::new(Tag::Other(String::from("X4")), Value::UInt16(65535)),
+            Field::new(Tag::AlignmentHitCount, Value::Int32(1)),
+            Field::new(Tag::Other(String::from("X5")), Value::UInt32(u32::MAX)),
+            Field::new(Tag::Other(String::from("X6")), Value::Float(0.5)),
+            Field::new(Tag::ReadGroup, Value::String(String::from("rg0"))),
+            Field::new(
+                Tag::Other(String::from("X7")),
+                Value::Hex(String::from("CAFE")),
+            ),
+            Field::new(
+                Tag::Other(String::from("X8")),
+                Value::Int8Array(vec![-1, 1]),
+            ),
+            Field::new(
+                Tag::Other(String::from("X9")),
+                Value::UInt8Array(vec![0, 255]),
+            ),
+            Field::new(
+                Tag::Other(String::from("Y0")),
+                Value::Int16Array(vec![-256]),
+            ),
+            Field::new(
+                Tag::Other(String::from("Y1")),
+                Value::UInt16Array(vec![65535]),
+            ),
+            Field::new(
+                Tag::Other(String::from("Y2")),
+                Value::Int32Array(vec![i32::MIN]),
+            ),
+            Field::new(
+                Tag::Other(String::from("Y3")),
+                Value::UInt32Array(vec![u32::MAX]),
+            ),
+            Field::new(
+                Tag::Other(String::from("Y4")),
+                Value::FloatArray(vec![1.5, 0.0]),
+            ),
+        ];
+
+        let mut buf = Vec::new();
+
+        for field in &fields {
+            write_field(&mut buf, field)?;
+        }
+
+        let actual = Reader::new(&buf[..])
+            .fields()
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual, fields);
+
+        let mut buf = Vec::new();
+        let field = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+        write_field(&mut buf, &field)?;
+        assert_eq!(buf, [0x52, 0x47, 0x5a, 0x72, 0x67, 0x30, 0x00]);
+
+        Ok(())
+    }
+}
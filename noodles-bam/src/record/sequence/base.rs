@@ -107,6 +107,29 @@ impl From<sam::record::sequence::Base> for Base {
     }
 }
 
+impl From<Base> for sam::record::sequence::Base {
+    fn from(base: Base) -> Self {
+        match base {
+            Base::Eq => Self::Eq,
+            Base::A => Self::A,
+            Base::C => Self::C,
+            Base::M => Self::M,
+            Base::G => Self::G,
+            Base::R => Self::R,
+            Base::S => Self::S,
+            Base::V => Self::V,
+            Base::T => Self::T,
+            Base::W => Self::W,
+            Base::Y => Self::Y,
+            Base::H => Self::H,
+            Base::K => Self::K,
+            Base::D => Self::D,
+            Base::B => Self::B,
+            Base::N => Self::N,
+        }
+    }
+}
+
 impl From<Base> for char {
     fn from(base: Base) -> Self {
         match base {
@@ -217,4 +240,26 @@ mod tests {
         assert_eq!(char::from(Base::B), 'B');
         assert_eq!(char::from(Base::N), 'N');
     }
+
+    #[test]
+    fn test_from_base_for_sam_base() {
+        use sam::record::sequence::Base as SamBase;
+
+        assert_eq!(SamBase::from(Base::Eq), SamBase::Eq);
+        assert_eq!(SamBase::from(Base::A), SamBase::A);
+        assert_eq!(SamBase::from(Base::C), SamBase::C);
+        assert_eq!(SamBase::from(Base::G), SamBase::G);
+        assert_eq!(SamBase::from(Base::T), SamBase::T);
+        assert_eq!(SamBase::from(Base::W), SamBase::W);
+        assert_eq!(SamBase::from(Base::S), SamBase::S);
+        assert_eq!(SamBase::from(Base::M), SamBase::M);
+        assert_eq!(SamBase::from(Base::K), SamBase::K);
+        assert_eq!(SamBase::from(Base::R), SamBase::R);
+        assert_eq!(SamBase::from(Base::Y), SamBase::Y);
+        assert_eq!(SamBase::from(Base::B), SamBase::B);
+        assert_eq!(SamBase::from(Base::D), SamBase::D);
+        assert_eq!(SamBase::from(Base::H), SamBase::H);
+        assert_eq!(SamBase::from(Base::V), SamBase::V);
+        assert_eq!(SamBase::from(Base::N), SamBase::N);
+    }
 }
@@ -0,0 +1,537 @@
+//! BAM record sorting.
+//!
+//! Records are sorted using an external merge sort. Records are buffered in memory until a memory
+//! limit is reached. The buffer is then sorted and written to disk as a BGZF-compressed run. After
+//! all records are read, the runs are merged into the output.
+
+mod key;
+
+pub use self::key::Key;
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use noodles_sam::{
+    self as sam,
+    header::header::{GroupOrder, SortOrder, SubsortOrder},
+};
+
+pub(crate) use self::key::{CoordinateSortCheck, RecordKey};
+use super::{Reader, Record, Writer};
+
+// 768 MiB, the default used by `samtools sort`.
+const DEFAULT_MEMORY_LIMIT: usize = 768 * 1024 * 1024;
+
+// An approximation of the per-record overhead of a buffered record.
+const RECORD_OVERHEAD: usize = mem::size_of::<(RecordKey, Record)>();
+
+/// A BAM record sorter.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, sort::{Key, Sorter}};
+/// use noodles_sam as sam;
+///
+/// let header = sam::Header::default();
+/// let records = vec![Ok(bam::Record::default())];
+///
+/// let mut writer = bam::Writer::new(Vec::new());
+/// Sorter::new(Key::Coordinate).sort(&header, records, &mut writer)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sorter {
+    key: Key,
+    memory_limit: usize,
+    temp_dir: PathBuf,
+}
+
+impl Sorter {
+    /// Creates a BAM record sorter.
+    ///
+    /// By default, the memory limit is 768 MiB, and temporary runs are written to the system
+    /// temporary directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::sort::{Key, Sorter};
+    /// let sorter = Sorter::new(Key::Coordinate);
+    /// ```
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            temp_dir: env::temp_dir(),
+        }
+    }
+
+    /// Sets the approximate maximum number of bytes of records to hold in memory.
+    ///
+    /// When the limit is reached, the buffered records are sorted and written to a temporary run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::sort::{Key, Sorter};
+    /// let sorter = Sorter::new(Key::Coordinate).set_memory_limit(64 * 1024 * 1024);
+    /// ```
+    pub fn set_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets the directory in which temporary runs are written.
+    ///
+    /// A uniquely named subdirectory is created in this directory and is removed when sorting
+    /// finishes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::sort::{Key, Sorter};
+    /// let sorter = Sorter::new(Key::Coordinate).set_temp_dir("/tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = temp_dir.into();
+        self
+    }
+
+    /// Sorts records and writes them to the given BAM writer.
+    ///
+    /// The header is written with its sort order (`@HD SO`) set for the sort key, followed by the
+    /// reference sequences and the sorted records. Sorting is stable: records that compare equal
+    /// keep their input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, sort::{Key, Sorter}};
+    /// use noodles_sam as sam;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = vec![Ok(bam::Record::default())];
+    ///
+    /// let mut writer = bam::Writer::new(Vec::new());
+    /// Sorter::new(Key::QueryName).sort(&header, records, &mut writer)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn sort<I, W>(
+        &self,
+        header: &sam::Header,
+        records: I,
+        writer: &mut Writer<W>,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+        W: Write,
+    {
+        let sorted_header = build_sorted_header(header, self.key);
+        writer.write_header(&sorted_header)?;
+        writer.write_reference_sequences(sorted_header.reference_sequences())?;
+
+        let mut buf = Vec::new();
+        let mut buf_size = 0;
+        let mut temp_dir = None;
+        let mut run_paths = Vec::new();
+
+        for result in records {
+            let record = result?;
+            let key = RecordKey::new(self.key, header, &record)?;
+
+            buf_size += record.len() + key.heap_size() + RECORD_OVERHEAD;
+            buf.push((key, record));
+
+            if buf_size >= self.memory_limit {
+                let dir = match temp_dir {
+                    Some(ref dir) => dir,
+                    None => temp_dir.get_or_insert(TempDir::new(&self.temp_dir)?),
+                };
+
+                let path = dir.path().join(format!("{}.bam", run_paths.len()));
+                write_run(&path, &mut buf)?;
+                run_paths.push(path);
+
+                buf_size = 0;
+            }
+        }
+
+        sort_buf(&mut buf);
+
+        if run_paths.is_empty() {
+            for (_, record) in buf {
+                writer.write_record(&record)?;
+            }
+
+            return Ok(());
+        }
+
+        merge(self.key, header, &run_paths, buf, writer)
+    }
+}
+
+fn build_sorted_header(header: &sam::Header, key: Key) -> sam::Header {
+    let mut sorted_header = header.clone();
+
+    let hd = sorted_header
+        .header_mut()
+        .get_or_insert_with(sam::header::header::Header::default);
+
+    let (sort_order, group_order, subsort_order) = match key {
+        Key::Coordinate => (SortOrder::Coordinate, None, None),
+        Key::QueryName => (
+            SortOrder::QueryName,
+            None,
+            Some(SubsortOrder::QueryName(String::from("natural"))),
+        ),
+        Key::TemplateCoordinate => (
+            SortOrder::Unsorted,
+            Some(GroupOrder::Query),
+            Some(SubsortOrder::Unsorted(String::from("template-coordinate"))),
+        ),
+    };
+
+    *hd.sort_order_mut() = Some(sort_order);
+    *hd.group_order_mut() = group_order;
+    *hd.subsort_order_mut() = subsort_order;
+
+    sorted_header
+}
+
+fn sort_buf(buf: &mut [(RecordKey, Record)]) {
+    // `sort_by` is stable, which keeps records with equal keys in input order.
+    buf.sort_by(|(a, _), (b, _)| a.cmp(b));
+}
+
+fn write_run(path: &Path, buf: &mut Vec<(RecordKey, Record)>) -> io::Result<()> {
+    sort_buf(buf);
+
+    let mut writer = File::create(path).map(Writer::new)?;
+
+    for (_, record) in buf.drain(..) {
+        writer.write_record(&record)?;
+    }
+
+    writer.try_finish()
+}
+
+// A source of sorted records for the k-way merge.
+enum Run {
+    File(Reader<File>),
+    Memory(std::vec::IntoIter<(RecordKey, Record)>),
+}
+
+impl Run {
+    fn next(&mut self, key: Key, header: &sam::Header) -> io::Result<Option<(RecordKey, Record)>> {
+        match self {
+            Self::File(reader) => {
+                let mut record = Record::default();
+
+                match reader.read_record(&mut record)? {
+                    0 => Ok(None),
+                    _ => RecordKey::new(key, header, &record).map(|k| Some((k, record))),
+                }
+            }
+            Self::Memory(iter) => Ok(iter.next()),
+        }
+    }
+}
+
+// A heap entry for a k-way merge.
+//
+// Entries with equal keys are ordered by the index of their source (e.g., a run or an input) to
+// keep the merge stable.
+pub(crate) struct Entry {
+    pub key: RecordKey,
+    pub index: usize,
+    pub record: Record,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.index == other.index
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+fn merge<W>(
+    key: Key,
+    header: &sam::Header,
+    run_paths: &[PathBuf],
+    buf: Vec<(RecordKey, Record)>,
+    writer: &mut Writer<W>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut runs = Vec::with_capacity(run_paths.len() + 1);
+
+    for path in run_paths {
+        let reader = File::open(path).map(Reader::new)?;
+        runs.push(Run::File(reader));
+    }
+
+    // The remaining buffered records are the last run.
+    runs.push(Run::Memory(buf.into_iter()));
+
+    let mut heap = BinaryHeap::with_capacity(runs.len());
+
+    for (run_index, run) in runs.iter_mut().enumerate() {
+        if let Some((key, record)) = run.next(key, header)? {
+            heap.push(Reverse(Entry {
+                key,
+                index: run_index,
+                record,
+            }));
+        }
+    }
+
+    while let Some(Reverse(entry)) = heap.pop() {
+        writer.write_record(&entry.record)?;
+
+        let run_index = entry.index;
+
+        if let Some((key, record)) = runs[run_index].next(key, header)? {
+            heap.push(Reverse(Entry {
+                key,
+                index: run_index,
+                record,
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+// A uniquely named temporary directory that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(parent: &Path) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!(
+            "noodles-bam-sort-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+
+        let path = parent.join(name);
+        fs::create_dir(&path)?;
+
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::{
+        header::ReferenceSequence,
+        record::{Flags, Position},
+    };
+
+    use crate::record::{aligned_record_builder, ReferenceSequenceId};
+
+    use super::*;
+
+    fn build_header() -> sam::Header {
+        sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 1000))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 1000))
+            .build()
+    }
+
+    fn build_records() -> io::Result<Vec<Record>> {
+        let records = [
+            ("r10", 1, 5),
+            ("r2", -1, 0),
+            ("r1", 0, 8),
+            ("r3", 1, 2),
+            ("r20", 0, 3),
+            ("r4", 0, 8),
+        ];
+
+        records
+            .iter()
+            .map(|&(name, reference_sequence_id, position)| {
+                let read_name = name
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+                let flags = if reference_sequence_id < 0 {
+                    Flags::UNMAPPED
+                } else {
+                    Flags::empty()
+                };
+
+                aligned_record_builder(reference_sequence_id, position, &[])
+                    .set_read_name(read_name)
+                    .set_flags(flags)
+                    .build()
+            })
+            .collect()
+    }
+
+    fn sort(sorter: &Sorter, records: Vec<Record>) -> io::Result<(String, Vec<String>)> {
+        let header = build_header();
+
+        let mut writer = Writer::new(Vec::new());
+        sorter.sort(&header, records.into_iter().map(Ok), &mut writer)?;
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        let raw_header = reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut names = Vec::new();
+
+        for result in reader.records() {
+            let record = result?;
+            let read_name = record.read_name();
+            names.push(String::from_utf8_lossy(&read_name[..read_name.len() - 1]).into_owned());
+        }
+
+        Ok((raw_header, names))
+    }
+
+    #[test]
+    fn test_sort_by_coordinate() -> io::Result<()> {
+        let sorter = Sorter::new(Key::Coordinate);
+        let (raw_header, names) = sort(&sorter, build_records()?)?;
+
+        assert!(raw_header.starts_with("@HD\tVN:1.6\tSO:coordinate\n"));
+        assert_eq!(names, ["r20", "r1", "r4", "r3", "r10", "r2"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_query_name() -> io::Result<()> {
+        let sorter = Sorter::new(Key::QueryName);
+        let (raw_header, names) = sort(&sorter, build_records()?)?;
+
+        assert!(raw_header.starts_with("@HD\tVN:1.6\tSO:queryname\tSS:queryname:natural\n"));
+        assert_eq!(names, ["r1", "r2", "r3", "r4", "r10", "r20"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_runs() -> io::Result<()> {
+        let temp_dir = env::temp_dir();
+
+        // A limit of 1 byte spills every record to its own run.
+        let sorter = Sorter::new(Key::Coordinate)
+            .set_memory_limit(1)
+            .set_temp_dir(&temp_dir);
+
+        let (_, names) = sort(&sorter, build_records()?)?;
+        assert_eq!(names, ["r20", "r1", "r4", "r3", "r10", "r2"]);
+
+        let sorter = sorter.set_memory_limit(RECORD_OVERHEAD * 3);
+        let (_, names) = sort(&sorter, build_records()?)?;
+        assert_eq!(names, ["r20", "r1", "r4", "r3", "r10", "r2"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_template_coordinate() -> io::Result<()> {
+        use crate::record::{cigar::Op, data::field::Value, data::Field};
+        use noodles_sam::record::{cigar::op::Kind, data::field::Tag};
+
+        fn build_pair(
+            name: &str,
+            position: i32,
+            mate_position: i32,
+        ) -> Result<[Record; 2], Box<dyn std::error::Error>> {
+            let read_1 = Record::builder()
+                .set_read_name(name.parse()?)
+                .set_flags(Flags::PAIRED | Flags::READ_1 | Flags::MATE_REVERSE_COMPLEMENTED)
+                .set_reference_sequence_id(ReferenceSequenceId::from(0))
+                .set_position(Position::from(position))
+                .set_cigar(vec![Op::new(Kind::Match, 4)])
+                .set_mate_reference_sequence_id(ReferenceSequenceId::from(0))
+                .set_mate_position(Position::from(mate_position))
+                .set_data(vec![Field::new(
+                    Tag::MateCigar,
+                    Value::String(String::from("4M")),
+                )])
+                .build()?;
+
+            let read_2 = Record::builder()
+                .set_read_name(name.parse()?)
+                .set_flags(Flags::PAIRED | Flags::READ_2 | Flags::REVERSE_COMPLEMENTED)
+                .set_reference_sequence_id(ReferenceSequenceId::from(0))
+                .set_position(Position::from(mate_position))
+                .set_cigar(vec![Op::new(Kind::Match, 4)])
+                .set_mate_reference_sequence_id(ReferenceSequenceId::from(0))
+                .set_mate_position(Position::from(position))
+                .set_data(vec![Field::new(
+                    Tag::MateCigar,
+                    Value::String(String::from("4M")),
+                )])
+                .build()?;
+
+            Ok([read_1, read_2])
+        }
+
+        let build = || -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+            let [a1, a2] = build_pair("a", 21, 34)?;
+            let [b1, b2] = build_pair("b", 8, 13)?;
+            Ok(vec![a2, b1, a1, b2])
+        };
+
+        let records =
+            build().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+
+        let sorter = Sorter::new(Key::TemplateCoordinate);
+        let (raw_header, names) = sort(&sorter, records)?;
+
+        assert!(raw_header
+            .starts_with("@HD\tVN:1.6\tSO:unsorted\tGO:query\tSS:unsorted:template-coordinate\n"));
+        assert_eq!(names, ["b", "b", "a", "a"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use std::{cmp::Ordering, io};
+
+use noodles_sam::{
+    self as sam,
+    record::{data::field::Tag, Flags},
+};
+
+use crate::{record::data::field::Value, Record};
+
+/// A sort key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Key {
+    /// Sort by reference sequence ID, position, and strand (`coordinate`).
+    ///
+    /// Unmapped records (i.e., records without a reference sequence ID) are placed last.
+    Coordinate,
+    /// Sort by read name, using natural order (`queryname`).
+    ///
+    /// Runs of digits are compared numerically, e.g., `r2` sorts before `r10`. Records with the
+    /// same name are ordered by their `READ_1` and `READ_2` flags.
+    QueryName,
+    /// Sort by template coordinate (`template-coordinate`).
+    ///
+    /// Records are ordered by the unclipped 5' positions of the record and its mate, the strands,
+    /// the library, the molecular identifier (`MI`), and the read name. This keeps records of the
+    /// same template together while ordering templates by coordinate.
+    TemplateCoordinate,
+}
+
+// A key computed from a record that is used for comparison.
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum RecordKey {
+    Coordinate(CoordinateKey),
+    QueryName(QueryNameKey),
+    TemplateCoordinate(Box<TemplateCoordinateKey>),
+}
+
+impl RecordKey {
+    pub fn new(key: Key, header: &sam::Header, record: &Record) -> io::Result<Self> {
+        match key {
+            Key::Coordinate => Ok(Self::Coordinate(CoordinateKey::new(record))),
+            Key::QueryName => Ok(Self::QueryName(QueryNameKey::new(record))),
+            Key::TemplateCoordinate => TemplateCoordinateKey::new(header, record)
+                .map(|k| Self::TemplateCoordinate(Box::new(k))),
+        }
+    }
+
+    // Returns the approximate number of heap bytes used by the key.
+    pub fn heap_size(&self) -> usize {
+        match self {
+            Self::Coordinate(_) => 0,
+            Self::QueryName(k) => k.name.0.len(),
+            Self::TemplateCoordinate(k) => {
+                std::mem::size_of::<TemplateCoordinateKey>()
+                    + k.library.len()
+                    + k.molecular_identifier.len()
+                    + k.name.len()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct CoordinateKey {
+    reference_sequence_id: u32,
+    position: i32,
+    is_reverse_complemented: bool,
+}
+
+impl CoordinateKey {
+    fn new(record: &Record) -> Self {
+        let (reference_sequence_id, position) = coordinate(record);
+
+        Self {
+            reference_sequence_id,
+            position,
+            is_reverse_complemented: record.flags().is_reverse_complemented(),
+        }
+    }
+}
+
+// Returns the (reference sequence ID, position) of a record.
+fn coordinate(record: &Record) -> (u32, i32) {
+    // Casting to an unsigned integer places unmapped records (-1) last.
+    let reference_sequence_id = i32::from(record.reference_sequence_id()) as u32;
+    (reference_sequence_id, i32::from(record.position()))
+}
+
+// Checks that a sequence of records is coordinate-sorted.
+//
+// Only the reference sequence IDs and positions are compared, so records that start at the same
+// position may be in any order.
+#[derive(Debug, Default)]
+pub(crate) struct CoordinateSortCheck {
+    last_coordinate: Option<(u32, i32)>,
+}
+
+impl CoordinateSortCheck {
+    // Adds the next record, returning an error of the given kind if it sorts before the previous
+    // record.
+    pub fn add(&mut self, record: &Record, error_kind: io::ErrorKind) -> io::Result<()> {
+        let coordinate = coordinate(record);
+
+        if let Some(last_coordinate) = self.last_coordinate {
+            if coordinate < last_coordinate {
+                return Err(io::Error::new(
+                    error_kind,
+                    "records are not coordinate-sorted",
+                ));
+            }
+        }
+
+        self.last_coordinate = Some(coordinate);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct QueryNameKey {
+    name: NaturalName,
+    segment: u16,
+}
+
+impl QueryNameKey {
+    fn new(record: &Record) -> Self {
+        let segment = u16::from(record.flags() & (Flags::READ_1 | Flags::READ_2));
+
+        Self {
+            name: NaturalName(read_name(record).to_vec()),
+            segment,
+        }
+    }
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct TemplateCoordinateKey {
+    reference_sequence_id_1: u32,
+    reference_sequence_id_2: u32,
+    position_1: i64,
+    position_2: i64,
+    is_reverse_complemented_1: bool,
+    is_reverse_complemented_2: bool,
+    library: String,
+    molecular_identifier: String,
+    name: Vec<u8>,
+    is_upper: bool,
+}
+
+impl TemplateCoordinateKey {
+    fn new(header: &sam::Header, record: &Record) -> io::Result<Self> {
+        let flags = record.flags();
+
+        let mut read_group = None;
+        let mut mate_cigar = None;
+        let mut molecular_identifier = String::new();
+
+        for result in record.data().fields() {
+            let field = result?;
+
+            match (field.tag(), field.value()) {
+                (Tag::ReadGroup, Value::String(s)) => read_group = Some(s.clone()),
+                (Tag::MateCigar, Value::String(s)) => mate_cigar = Some(s.clone()),
+                (Tag::UmiId, Value::String(s)) => molecular_identifier = s.clone(),
+                _ => {}
+            }
+        }
+
+        let library = read_group
+            .and_then(|id| header.read_groups().get(&id))
+            .and_then(|rg| rg.library())
+            .map(String::from)
+            .unwrap_or_default();
+
+        let end_1 = if flags.is_unmapped() {
+            UNMAPPED_END
+        } else {
+            let position = i32::from(record.position());
+            let cigar = sam::record::Cigar::from(
+                record
+                    .cigar()
+                    .ops()
+                    .map(sam::record::cigar::Op::from)
+                    .collect::<Vec<_>>(),
+            );
+            let is_reverse_complemented = flags.is_reverse_complemented();
+            let tid = i32::from(record.reference_sequence_id()) as u32;
+            let pos = unclipped_five_prime_position(position, &cigar, is_reverse_complemented);
+            (tid, pos, is_reverse_complemented)
+        };
+
+        let end_2 = if !flags.is_paired() || flags.is_mate_unmapped() {
+            UNMAPPED_END
+        } else {
+            let position = i32::from(record.mate_position());
+            let is_reverse_complemented = flags.is_mate_reverse_complemented();
+            let tid = i32::from(record.mate_reference_sequence_id()) as u32;
+
+            let cigar = match mate_cigar {
+                Some(s) => s
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                None => sam::record::Cigar::default(),
+            };
+
+            let pos = unclipped_five_prime_position(position, &cigar, is_reverse_complemented);
+            (tid, pos, is_reverse_complemented)
+        };
+
+        let is_upper = end_1 > end_2;
+        let (lower, upper) = if is_upper {
+            (end_2, end_1)
+        } else {
+            (end_1, end_2)
+        };
+
+        Ok(Self {
+            reference_sequence_id_1: lower.0,
+            reference_sequence_id_2: upper.0,
+            position_1: lower.1,
+            position_2: upper.1,
+            is_reverse_complemented_1: lower.2,
+            is_reverse_complemented_2: upper.2,
+            library,
+            molecular_identifier,
+            name: read_name(record).to_vec(),
+            is_upper,
+        })
+    }
+}
+
+// (reference sequence ID, unclipped 5' position, is reverse complemented)
+type End = (u32, i64, bool);
+
+const UNMAPPED_END: End = (u32::MAX, i64::MAX, false);
+
+// Calculates the unclipped 5' position of a read.
+//
+// For a forward read, this is the start position minus any leading clips. For a reverse read,
+// this is the end position plus any trailing clips.
+fn unclipped_five_prime_position(
+    position: i32,
+    cigar: &sam::record::Cigar,
+    is_reverse_complemented: bool,
+) -> i64 {
+    if is_reverse_complemented {
+        let mut end = i64::from(cigar.unclipped_end(position));
+
+        // An alignment that does not consume the reference is treated as spanning one base.
+        if cigar.reference_len() == 0 {
+            end += 1;
+        }
+
+        end
+    } else {
+        i64::from(cigar.unclipped_start(position))
+    }
+}
+
+fn read_name(record: &Record) -> &[u8] {
+    let buf = record.read_name();
+    buf.strip_suffix(b"\0").unwrap_or(buf)
+}
+
+// A read name that is compared using natural order.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct NaturalName(Vec<u8>);
+
+impl Ord for NaturalName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for NaturalName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compares two strings, treating runs of digits as numbers.
+//
+// Leading zeros are ignored when comparing numbers. If the strings are otherwise equal, they are
+// compared bytewise to give a total order.
+fn natural_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let mut i = 0;
+    let mut j = 0;
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let (m, next_i) = digit_run(a, i);
+            let (n, next_j) = digit_run(b, j);
+
+            match m.len().cmp(&n.len()).then_with(|| m.cmp(n)) {
+                Ordering::Equal => {
+                    i = next_i;
+                    j = next_j;
+                }
+                ordering => return ordering,
+            }
+        } else {
+            match a[i].cmp(&b[j]) {
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+                ordering => return ordering,
+            }
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j)).then_with(|| a.cmp(b))
+}
+
+// Returns the digits starting at `start` without leading zeros and the index after the run.
+fn digit_run(buf: &[u8], start: usize) -> (&[u8], usize) {
+    let end = buf[start..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map(|k| start + k)
+        .unwrap_or_else(|| buf.len());
+
+    let digits = &buf[start..end];
+    let k = digits
+        .iter()
+        .position(|&b| b != b'0')
+        .unwrap_or(digits.len());
+
+    (&digits[k..], end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp(b"r2", b"r10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r10", b"r2"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(natural_cmp(b"a:1:20", b"a:1:3"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"r", b"r0"), Ordering::Less);
+        assert_eq!(natural_cmp(b"ra", b"rb"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r01", b"r1"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r01", b"r2"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_unclipped_five_prime_position() {
+        let cigar: sam::record::Cigar = "2S4M1S".parse().unwrap();
+        assert_eq!(unclipped_five_prime_position(8, &cigar, false), 6);
+        assert_eq!(unclipped_five_prime_position(8, &cigar, true), 12);
+
+        let cigar = sam::record::Cigar::default();
+        assert_eq!(unclipped_five_prime_position(8, &cigar, false), 8);
+        assert_eq!(unclipped_five_prime_position(8, &cigar, true), 8);
+    }
+}
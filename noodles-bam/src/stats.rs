@@ -0,0 +1,477 @@
+//! BAM record statistics.
+//!
+//! [`Stats`] collects alignment statistics similar to those reported by `samtools stats`, both for
+//! all records and for each read group. With the `serde` feature enabled, the statistics are
+//! serializable.
+//!
+//! [`Stats`]: struct.Stats.html
+
+mod cycle;
+mod flag_counts;
+
+pub use self::{
+    cycle::{BaseCounts, CycleStats},
+    flag_counts::FlagCounts,
+};
+
+use std::{collections::BTreeMap, convert::TryFrom, io};
+
+use noodles_sam::record::{cigar::op::Kind, data::field::Tag};
+
+use super::{record::data::field::Value, Record};
+
+const MISSING_QUALITY_SCORE: u8 = 255;
+
+/// Alignment statistics of all records and of each read group.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::{self as bam, stats::Stats};
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// reader.read_header()?;
+/// reader.read_reference_sequences()?;
+///
+/// let mut stats = Stats::default();
+///
+/// for result in reader.records() {
+///     let record = result?;
+///     stats.add(&record)?;
+/// }
+///
+/// println!("error rate: {}", stats.all.error_rate());
+///
+/// for (id, read_group_stats) in &stats.read_groups {
+///     println!("{}: error rate: {}", id, read_group_stats.error_rate());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stats {
+    /// The statistics of all records.
+    pub all: AlignmentStats,
+    /// The statistics of the records in each read group, keyed by read group ID.
+    ///
+    /// Records without a read group (`RG`) are only counted in [`all`].
+    ///
+    /// [`all`]: #structfield.all
+    pub read_groups: BTreeMap<String, AlignmentStats>,
+}
+
+impl Stats {
+    /// Adds a record to the statistics.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record data is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, stats::Stats};
+    ///
+    /// let mut stats = Stats::default();
+    /// stats.add(&bam::Record::default())?;
+    ///
+    /// assert_eq!(stats.all.qc_pass_flag_counts.read, 1);
+    /// assert!(stats.read_groups.is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn add(&mut self, record: &Record) -> io::Result<()> {
+        let mut read_group = None;
+        let mut edit_distance = None;
+
+        for result in record.data().fields() {
+            let field = result?;
+
+            match field.tag() {
+                Tag::ReadGroup => {
+                    if let Value::String(id) = field.value() {
+                        read_group = Some(id.clone());
+                    }
+                }
+                Tag::EditDistance => {
+                    edit_distance = field.value().as_int().and_then(|n| u64::try_from(n).ok());
+                }
+                _ => {}
+            }
+        }
+
+        self.all.add(record, edit_distance);
+
+        if let Some(id) = read_group {
+            self.read_groups
+                .entry(id)
+                .or_default()
+                .add(record, edit_distance);
+        }
+
+        Ok(())
+    }
+}
+
+/// Alignment statistics of a set of records.
+///
+/// Flag counts include all records. All other statistics only include primary records, i.e.,
+/// records that are neither secondary nor supplementary.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlignmentStats {
+    /// The flag counts of records that pass quality control.
+    pub qc_pass_flag_counts: FlagCounts,
+    /// The flag counts of records that fail quality control.
+    pub qc_fail_flag_counts: FlagCounts,
+    /// The number of primary records.
+    pub sequences: u64,
+    /// The total number of bases in the sequences of primary records.
+    pub total_len: u64,
+    /// The total number of bases in the sequences of mapped primary records.
+    pub bases_mapped: u64,
+    /// The total number of aligned (`M`, `=`, `X`) and inserted (`I`) bases of mapped primary
+    /// records.
+    pub bases_mapped_cigar: u64,
+    /// The total edit distance (`NM`) of mapped primary records.
+    pub mismatches: u64,
+    /// The number of primary records with at least one soft clip.
+    pub soft_clipped_reads: u64,
+    /// The total number of soft clipped bases of primary records.
+    pub soft_clipped_bases: u64,
+    /// A histogram of the read lengths of primary records.
+    pub read_lengths: BTreeMap<usize, u64>,
+    /// A histogram of the mapping qualities of mapped primary records.
+    pub mapping_qualities: BTreeMap<u8, u64>,
+    /// A histogram of the insert sizes of mapped pairs.
+    ///
+    /// Each pair is counted once, using the absolute template length of the leftmost segment.
+    /// Only pairs where both segments are mapped to the same reference sequence are counted.
+    pub insert_sizes: BTreeMap<u32, u64>,
+    /// The statistics of each sequencing cycle.
+    ///
+    /// Cycles are 0-based positions in reads in the order they were sequenced, i.e., the
+    /// sequences of reverse complemented records are reversed and complemented.
+    pub cycles: Vec<CycleStats>,
+}
+
+impl AlignmentStats {
+    /// Returns the mismatch rate of mapped bases.
+    ///
+    /// This is the total edit distance divided by the number of aligned and inserted bases, or 0
+    /// if there are no mapped bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::stats::AlignmentStats;
+    ///
+    /// let stats = AlignmentStats {
+    ///     bases_mapped_cigar: 200,
+    ///     mismatches: 3,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(stats.error_rate(), 0.015);
+    /// ```
+    pub fn error_rate(&self) -> f64 {
+        ratio(self.mismatches, self.bases_mapped_cigar)
+    }
+
+    /// Returns the fraction of bases of primary records that are soft clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::stats::AlignmentStats;
+    ///
+    /// let stats = AlignmentStats {
+    ///     total_len: 200,
+    ///     soft_clipped_bases: 10,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(stats.soft_clip_rate(), 0.05);
+    /// ```
+    pub fn soft_clip_rate(&self) -> f64 {
+        ratio(self.soft_clipped_bases, self.total_len)
+    }
+
+    /// Returns the mean insert size.
+    ///
+    /// This returns `None` if no insert sizes were counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::stats::AlignmentStats;
+    ///
+    /// let mut stats = AlignmentStats::default();
+    /// assert!(stats.mean_insert_size().is_none());
+    ///
+    /// stats.insert_sizes.insert(300, 1);
+    /// stats.insert_sizes.insert(400, 1);
+    /// assert_eq!(stats.mean_insert_size(), Some(350.0));
+    /// ```
+    pub fn mean_insert_size(&self) -> Option<f64> {
+        let (sum, count) = self
+            .insert_sizes
+            .iter()
+            .fold((0, 0), |(sum, count), (&size, &n)| {
+                (sum + u64::from(size) * n, count + n)
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(sum as f64 / count as f64)
+        }
+    }
+
+    fn add(&mut self, record: &Record, edit_distance: Option<u64>) {
+        let flags = record.flags();
+
+        if flags.is_qc_fail() {
+            self.qc_fail_flag_counts.add(record);
+        } else {
+            self.qc_pass_flag_counts.add(record);
+        }
+
+        if flags.is_secondary() || flags.is_supplementary() {
+            return;
+        }
+
+        let sequence = record.sequence();
+        let read_len = sequence.base_count();
+
+        self.sequences += 1;
+        self.total_len += read_len as u64;
+        *self.read_lengths.entry(read_len).or_insert(0) += 1;
+
+        let is_reverse_complemented = flags.is_reverse_complemented();
+
+        // Maps a read position to its sequencing cycle.
+        let cycle = |read_position: usize| {
+            if is_reverse_complemented {
+                read_len - 1 - read_position
+            } else {
+                read_position
+            }
+        };
+
+        if self.cycles.len() < read_len {
+            self.cycles.resize_with(read_len, Default::default);
+        }
+
+        let quality_scores = record.quality_scores();
+
+        for (read_position, base) in sequence.bases().enumerate() {
+            let cycle_stats = &mut self.cycles[cycle(read_position)];
+
+            let base = if is_reverse_complemented {
+                base.complement()
+            } else {
+                base
+            };
+
+            cycle_stats.base_counts.add(base);
+
+            if let Some(&score) = quality_scores.get(read_position) {
+                if score != MISSING_QUALITY_SCORE {
+                    *cycle_stats.quality_scores.entry(score).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let cigar = record.cigar();
+
+        let soft_clipped_bases =
+            (cigar.leading_soft_clip_len() + cigar.trailing_soft_clip_len()) as usize;
+        let aligned_bases = cigar.query_len() as usize - soft_clipped_bases;
+
+        let mut read_position = 0;
+
+        for op in cigar.ops() {
+            let len = op.len() as usize;
+
+            match op.kind() {
+                Kind::Insertion if len > 0 && read_position + len <= read_len => {
+                    // The first inserted base in sequencing order.
+                    let i = if is_reverse_complemented {
+                        read_position + len - 1
+                    } else {
+                        read_position
+                    };
+
+                    self.cycles[cycle(i)].insertions += 1;
+                }
+                Kind::Deletion => {
+                    // The last base sequenced before the deletion.
+                    let i = if is_reverse_complemented {
+                        Some(read_position)
+                    } else {
+                        read_position.checked_sub(1)
+                    };
+
+                    if let Some(i) = i.filter(|&i| i < read_len) {
+                        self.cycles[cycle(i)].deletions += 1;
+                    }
+                }
+                _ => {}
+            }
+
+            if op.kind().consumes_read() {
+                read_position += len;
+            }
+        }
+
+        if soft_clipped_bases > 0 {
+            self.soft_clipped_reads += 1;
+            self.soft_clipped_bases += soft_clipped_bases as u64;
+        }
+
+        if flags.is_unmapped() {
+            return;
+        }
+
+        self.bases_mapped += read_len as u64;
+        self.bases_mapped_cigar += aligned_bases as u64;
+        self.mismatches += edit_distance.unwrap_or_default();
+
+        let mapping_quality = u8::from(record.mapping_quality());
+        *self.mapping_qualities.entry(mapping_quality).or_insert(0) += 1;
+
+        let template_len = record.template_len();
+
+        if flags.is_paired()
+            && !flags.is_mate_unmapped()
+            && record.mate_reference_sequence_id() == record.reference_sequence_id()
+            && template_len > 0
+        {
+            *self.insert_sizes.entry(template_len as u32).or_insert(0) += 1;
+        }
+    }
+}
+
+fn ratio(a: u64, b: u64) -> f64 {
+    if b == 0 {
+        0.0
+    } else {
+        a as f64 / b as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{Flags, MappingQuality, Position};
+
+    use crate::record::{cigar::Op, data::Field, sequence::Base, ReferenceSequenceId};
+
+    use super::*;
+
+    #[test]
+    fn test_add() -> Result<(), Box<dyn std::error::Error>> {
+        // 2S4M1I1M1D2M
+        let r0 = Record::builder()
+            .set_flags(Flags::PAIRED | Flags::READ_1)
+            .set_reference_sequence_id(ReferenceSequenceId::from(0))
+            .set_position(Position::from(8))
+            .set_mapping_quality(MappingQuality::from(30))
+            .set_mate_reference_sequence_id(ReferenceSequenceId::from(0))
+            .set_mate_position(Position::from(300))
+            .set_template_len(300)
+            .set_cigar(vec![
+                Op::new(Kind::SoftClip, 2),
+                Op::new(Kind::Match, 4),
+                Op::new(Kind::Insertion, 1),
+                Op::new(Kind::Match, 1),
+                Op::new(Kind::Deletion, 1),
+                Op::new(Kind::Match, 2),
+            ])
+            .set_sequence(vec![Base::A; 10])
+            .set_quality_scores(vec![40; 10])
+            .set_data(vec![
+                Field::new(Tag::ReadGroup, Value::String(String::from("rg0"))),
+                Field::new(Tag::EditDistance, Value::UInt8(3)),
+            ])
+            .build()?;
+
+        let r1 = Record::builder()
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(ReferenceSequenceId::from(0))
+            .set_position(Position::from(13))
+            .set_mapping_quality(MappingQuality::from(30))
+            .set_cigar(vec![Op::new(Kind::Match, 3)])
+            .set_sequence(vec![Base::A, Base::C, Base::G])
+            .set_quality_scores(vec![10, 20, 30])
+            .build()?;
+
+        let r2 = Record::builder()
+            .set_flags(Flags::SECONDARY)
+            .set_reference_sequence_id(ReferenceSequenceId::from(0))
+            .set_position(Position::from(21))
+            .set_cigar(vec![Op::new(Kind::Match, 3)])
+            .set_sequence(vec![Base::T; 3])
+            .set_data(vec![Field::new(
+                Tag::ReadGroup,
+                Value::String(String::from("rg0")),
+            )])
+            .build()?;
+
+        let mut stats = Stats::default();
+
+        for record in &[r0, r1, r2] {
+            stats.add(record)?;
+        }
+
+        let all = &stats.all;
+        assert_eq!(all.qc_pass_flag_counts.read, 3);
+        assert_eq!(all.qc_pass_flag_counts.secondary, 1);
+        assert_eq!(all.sequences, 2);
+        assert_eq!(all.total_len, 13);
+        assert_eq!(all.bases_mapped, 13);
+        assert_eq!(all.bases_mapped_cigar, 11);
+        assert_eq!(all.mismatches, 3);
+        assert_eq!(all.soft_clipped_reads, 1);
+        assert_eq!(all.soft_clipped_bases, 2);
+        assert_eq!(
+            all.read_lengths,
+            [(3, 1), (10, 1)].iter().copied().collect()
+        );
+        assert_eq!(all.mapping_qualities, [(30, 2)].iter().copied().collect());
+        assert_eq!(all.insert_sizes, [(300, 1)].iter().copied().collect());
+
+        assert_eq!(all.cycles.len(), 10);
+        assert_eq!(
+            all.cycles[0].base_counts,
+            BaseCounts {
+                a: 1,
+                c: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            all.cycles[2].base_counts,
+            BaseCounts {
+                a: 1,
+                t: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            all.cycles[0].quality_scores,
+            [(30, 1), (40, 1)].iter().copied().collect()
+        );
+        assert_eq!(all.cycles[6].insertions, 1);
+        assert_eq!(all.cycles[7].deletions, 1);
+
+        let rg0 = &stats.read_groups["rg0"];
+        assert_eq!(stats.read_groups.len(), 1);
+        assert_eq!(rg0.qc_pass_flag_counts.read, 2);
+        assert_eq!(rg0.sequences, 1);
+        assert_eq!(rg0.error_rate(), 3.0 / 8.0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use crate::record::sequence::Base;
+
+/// Counts of bases by nucleotide.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BaseCounts {
+    /// The number of `A` bases.
+    pub a: u64,
+    /// The number of `C` bases.
+    pub c: u64,
+    /// The number of `G` bases.
+    pub g: u64,
+    /// The number of `T` bases.
+    pub t: u64,
+    /// The number of `N` bases.
+    pub n: u64,
+    /// The number of other bases, e.g., IUPAC ambiguity codes.
+    pub other: u64,
+}
+
+impl BaseCounts {
+    pub(super) fn add(&mut self, base: Base) {
+        match base {
+            Base::A => self.a += 1,
+            Base::C => self.c += 1,
+            Base::G => self.g += 1,
+            Base::T => self.t += 1,
+            Base::N => self.n += 1,
+            _ => self.other += 1,
+        }
+    }
+
+    /// Returns the total number of bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::stats::BaseCounts;
+    /// let counts = BaseCounts { a: 2, n: 1, ..Default::default() };
+    /// assert_eq!(counts.total(), 3);
+    /// ```
+    pub fn total(&self) -> u64 {
+        self.a + self.c + self.g + self.t + self.n + self.other
+    }
+}
+
+/// Statistics of a sequencing cycle, i.e., a position in a read in the order it was sequenced.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CycleStats {
+    /// The counts of bases sequenced in this cycle.
+    pub base_counts: BaseCounts,
+    /// A histogram of the quality scores of bases sequenced in this cycle.
+    pub quality_scores: BTreeMap<u8, u64>,
+    /// The number of insertions that start in this cycle.
+    pub insertions: u64,
+    /// The number of deletions that follow this cycle.
+    pub deletions: u64,
+}
+
+impl CycleStats {
+    /// Returns the mean quality score of bases sequenced in this cycle.
+    ///
+    /// This returns `None` if no quality scores were counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::stats::CycleStats;
+    ///
+    /// let mut cycle_stats = CycleStats::default();
+    /// assert!(cycle_stats.mean_quality_score().is_none());
+    ///
+    /// cycle_stats.quality_scores.insert(30, 3);
+    /// cycle_stats.quality_scores.insert(40, 1);
+    /// assert_eq!(cycle_stats.mean_quality_score(), Some(32.5));
+    /// ```
+    pub fn mean_quality_score(&self) -> Option<f64> {
+        let (sum, count) = self
+            .quality_scores
+            .iter()
+            .fold((0, 0), |(sum, count), (&score, &n)| {
+                (sum + u64::from(score) * n, count + n)
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(sum as f64 / count as f64)
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::Record;
+
+/// Counts of records by their flags.
+///
+/// These are the counts reported by `samtools flagstat`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FlagCounts {
+    /// The number of records.
+    pub read: u64,
+    /// The number of secondary records.
+    pub secondary: u64,
+    /// The number of supplementary records.
+    pub supplementary: u64,
+    /// The number of duplicate records.
+    pub duplicate: u64,
+    /// The number of mapped records.
+    pub mapped: u64,
+    /// The number of primary paired records.
+    pub paired: u64,
+    /// The number of primary paired records that are the first segment.
+    pub read_1: u64,
+    /// The number of primary paired records that are the last segment.
+    pub read_2: u64,
+    /// The number of mapped primary paired records that are properly paired.
+    pub proper_pair: u64,
+    /// The number of mapped primary paired records whose mate is mapped.
+    pub mate_mapped: u64,
+    /// The number of mapped primary paired records whose mate is unmapped.
+    pub singleton: u64,
+    /// The number of mapped primary paired records whose mate is mapped to a different reference
+    /// sequence.
+    pub mate_reference_sequence_id_mismatch: u64,
+    /// The number of mapped primary paired records whose mate is mapped to a different reference
+    /// sequence and that have a mapping quality >= 5.
+    pub mate_reference_sequence_id_mismatch_hq: u64,
+}
+
+impl FlagCounts {
+    /// Counts a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, stats::FlagCounts};
+    ///
+    /// let mut counts = FlagCounts::default();
+    /// counts.add(&bam::Record::default());
+    ///
+    /// assert_eq!(counts.read, 1);
+    /// assert_eq!(counts.mapped, 0);
+    /// ```
+    pub fn add(&mut self, record: &Record) {
+        let flags = record.flags();
+
+        self.read += 1;
+
+        if !flags.is_unmapped() {
+            self.mapped += 1;
+        }
+
+        if flags.is_duplicate() {
+            self.duplicate += 1;
+        }
+
+        if flags.is_secondary() {
+            self.secondary += 1;
+        } else if flags.is_supplementary() {
+            self.supplementary += 1;
+        } else if flags.is_paired() {
+            self.paired += 1;
+
+            if flags.is_read_1() {
+                self.read_1 += 1;
+            }
+
+            if flags.is_read_2() {
+                self.read_2 += 1;
+            }
+
+            if !flags.is_unmapped() {
+                if flags.is_proper_pair() {
+                    self.proper_pair += 1;
+                }
+
+                if flags.is_mate_unmapped() {
+                    self.singleton += 1;
+                } else {
+                    self.mate_mapped += 1;
+
+                    if record.mate_reference_sequence_id() != record.reference_sequence_id() {
+                        self.mate_reference_sequence_id_mismatch += 1;
+
+                        if u8::from(record.mapping_quality()) >= 5 {
+                            self.mate_reference_sequence_id_mismatch_hq += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
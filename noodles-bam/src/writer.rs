@@ -1,4 +1,9 @@
-mod record;
+//! BAM writer.
+
+mod builder;
+pub(crate) mod record;
+
+pub use self::builder::Builder;
 
 use std::{
     ffi::CString,
@@ -12,7 +17,11 @@ use noodles_sam::{
     header::{ReferenceSequence, ReferenceSequences},
 };
 
-use super::{Record, MAGIC_NUMBER};
+use super::{
+    bai::{self, index::reference_sequence::bin::Chunk},
+    sort::CoordinateSortCheck,
+    Record, MAGIC_NUMBER,
+};
 
 /// A BAM writer.
 ///
@@ -41,6 +50,15 @@ where
     W: Write,
 {
     inner: bgzf::Writer<W>,
+    indexer: Option<Indexer>,
+}
+
+// Builds a BAM index from the records as they are written.
+#[derive(Default)]
+struct Indexer {
+    builder: bai::index::Builder,
+    reference_sequence_count: usize,
+    sort_check: CoordinateSortCheck,
 }
 
 impl<W> Writer<W>
@@ -58,8 +76,30 @@ where
     /// let writer = bam::Writer::new(Vec::new());
     /// ```
     pub fn new(writer: W) -> Self {
+        Self::from_bgzf_writer(bgzf::Writer::new(writer), false)
+    }
+
+    /// Returns a builder to create a writer with options, e.g., a compression level or building an
+    /// index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::Writer::builder(Vec::new()).set_build_index(true).build();
+    /// ```
+    pub fn builder(writer: W) -> Builder<W> {
+        Builder::new(writer)
+    }
+
+    fn from_bgzf_writer(inner: bgzf::Writer<W>, build_index: bool) -> Self {
         Self {
-            inner: bgzf::Writer::new(writer),
+            inner,
+            indexer: if build_index {
+                Some(Indexer::default())
+            } else {
+                None
+            },
         }
     }
 
@@ -76,6 +116,21 @@ where
         self.inner.get_ref()
     }
 
+    /// Returns the current virtual position of the underlying BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let writer = bam::Writer::new(Vec::new());
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
     /// Attempts to finish the output stream.
     ///
     /// This is typically only manually called if the underlying stream is needed before the writer
@@ -94,6 +149,49 @@ where
         self.inner.try_finish()
     }
 
+    /// Attempts to finish the output stream and returns the BAM index (BAI) built from the
+    /// written records.
+    ///
+    /// This returns `None` if the writer was not built to build an index (see
+    /// [`bam::writer::Builder::set_build_index`]). The index can only be taken once.
+    ///
+    /// [`bam::writer::Builder::set_build_index`]: writer/struct.Builder.html#method.set_build_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::Writer::builder(Vec::new()).set_build_index(true).build();
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq0"), 8))
+    ///     .build();
+    ///
+    /// writer.write_header(&header)?;
+    /// writer.write_reference_sequences(header.reference_sequences())?;
+    /// writer.write_record(&bam::Record::default())?;
+    ///
+    /// if let Some(index) = writer.try_finish_with_index()? {
+    ///     assert_eq!(index.reference_sequences().len(), 1);
+    ///
+    ///     let mut index_writer = bai::Writer::new(Vec::new());
+    ///     index_writer.write_header()?;
+    ///     index_writer.write_index(&index)?;
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish_with_index(&mut self) -> io::Result<Option<bai::Index>> {
+        self.try_finish()?;
+
+        Ok(self
+            .indexer
+            .take()
+            .map(|indexer| indexer.builder.build(indexer.reference_sequence_count)))
+    }
+
     /// Writes a SAM header.
     ///
     /// # Examples
@@ -151,6 +249,10 @@ where
         let n_ref = reference_sequences.len() as i32;
         self.inner.write_i32::<LittleEndian>(n_ref)?;
 
+        if let Some(indexer) = self.indexer.as_mut() {
+            indexer.reference_sequence_count = reference_sequences.len();
+        }
+
         for reference_sequence in reference_sequences.values() {
             write_reference(&mut self.inner, reference_sequence)?;
         }
@@ -160,6 +262,9 @@ where
 
     /// Writes a BAM record.
     ///
+    /// If the writer is building an index, the record is added to the index. In this case, an
+    /// error is returned if the record is not in coordinate-sorted order.
+    ///
     /// # Examples
     ///
     /// ```
@@ -171,9 +276,23 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        if let Some(indexer) = self.indexer.as_mut() {
+            indexer.check_sort_order(record)?;
+        }
+
+        let start_position = self.inner.virtual_position();
+
         let block_size = record.len() as u32;
         self.inner.write_u32::<LittleEndian>(block_size)?;
-        self.inner.write_all(record)
+        self.inner.write_all(record)?;
+
+        if let Some(indexer) = self.indexer.as_mut() {
+            let end_position = self.inner.virtual_position();
+            let chunk = Chunk::new(start_position, end_position);
+            indexer.builder.add_record(record, chunk);
+        }
+
+        Ok(())
     }
 
     /// Writes a SAM record.
@@ -197,7 +316,41 @@ where
         reference_sequences: &ReferenceSequences,
         record: &sam::Record,
     ) -> io::Result<()> {
-        record::write_sam_record(&mut self.inner, reference_sequences, record)
+        if self.indexer.is_some() {
+            let mut buf = Vec::new();
+            record::write_sam_record(&mut buf, reference_sequences, record)?;
+
+            // Skip block_size.
+            let record = Record::from(buf.split_off(4));
+
+            self.write_record(&record)
+        } else {
+            record::write_sam_record(&mut self.inner, reference_sequences, record)
+        }
+    }
+}
+
+impl Indexer {
+    fn check_sort_order(&mut self, record: &Record) -> io::Result<()> {
+        if !record.flags().is_unmapped() {
+            match *record.reference_sequence_id() {
+                Some(id) if (id as usize) < self.reference_sequence_count => {}
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid reference sequence ID",
+                    ))
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "mapped record is missing a reference sequence ID",
+                    ))
+                }
+            }
+        }
+
+        self.sort_check.add(record, io::ErrorKind::InvalidInput)
     }
 }
 
@@ -230,9 +383,12 @@ where
 
 #[cfg(test)]
 mod tests {
-    use noodles_sam::record::Data;
+    use noodles_sam::record::{cigar::op::Kind, Data};
 
-    use crate::{record::sequence::Base, Reader, Record};
+    use crate::{
+        record::{aligned_record_builder, sequence::Base},
+        Reader, Record,
+    };
 
     use super::*;
 
@@ -456,4 +612,142 @@ mod tests {
 
         Ok(())
     }
+
+    fn build_indexed_header() -> sam::Header {
+        sam::Header::builder()
+            .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq1"), 13))
+            .build()
+    }
+
+    #[test]
+    fn test_try_finish_with_index() -> io::Result<()> {
+        let header = build_indexed_header();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        writer.write_record(&aligned_record_builder(0, 1, &[(Kind::Match, 4)]).build()?)?;
+        writer.write_record(&aligned_record_builder(0, 3, &[(Kind::Match, 4)]).build()?)?;
+        writer.write_record(&aligned_record_builder(1, 2, &[(Kind::Match, 4)]).build()?)?;
+        writer.write_record(&Record::default())?;
+
+        let actual = writer.try_finish_with_index()?;
+        assert!(writer.try_finish_with_index()?.is_none());
+
+        // Build the expected index by rereading the written records.
+        let mut reader = Reader::new(writer.get_ref().as_slice());
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position));
+            start_position = end_position;
+        }
+
+        let expected = builder.build(header.reference_sequences().len());
+
+        assert_eq!(actual, Some(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_index_and_unsorted_records() -> io::Result<()> {
+        let header = build_indexed_header();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_reference_sequences(header.reference_sequences())?;
+        writer.write_record(&aligned_record_builder(1, 2, &[(Kind::Match, 4)]).build()?)?;
+        assert!(writer
+            .write_record(&aligned_record_builder(0, 1, &[(Kind::Match, 4)]).build()?)
+            .is_err());
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_reference_sequences(header.reference_sequences())?;
+        writer.write_record(&Record::default())?;
+        assert!(writer
+            .write_record(&aligned_record_builder(0, 1, &[(Kind::Match, 4)]).build()?)
+            .is_err());
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_record(&aligned_record_builder(1, 2, &[(Kind::Match, 4)]).build()?)?;
+        writer.write_record(&aligned_record_builder(0, 1, &[(Kind::Match, 4)]).build()?)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_index_and_mapped_record_without_reference_sequence_id(
+    ) -> io::Result<()> {
+        let header = build_indexed_header();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let record = aligned_record_builder(-1, 1, &[(Kind::Match, 4)]).build()?;
+        assert!(matches!(
+            writer.write_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_index_and_out_of_range_reference_sequence_id() -> io::Result<()> {
+        let header = build_indexed_header();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let record = aligned_record_builder(2, 1, &[(Kind::Match, 4)]).build()?;
+        assert!(matches!(
+            writer.write_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_sam_record_with_index() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_indexed_header();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let sam_record = sam::Record::builder()
+            .set_flags(sam::record::Flags::empty())
+            .set_reference_sequence_name("sq1".parse()?)
+            .set_position(sam::record::Position::from(2))
+            .set_cigar("4M".parse()?)
+            .build();
+
+        writer.write_sam_record(header.reference_sequences(), &sam_record)?;
+
+        let index = writer.try_finish_with_index()?.expect("missing index");
+        let reference_sequences = index.reference_sequences();
+        assert!(reference_sequences[0].bins().is_empty());
+        assert!(!reference_sequences[1].bins().is_empty());
+
+        let mut reader = Reader::new(writer.get_ref().as_slice());
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+        let record = reader
+            .records()
+            .next()
+            .transpose()?
+            .expect("missing record");
+        assert_eq!(i32::from(record.reference_sequence_id()), 1);
+
+        Ok(())
+    }
 }
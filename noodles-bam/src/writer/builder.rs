@@ -0,0 +1,96 @@
+use std::io::Write;
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+
+use super::Writer;
+
+/// A BAM writer builder.
+pub struct Builder<W>
+where
+    W: Write,
+{
+    inner: W,
+    compression_level: CompressionLevel,
+    build_index: bool,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    /// Creates a BAM writer builder.
+    ///
+    /// Typically, [`bam::Writer::builder`] is used instead of calling
+    /// [`bam::writer::Builder::new`].
+    ///
+    /// [`bam::Writer::builder`]: struct.Writer.html#method.builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: CompressionLevel::default(),
+            build_index: false,
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let writer = bam::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .build();
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Sets whether to build a BAM index (BAI) while writing records.
+    ///
+    /// When enabled, records must be written in coordinate-sorted order. The index is returned by
+    /// [`bam::Writer::try_finish_with_index`].
+    ///
+    /// By default, an index is not built.
+    ///
+    /// [`bam::Writer::try_finish_with_index`]: struct.Writer.html#method.try_finish_with_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::Writer::builder(Vec::new()).set_build_index(true).build();
+    /// ```
+    pub fn set_build_index(mut self, build_index: bool) -> Self {
+        self.build_index = build_index;
+        self
+    }
+
+    /// Builds a BAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        let inner = bgzf::Writer::builder(self.inner)
+            .set_compression_level(self.compression_level)
+            .build();
+
+        Writer::from_bgzf_writer(inner, self.build_index)
+    }
+}
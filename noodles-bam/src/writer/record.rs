@@ -12,7 +12,7 @@ use noodles_sam::{
     record::{Cigar, Data, MateReferenceSequenceName, QualityScores, Sequence},
 };
 
-use crate::record::sequence::Base;
+use crate::record::{calculate_bin, sequence::Base};
 
 // § 4.2 The BAM format (2020-04-30)
 //
@@ -20,9 +20,6 @@ use crate::record::sequence::Base;
 // (4) + next_ref_id (4) + next_pos (4) + tlen (4)
 const BLOCK_HEADER_SIZE: usize = 32;
 
-// § 4.2.1 BIN field calculation (2020-04-30)
-const UNMAPPED_BIN: u16 = 4680;
-
 // § 4.2.3 SEQ and QUAL encoding (2020-04-30)
 const NULL_QUALITY_SCORE: u8 = 255;
 
@@ -84,15 +81,7 @@ where
     let mapq = u8::from(record.mapping_quality());
     writer.write_u8(mapq)?;
 
-    let bin = record
-        .position()
-        .map(|start| {
-            // 0-based, [start, end)
-            let reference_len = record.cigar().reference_len() as i32;
-            let end = start + reference_len;
-            region_to_bin(start, end) as u16
-        })
-        .unwrap_or(UNMAPPED_BIN);
+    let bin = calculate_bin(record.position(), record.cigar().reference_len());
 
     writer.write_u16::<LittleEndian>(bin)?;
 
@@ -225,6 +214,9 @@ fn calculate_data_len(data: &Data) -> usize {
             Value::Int32(_) => {
                 len += mem::size_of::<i32>();
             }
+            Value::UInt32(_) => {
+                len += mem::size_of::<u32>();
+            }
             Value::Float(_) => {
                 len += mem::size_of::<f32>();
             }
@@ -268,7 +260,14 @@ where
         writer.write_all(field.tag().as_ref().as_bytes())?;
 
         let value = field.value();
-        writer.write_u8(char::from(value.ty()) as u8)?;
+
+        // SAM integers greater than `i32::MAX` are written as unsigned 32-bit integers (`I`).
+        let ty = match value {
+            Value::UInt32(_) => b'I',
+            _ => char::from(value.ty()) as u8,
+        };
+
+        writer.write_u8(ty)?;
 
         if let Some(subtype) = value.subtype() {
             writer.write_u8(char::from(subtype) as u8)?;
@@ -281,6 +280,9 @@ where
             Value::Int32(n) => {
                 writer.write_i32::<LittleEndian>(*n)?;
             }
+            Value::UInt32(n) => {
+                writer.write_u32::<LittleEndian>(*n)?;
+            }
             Value::Float(n) => {
                 writer.write_f32::<LittleEndian>(*n)?;
             }
@@ -343,37 +345,3 @@ where
 
     Ok(())
 }
-
-// § 5.3 C source code for computing bin number and overlapping bins (2020-04-30)
-// 0-based, [start, end)
-#[allow(clippy::eq_op)]
-fn region_to_bin(start: i32, mut end: i32) -> i32 {
-    end -= 1;
-
-    if start >> 14 == end >> 14 {
-        ((1 << 15) - 1) / 7 + (start >> 14)
-    } else if start >> 17 == end >> 17 {
-        ((1 << 12) - 1) / 7 + (start >> 17)
-    } else if start >> 20 == end >> 20 {
-        ((1 << 9) - 1) / 7 + (start >> 20)
-    } else if start >> 23 == end >> 23 {
-        ((1 << 6) - 1) / 7 + (start >> 23)
-    } else if start >> 26 == end >> 26 {
-        ((1 << 3) - 1) / 7 + (start >> 26)
-    } else {
-        0
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_region_to_bin() {
-        // [8, 13]
-        assert_eq!(region_to_bin(7, 13), 4681);
-        // [63245986, 63245986]
-        assert_eq!(region_to_bin(63245985, 63255986), 8541);
-    }
-}
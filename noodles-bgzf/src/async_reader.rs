@@ -0,0 +1,580 @@
+use std::{
+    io::{self, SeekFrom},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, ReadBuf};
+
+use super::{
+    gz, gzi,
+    reader::{inflate_block, missing_eof_block_error, read_compressed_block},
+    writer::BGZF_EOF,
+    Block, VirtualPosition, BGZF_HEADER_SIZE,
+};
+
+enum State {
+    Header,
+    Body,
+    Eof,
+}
+
+/// An async BGZF reader.
+///
+/// This is the async counterpart of [`bgzf::Reader`]. It implements [`tokio::io::AsyncRead`],
+/// consuming compressed data and emitting uncompressed data, and, like the sync reader, tracks
+/// the current virtual position and can seek to a virtual position.
+///
+/// Unlike [`bgzf::Reader`], reading does not return an interrupted error at block boundaries.
+///
+/// [`bgzf::Reader`]: struct.Reader.html
+/// [`tokio::io::AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bgzf as bgzf;
+/// use tokio::{fs::File, io::AsyncReadExt};
+///
+/// let mut reader = File::open("data.gz").await.map(bgzf::AsyncReader::new)?;
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncReader<R> {
+    inner: R,
+    position: u64,
+    state: State,
+    buf: Vec<u8>,
+    filled: usize,
+    cdata: Vec<u8>,
+    block: Block,
+}
+
+impl<R> AsyncReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: 0,
+            state: State::Header,
+            buf: Vec::new(),
+            filled: 0,
+            cdata: Vec::new(),
+            block: Block::default(),
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the current position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the current virtual position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        self.block.virtual_position()
+    }
+
+    // Reads the next block into `self.block`. This returns `false` if the stream reached EOF.
+    fn poll_read_block(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        loop {
+            match self.state {
+                State::Header => {
+                    self.buf.resize(BGZF_HEADER_SIZE, 0);
+
+                    if self.filled == 0 {
+                        let n = ready!(poll_read_some(&mut self.inner, cx, &mut self.buf))?;
+
+                        if n == 0 {
+                            self.state = State::Eof;
+                            continue;
+                        }
+
+                        self.filled = n;
+                    }
+
+                    ready!(poll_fill(
+                        &mut self.inner,
+                        cx,
+                        &mut self.buf,
+                        &mut self.filled
+                    ))?;
+
+                    let block_size = parse_block_size(&self.buf)?;
+                    self.buf.resize(block_size, 0);
+
+                    self.state = State::Body;
+                }
+                State::Body => {
+                    ready!(poll_fill(
+                        &mut self.inner,
+                        cx,
+                        &mut self.buf,
+                        &mut self.filled
+                    ))?;
+
+                    let mut reader = &self.buf[..];
+                    let block_size = read_compressed_block(&mut reader, &mut self.cdata)?;
+                    inflate_block(&self.cdata, &mut self.block)?;
+
+                    self.block.set_position(self.position);
+                    self.position += block_size as u64;
+
+                    self.filled = 0;
+                    self.state = State::Header;
+
+                    return Poll::Ready(Ok(true));
+                }
+                State::Eof => return Poll::Ready(Ok(false)),
+            }
+        }
+    }
+}
+
+impl<R> AsyncReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Seeks the stream to the given virtual position.
+    ///
+    /// The underlying stream's cursor is first moved the the compressed position. A block is read,
+    /// decompressed, and has its own cursor moved to the uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Cursor};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf as bgzf;
+    /// let mut reader = bgzf::AsyncReader::new(Cursor::new(Vec::new()));
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
+        let (compressed_pos, uncompressed_pos) = pos.into();
+
+        self.inner.seek(SeekFrom::Start(compressed_pos)).await?;
+        self.position = compressed_pos;
+
+        self.state = State::Header;
+        self.filled = 0;
+
+        self.buf.resize(BGZF_HEADER_SIZE, 0);
+
+        // Like the sync reader, seeking to the end of the stream succeeds, leaving no data to read.
+        let n = self.inner.read(&mut self.buf).await?;
+
+        if n == 0 {
+            let data = self.block.data_mut();
+            data.get_mut().clear();
+            data.set_position(u64::from(uncompressed_pos));
+
+            self.block.set_position(self.position);
+
+            return Ok(pos);
+        }
+
+        self.inner.read_exact(&mut self.buf[n..]).await?;
+
+        let block_size = parse_block_size(&self.buf)?;
+        self.buf.resize(block_size, 0);
+        self.inner
+            .read_exact(&mut self.buf[BGZF_HEADER_SIZE..])
+            .await?;
+
+        let mut reader = &self.buf[..];
+        read_compressed_block(&mut reader, &mut self.cdata)?;
+        inflate_block(&self.cdata, &mut self.block)?;
+
+        self.block.set_position(self.position);
+        self.position += block_size as u64;
+
+        self.block
+            .data_mut()
+            .set_position(u64::from(uncompressed_pos));
+
+        Ok(pos)
+    }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The virtual position of the uncompressed position is looked up in the given gzip index
+    /// (GZI). This is typically used for files addressed by uncompressed offsets, e.g., using a
+    /// FASTA index (FAI) with a bgzip-compressed FASTA file.
+    ///
+    /// This returns the resulting virtual position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf as bgzf;
+    /// use tokio::io::AsyncReadExt;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"-bgzf")?;
+    /// writer.try_finish()?;
+    ///
+    /// let index = writer.gzi_index();
+    /// let mut reader = bgzf::AsyncReader::new(Cursor::new(writer.get_ref()));
+    /// reader.seek_by_uncompressed_position(index, 8).await?;
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf).await?;
+    /// assert_eq!(buf, b"bgzf");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn seek_by_uncompressed_position(
+        &mut self,
+        index: &gzi::Index,
+        pos: u64,
+    ) -> io::Result<VirtualPosition> {
+        let virtual_position = index.query(pos)?;
+        self.seek(virtual_position).await
+    }
+
+    /// Checks whether the stream ends with a BGZF EOF block.
+    ///
+    /// A BGZF stream is terminated by an empty block (the EOF marker). If it is missing, the
+    /// stream is likely truncated, e.g., from an incomplete copy or write. This returns an error
+    /// of kind [`std::io::ErrorKind::UnexpectedEof`] in that case.
+    ///
+    /// The position of the underlying stream is restored after the check.
+    ///
+    /// [`std::io::ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::AsyncReader::new(Cursor::new(data));
+    /// reader.check_eof().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_eof(&mut self) -> io::Result<()> {
+        let pos = self.inner.stream_position().await?;
+        let result = read_eof_block(&mut self.inner).await;
+        self.inner.seek(SeekFrom::Start(pos)).await?;
+
+        if result? {
+            Ok(())
+        } else {
+            Err(missing_eof_block_error())
+        }
+    }
+}
+
+impl<R> AsyncRead for AsyncReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        let src = ready!(Pin::new(&mut *this).poll_fill_buf(cx))?;
+        let amt = src.len().min(buf.remaining());
+        buf.put_slice(&src[..amt]);
+
+        Pin::new(this).consume(amt);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> AsyncBufRead for AsyncReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        loop {
+            let data = this.block.data_mut();
+
+            if (data.position() as usize) < data.get_ref().len() {
+                break;
+            }
+
+            if !ready!(this.poll_read_block(cx))? {
+                break;
+            }
+        }
+
+        let data = this.block.data_mut();
+        let pos = data.position() as usize;
+        let len = data.get_ref().len();
+
+        Poll::Ready(Ok(&data.get_ref()[pos.min(len)..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let data = self.get_mut().block.data_mut();
+        let pos = data.position();
+        data.set_position(pos + amt as u64);
+    }
+}
+
+async fn read_eof_block<R>(reader: &mut R) -> io::Result<bool>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let eof_block_len = BGZF_EOF.len() as u64;
+    let len = reader.seek(SeekFrom::End(0)).await?;
+
+    if len < eof_block_len {
+        return Ok(false);
+    }
+
+    reader.seek(SeekFrom::Start(len - eof_block_len)).await?;
+
+    let mut buf = vec![0; BGZF_EOF.len()];
+    reader.read_exact(&mut buf).await?;
+
+    Ok(buf == BGZF_EOF)
+}
+
+fn parse_block_size(header: &[u8]) -> io::Result<usize> {
+    // Add 1 because BSIZE is "total Block SIZE minus 1".
+    let block_size = usize::from(LittleEndian::read_u16(&header[16..18])) + 1;
+
+    if block_size < BGZF_HEADER_SIZE + gz::TRAILER_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid block size",
+        ));
+    }
+
+    Ok(block_size)
+}
+
+fn poll_read_some<R>(
+    reader: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+) -> Poll<io::Result<usize>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut read_buf = ReadBuf::new(buf);
+    ready!(Pin::new(reader).poll_read(cx, &mut read_buf))?;
+    Poll::Ready(Ok(read_buf.filled().len()))
+}
+
+// Reads into `buf[*filled..]` until `buf` is full.
+fn poll_fill<R>(
+    reader: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+    filled: &mut usize,
+) -> Poll<io::Result<()>>
+where
+    R: AsyncRead + Unpin,
+{
+    while *filled < buf.len() {
+        match ready!(poll_read_some(reader, cx, &mut buf[*filled..]))? {
+            0 => return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof))),
+            n => *filled += n,
+        }
+    }
+
+    Poll::Ready(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use tokio::io::AsyncReadExt;
+
+    use crate::{Reader, Writer};
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+
+        writer.finish()
+    }
+
+    #[tokio::test]
+    async fn test_read() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = AsyncReader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+
+        assert_eq!(buf, b"noodles-bgzf");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_virtual_position() -> io::Result<()> {
+        use std::io::Read;
+
+        let data = build_data()?;
+
+        let mut expected_reader = Reader::new(&data[..]);
+        let mut reader = AsyncReader::new(&data[..]);
+
+        for len in &[7, 1, 1, 3] {
+            let mut expected_buf = vec![0; *len];
+            expected_reader.read_exact(&mut expected_buf)?;
+
+            let mut buf = vec![0; *len];
+            reader.read_exact(&mut buf).await?;
+
+            assert_eq!(buf, expected_buf);
+            assert_eq!(
+                reader.virtual_position(),
+                expected_reader.virtual_position()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek() -> io::Result<()> {
+        let data = build_data()?;
+        let mut reader = AsyncReader::new(Cursor::new(data));
+
+        let mut buf = [0; 9];
+        reader.read_exact(&mut buf).await?;
+        let pos = reader.virtual_position();
+
+        reader.seek(VirtualPosition::from(3)).await?;
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(3));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"dles-bgzf");
+
+        reader.seek(pos).await?;
+        assert_eq!(reader.virtual_position(), pos);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"gzf");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_to_eof() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut expected_reader = Reader::new(Cursor::new(data.clone()));
+        let mut reader = AsyncReader::new(Cursor::new(data.clone()));
+
+        // The start of the BGZF EOF block and the end of the stream.
+        let eof_block_start = (data.len() - BGZF_EOF.len()) as u64;
+        let eof = data.len() as u64;
+
+        for &compressed_pos in &[eof_block_start, eof] {
+            let pos = VirtualPosition::from(compressed_pos << 16);
+            assert_eq!(reader.seek(pos).await?, expected_reader.seek(pos)?);
+            assert_eq!(reader.virtual_position(), pos);
+
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).await?;
+            assert!(buf.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_eof() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = AsyncReader::new(Cursor::new(data.clone()));
+        reader.check_eof().await?;
+        assert_eq!(reader.get_ref().position(), 0);
+
+        let truncated_data = data[..data.len() - BGZF_EOF.len()].to_vec();
+        let mut reader = AsyncReader::new(Cursor::new(truncated_data));
+        assert!(matches!(
+            reader.check_eof().await,
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+//! Async BGZF writer.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    cmp,
+    convert::TryFrom,
+    io, mem,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use flate2::Compression;
+use tokio::io::AsyncWrite;
+
+use super::{
+    writer::{deflate_block, CompressionLevel, BGZF_EOF, MAX_BGZF_BLOCK_SIZE},
+    VirtualPosition,
+};
+
+/// An async BGZF writer.
+///
+/// This is the async counterpart of [`bgzf::Writer`]. It implements [`tokio::io::AsyncWrite`],
+/// consuming uncompressed data and emitting compressed data.
+///
+/// Shutting down the writer (e.g., [`tokio::io::AsyncWriteExt::shutdown`]) flushes any remaining
+/// data and appends the final BGZF EOF block.
+///
+/// [`bgzf::Writer`]: struct.Writer.html
+/// [`tokio::io::AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+/// [`tokio::io::AsyncWriteExt::shutdown`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWriteExt.html#method.shutdown
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bgzf as bgzf;
+/// use tokio::io::AsyncWriteExt;
+///
+/// let mut writer = bgzf::AsyncWriter::new(Vec::new());
+/// writer.write_all(b"noodles-bgzf").await?;
+/// writer.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncWriter<W> {
+    inner: W,
+    position: u64,
+    compression_level: Compression,
+    buf: Vec<u8>,
+    frame: Vec<u8>,
+    frame_position: usize,
+    is_eof_written: bool,
+}
+
+impl<W> AsyncWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BGZF writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::with_compression_level(inner, CompressionLevel::default())
+    }
+
+    /// Returns a builder to create an async BGZF writer with options, e.g., a compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::AsyncWriter::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    fn with_compression_level(inner: W, compression_level: CompressionLevel) -> Self {
+        Self {
+            inner,
+            position: 0,
+            compression_level: Compression::from(compression_level),
+            buf: Vec::with_capacity(MAX_BGZF_BLOCK_SIZE as usize),
+            frame: Vec::new(),
+            frame_position: 0,
+            is_eof_written: false,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// The stream is not finished. Shut down the writer first to write any remaining data and the
+    /// final BGZF EOF block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Returns the current virtual position of the stream.
+    ///
+    /// The compressed position is the start of the current (unflushed) block, and the
+    /// uncompressed position is the number of bytes written to that block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf as bgzf;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// let mut writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(0));
+    ///
+    /// writer.write_all(b"noodles").await?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(7));
+    ///
+    /// writer.flush().await?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(35 << 16));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        // The uncompressed block size is always < 64 KiB, so the cast cannot truncate.
+        VirtualPosition::try_from((self.position, self.buf.len() as u16))
+            .expect("invalid virtual position")
+    }
+
+    // Compresses the current buffer into a pending frame.
+    fn compress_block(&mut self) -> io::Result<()> {
+        let capacity = MAX_BGZF_BLOCK_SIZE as usize;
+        let data = mem::replace(&mut self.buf, Vec::with_capacity(capacity));
+
+        self.frame = deflate_block(&data, self.compression_level)?;
+        self.frame_position = 0;
+
+        self.position += self.frame.len() as u64;
+
+        Ok(())
+    }
+
+    // Writes the pending frame, if any, to the underlying stream.
+    fn poll_write_frame(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.frame_position < self.frame.len() {
+            let n = ready!(
+                Pin::new(&mut self.inner).poll_write(cx, &self.frame[self.frame_position..])
+            )?;
+
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero)));
+            }
+
+            self.frame_position += n;
+        }
+
+        self.frame.clear();
+        self.frame_position = 0;
+
+        Poll::Ready(Ok(()))
+    }
+
+    // Compresses and writes any buffered data.
+    fn poll_flush_block(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_frame(cx))?;
+
+        if !self.buf.is_empty() {
+            self.compress_block()?;
+            ready!(self.poll_write_frame(cx))?;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncWrite for AsyncWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let max_block_size = MAX_BGZF_BLOCK_SIZE as usize;
+
+        ready!(this.poll_write_frame(cx))?;
+
+        if this.buf.len() >= max_block_size {
+            this.compress_block()?;
+            ready!(this.poll_write_frame(cx))?;
+        }
+
+        let n = cmp::min(max_block_size - this.buf.len(), buf.len());
+        this.buf.extend_from_slice(&buf[..n]);
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_flush_block(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flush_block(cx))?;
+
+        if !this.is_eof_written {
+            this.frame = BGZF_EOF.to_vec();
+            this.frame_position = 0;
+            this.is_eof_written = true;
+        }
+
+        ready!(this.poll_write_frame(cx))?;
+
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use tokio::io::AsyncWriteExt;
+
+    use crate::{Reader, Writer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write() -> io::Result<()> {
+        let data: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+
+        let mut expected_writer = Writer::new(Vec::new());
+        expected_writer.write_all(&data)?;
+        let expected = expected_writer.finish()?;
+
+        let mut writer = AsyncWriter::new(Vec::new());
+        writer.write_all(&data).await?;
+        writer.shutdown().await?;
+        let actual = writer.into_inner();
+
+        assert_eq!(actual, expected);
+
+        let mut reader = Reader::new(&actual[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_with_compression_level() -> io::Result<()> {
+        let data: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+
+        for compression_level in &[CompressionLevel::none(), CompressionLevel::best()] {
+            let mut expected_writer = Writer::builder(Vec::new())
+                .set_compression_level(*compression_level)
+                .build();
+            expected_writer.write_all(&data)?;
+            let expected = expected_writer.finish()?;
+
+            let mut writer = AsyncWriter::builder(Vec::new())
+                .set_compression_level(*compression_level)
+                .build();
+            writer.write_all(&data).await?;
+            writer.shutdown().await?;
+            let actual = writer.into_inner();
+
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_virtual_position() -> io::Result<()> {
+        let mut expected_writer = Writer::new(Vec::new());
+        let mut writer = AsyncWriter::new(Vec::new());
+
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        expected_writer.write_all(b"noodles")?;
+        writer.write_all(b"noodles").await?;
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        expected_writer.flush()?;
+        writer.flush().await?;
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        expected_writer.write_all(b"-bgzf")?;
+        writer.write_all(b"-bgzf").await?;
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        let data: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+        expected_writer.write_all(&data)?;
+        writer.write_all(&data).await?;
+        writer.flush().await?;
+        expected_writer.flush()?;
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() -> io::Result<()> {
+        let mut writer = AsyncWriter::new(Vec::new());
+        writer.write_all(b"noodles").await?;
+        writer.shutdown().await?;
+
+        let data = writer.into_inner();
+        let eof_start = data.len() - BGZF_EOF.len();
+
+        assert_eq!(&data[eof_start..], BGZF_EOF);
+
+        Ok(())
+    }
+}
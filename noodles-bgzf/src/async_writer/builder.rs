@@ -0,0 +1,70 @@
+use tokio::io::AsyncWrite;
+
+use crate::writer::CompressionLevel;
+
+use super::AsyncWriter;
+
+/// An async BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    inner: W,
+    compression_level: CompressionLevel,
+}
+
+impl<W> Builder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BGZF writer builder.
+    ///
+    /// Typically, [`bgzf::AsyncWriter::builder`] is used instead of calling
+    /// [`bgzf::async_writer::Builder::new`].
+    ///
+    /// [`bgzf::AsyncWriter::builder`]: ../struct.AsyncWriter.html#method.builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::AsyncWriter::builder(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: CompressionLevel::default(),
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::AsyncWriter::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::best())
+    ///     .build();
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Builds an async BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> AsyncWriter<W> {
+        AsyncWriter::with_compression_level(self.inner, self.compression_level)
+    }
+}
@@ -0,0 +1,152 @@
+//! gzip index (GZI) and fields.
+//!
+//! A gzip index (GZI) maps uncompressed positions to compressed positions in a BGZF file. It is
+//! typically used with bgzip-compressed files that are addressed by uncompressed byte offsets,
+//! e.g., a FASTA index (FAI) of a `.fa.gz` file.
+//!
+//! The index is a list of (compressed offset, uncompressed offset) pairs, one for the start of
+//! each block except the first, which implicitly starts at (0, 0).
+//!
+//! # Examples
+//!
+//! ## Reading a gzip index
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_bgzf::gzi;
+//! let index = gzi::read("sample.fa.gz.gzi")?;
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! ## Seeking to an uncompressed position
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bgzf::{self as bgzf, gzi};
+//!
+//! let index = gzi::read("sample.fa.gz.gzi")?;
+//!
+//! let mut reader = File::open("sample.fa.gz").map(bgzf::Reader::new)?;
+//! reader.seek_by_uncompressed_position(&index, 102334155)?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+mod index;
+mod reader;
+mod writer;
+
+pub use self::{index::Index, reader::Reader, writer::Writer};
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use super::{
+    reader::{inflate_block, read_compressed_block},
+    Block,
+};
+
+/// Reads the entire contents of a gzip index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::read("sample.fa.gz.gzi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_index()
+}
+
+/// Builds a gzip index by scanning the blocks of a BGZF stream.
+///
+/// The stream is expected to be at the start of the first block.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf::{self as bgzf, gzi};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let index = gzi::index(&data[..])?;
+/// assert_eq!(index.entries(), [(35, 7)]);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<R>(mut reader: R) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut entries = Vec::new();
+
+    let mut cdata = Vec::new();
+    let mut block = Block::default();
+
+    let mut compressed_position = 0;
+    let mut uncompressed_position = 0;
+
+    loop {
+        let block_size = read_compressed_block(&mut reader, &mut cdata)?;
+
+        if block_size == 0 {
+            break;
+        }
+
+        inflate_block(&cdata, &mut block)?;
+        let uncompressed_block_size = block.data_mut().get_ref().len() as u64;
+
+        if compressed_position > 0 && uncompressed_block_size > 0 {
+            entries.push((compressed_position, uncompressed_position));
+        }
+
+        compressed_position += block_size as u64;
+        uncompressed_position += uncompressed_block_size;
+    }
+
+    Ok(Index::new(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::Writer;
+
+    use super::*;
+
+    #[test]
+    fn test_index() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.try_finish()?;
+
+        let expected = writer.gzi_index();
+        let data = writer.get_ref();
+
+        let actual = index(&data[..])?;
+        assert_eq!(&actual, expected);
+        assert_eq!(actual.entries().len(), 2);
+
+        Ok(())
+    }
+}
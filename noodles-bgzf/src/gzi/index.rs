@@ -0,0 +1,95 @@
+use std::{convert::TryFrom, io};
+
+use crate::VirtualPosition;
+
+/// A gzip index (GZI).
+///
+/// Each entry is a (compressed offset, uncompressed offset) pair marking the start of a block. The
+/// first block, which starts at (0, 0), is not included.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Index(Vec<(u64, u64)>);
+
+impl Index {
+    /// Creates a gzip index.
+    ///
+    /// The entries are expected to be sorted by both compressed and uncompressed offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    /// ```
+    pub fn new(entries: Vec<(u64, u64)>) -> Self {
+        Self(entries)
+    }
+
+    /// Returns the list of (compressed offset, uncompressed offset) pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    /// assert_eq!(index.entries(), [(4668, 21294)]);
+    /// ```
+    pub fn entries(&self) -> &[(u64, u64)] {
+        &self.0
+    }
+
+    pub(crate) fn push(&mut self, entry: (u64, u64)) {
+        self.0.push(entry);
+    }
+
+    /// Returns the virtual position of the given uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{convert::TryFrom, io};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    ///
+    /// assert_eq!(index.query(8)?, bgzf::VirtualPosition::try_from((0, 8)).unwrap());
+    /// assert_eq!(index.query(21299)?, bgzf::VirtualPosition::try_from((4668, 5)).unwrap());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&self, pos: u64) -> io::Result<VirtualPosition> {
+        let i = self.0.partition_point(|&(_, u)| u <= pos);
+
+        let (compressed_pos, block_uncompressed_pos) = if i == 0 { (0, 0) } else { self.0[i - 1] };
+
+        let uncompressed_pos = u16::try_from(pos - block_uncompressed_pos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        VirtualPosition::try_from((compressed_pos, uncompressed_pos))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let index = Index::new(vec![(4668, 21294), (9547, 42588)]);
+
+        assert_eq!(index.query(0)?, VirtualPosition::from(0));
+        assert_eq!(index.query(21293)?, VirtualPosition::from(21293));
+        assert_eq!(
+            index.query(21294)?,
+            VirtualPosition::try_from((4668, 0)).unwrap()
+        );
+        assert_eq!(
+            index.query(42600)?,
+            VirtualPosition::try_from((9547, 12)).unwrap()
+        );
+
+        let index = Index::default();
+        assert!(index.query(65536).is_err());
+
+        Ok(())
+    }
+}
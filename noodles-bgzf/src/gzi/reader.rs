@@ -0,0 +1,102 @@
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) reader.
+///
+/// Consider using [`gzi::read`] to read the entire index at once.
+///
+/// [`gzi::read`]: fn.read.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::gzi;
+/// let mut reader = File::open("sample.fa.gz.gzi").map(gzi::Reader::new)?;
+/// let index = reader.read_index()?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index reader.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::gzi;
+    /// let reader = File::open("sample.fa.gz.gzi").map(gzi::Reader::new)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads a gzip index.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::gzi;
+    /// let mut reader = File::open("sample.fa.gz.gzi").map(gzi::Reader::new)?;
+    /// let index = reader.read_index()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let len = self.inner.read_u64::<LittleEndian>()?;
+
+        let mut entries = Vec::new();
+
+        for _ in 0..len {
+            let compressed_offset = self.inner.read_u64::<LittleEndian>()?;
+            let uncompressed_offset = self.inner.read_u64::<LittleEndian>()?;
+            entries.push((compressed_offset, uncompressed_offset));
+        }
+
+        Ok(Index::new(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        let data = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0x4b, 0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 9547
+            0x5c, 0xa6, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 42588
+        ];
+
+        let mut reader = Reader::new(&data[..]);
+        let actual = reader.read_index()?;
+
+        let expected = Index::new(vec![(4668, 21294), (9547, 42588)]);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_index_with_missing_entries() {
+        let data = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_index().is_err());
+    }
+}
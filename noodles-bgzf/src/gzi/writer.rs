@@ -0,0 +1,102 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) writer.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::gzi;
+///
+/// let index = gzi::Index::default();
+///
+/// let mut writer = File::create("sample.fa.gz.gzi").map(gzi::Writer::new)?;
+/// writer.write_index(&index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a gzip index writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    ///
+    /// let mut writer = gzi::Writer::new(Vec::new());
+    /// writer.write_index(&index)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        let entries = index.entries();
+
+        self.inner.write_u64::<LittleEndian>(entries.len() as u64)?;
+
+        for &(compressed_offset, uncompressed_offset) in entries {
+            self.inner.write_u64::<LittleEndian>(compressed_offset)?;
+            self.inner.write_u64::<LittleEndian>(uncompressed_offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gzi::Reader;
+
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let index = Index::new(vec![(4668, 21294), (9547, 42588)]);
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&index)?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        let actual = reader.read_index()?;
+
+        assert_eq!(actual, index);
+
+        Ok(())
+    }
+}
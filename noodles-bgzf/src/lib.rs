@@ -9,7 +9,13 @@
 //! noodles-bgzf abstracts away the concept of blocks, implementing [`std::io::Read`] for the
 //! reader and [`std::io::Write`] for the writer.
 //!
+//! With the `async` feature enabled, [`AsyncReader`] and [`AsyncWriter`] provide the same
+//! functionality for [tokio]'s `AsyncRead` and `AsyncWrite`.
+//!
 //! [`virtual positions`]: struct.VirtualPosition.html
+//! [`AsyncReader`]: struct.AsyncReader.html
+//! [`AsyncWriter`]: struct.AsyncWriter.html
+//! [tokio]: https://tokio.rs/
 //! [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
 //! [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
 //!
@@ -26,6 +32,17 @@
 //! # Ok::<(), io::Error>(())
 //! ```
 //!
+//! ## Read an entire BGZF file using multiple threads
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, Read}};
+//! use noodles_bgzf as bgzf;
+//! let mut reader = File::open("data.gz").map(bgzf::MultithreadedReader::new)?;
+//! let mut data = Vec::new();
+//! reader.read_to_end(&mut data)?;
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
 //! ## Write a BGZF file
 //!
 //! ```no_run
@@ -35,14 +52,39 @@
 //! writer.write_all(b"noodles-bgzf")?;
 //! # Ok::<(), io::Error>(())
 //! ```
+//!
+//! ## Write a BGZF file using multiple threads
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, Write}};
+//! use noodles_bgzf as bgzf;
+//! let mut writer = File::create("data.gz").map(bgzf::MultithreadedWriter::new)?;
+//! writer.write_all(b"noodles-bgzf")?;
+//! writer.finish()?;
+//! # Ok::<(), io::Error>(())
+//! ```
 
+#[cfg(feature = "async")]
+mod async_reader;
+#[cfg(feature = "async")]
+pub mod async_writer;
 mod block;
 mod gz;
+pub mod gzi;
+mod multithreaded_reader;
+pub mod multithreaded_writer;
+pub mod raw;
 mod reader;
 pub mod virtual_position;
-mod writer;
+pub mod writer;
+
+pub use self::{
+    multithreaded_reader::MultithreadedReader, multithreaded_writer::MultithreadedWriter,
+    reader::Reader, virtual_position::VirtualPosition, writer::Writer,
+};
 
-pub use self::{reader::Reader, virtual_position::VirtualPosition, writer::Writer};
+#[cfg(feature = "async")]
+pub use self::{async_reader::AsyncReader, async_writer::AsyncWriter};
 
 use self::block::Block;
 
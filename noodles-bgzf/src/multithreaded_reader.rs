@@ -0,0 +1,404 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use super::{
+    reader::{check_eof, inflate_block, read_compressed_block},
+    Block, VirtualPosition,
+};
+
+type BlockResult = io::Result<Block>;
+
+struct Job {
+    position: u64,
+    cdata: Vec<u8>,
+    tx: Sender<BlockResult>,
+}
+
+/// A multithreaded BGZF reader.
+///
+/// Compressed blocks are read ahead from the underlying stream on the calling thread and
+/// decompressed concurrently by a pool of worker threads. Blocks are still emitted in stream order,
+/// so this is a drop-in replacement for [`bgzf::Reader`] when decompression is the bottleneck.
+///
+/// Like [`bgzf::Reader`], this implements [`std::io::Read`], tracks the current virtual position,
+/// and, when the underlying stream is seekable, can seek to a virtual position.
+///
+/// [`bgzf::Reader`]: struct.Reader.html
+/// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io::{self, Read}};
+/// use noodles_bgzf as bgzf;
+/// let mut reader = File::open("data.gz").map(bgzf::MultithreadedReader::new)?;
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct MultithreadedReader<R> {
+    inner: R,
+    position: u64,
+    is_eof: bool,
+    block: Block,
+    queue: VecDeque<Receiver<BlockResult>>,
+    read_ahead_count: usize,
+    tx: Option<Sender<Job>>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl<R> MultithreadedReader<R>
+where
+    R: Read,
+{
+    /// Creates a multithreaded BGZF reader with a worker count equal to the available
+    /// parallelism of the system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::MultithreadedReader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        let worker_count = thread::available_parallelism().unwrap_or_else(|_| NonZeroUsize::MIN);
+        Self::with_worker_count(worker_count, inner)
+    }
+
+    /// Creates a multithreaded BGZF reader with a given number of worker threads.
+    ///
+    /// Up to `worker_count` blocks are read ahead and decompressed concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = [];
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let reader = bgzf::MultithreadedReader::with_worker_count(worker_count, &data[..]);
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: R) -> Self {
+        let worker_count = worker_count.get();
+
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        let handles = (0..worker_count)
+            .map(|_| spawn_inflater(Arc::clone(&rx)))
+            .collect();
+
+        Self {
+            inner,
+            position: 0,
+            is_eof: false,
+            block: Block::default(),
+            queue: VecDeque::with_capacity(worker_count),
+            read_ahead_count: worker_count,
+            tx: Some(tx),
+            handles,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::MultithreadedReader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the current virtual position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::MultithreadedReader::new(&data[..]);
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        self.block.virtual_position()
+    }
+
+    // Reads compressed blocks from the underlying stream and dispatches them to the workers until
+    // the read-ahead queue is full or the stream reaches EOF.
+    fn fill_queue(&mut self) -> io::Result<()> {
+        while !self.is_eof && self.queue.len() < self.read_ahead_count {
+            let mut cdata = Vec::new();
+
+            let block_size = read_compressed_block(&mut self.inner, &mut cdata)?;
+
+            if block_size == 0 {
+                self.is_eof = true;
+                break;
+            }
+
+            let (tx, rx) = mpsc::channel();
+
+            let job = Job {
+                position: self.position,
+                cdata,
+                tx,
+            };
+
+            self.position += block_size as u64;
+
+            self.tx
+                .as_ref()
+                .expect("missing job sender")
+                .send(job)
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "inflater hung up"))?;
+
+            self.queue.push_back(rx);
+        }
+
+        Ok(())
+    }
+
+    // Replaces the current block with the next decompressed block. This returns `false` if there
+    // are no more blocks.
+    fn next_block(&mut self) -> io::Result<bool> {
+        self.fill_queue()?;
+
+        let rx = match self.queue.pop_front() {
+            Some(rx) => rx,
+            None => return Ok(false),
+        };
+
+        self.block = rx
+            .recv()
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "inflater hung up"))??;
+
+        self.fill_queue()?;
+
+        Ok(true)
+    }
+}
+
+impl<R> MultithreadedReader<R>
+where
+    R: Read + Seek,
+{
+    /// Seeks the stream to the given virtual position.
+    ///
+    /// Any blocks that were read ahead are discarded. The block at the compressed position is
+    /// then decompressed and has its cursor moved to the uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bgzf as bgzf;
+    /// let mut reader = bgzf::MultithreadedReader::new(Cursor::new(Vec::new()));
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
+        let (compressed_pos, uncompressed_pos) = pos.into();
+
+        self.queue.clear();
+
+        self.inner.seek(SeekFrom::Start(compressed_pos))?;
+        self.position = compressed_pos;
+        self.is_eof = false;
+
+        self.block = Block::default();
+        self.block.set_position(compressed_pos);
+
+        self.next_block()?;
+
+        self.block
+            .data_mut()
+            .seek(SeekFrom::Start(u64::from(uncompressed_pos)))?;
+
+        Ok(pos)
+    }
+
+    /// Checks whether the stream ends with a BGZF EOF block.
+    ///
+    /// This returns an error of kind [`std::io::ErrorKind::UnexpectedEof`] if the EOF block is
+    /// missing, i.e., the stream is likely truncated. See [`bgzf::Reader::check_eof`].
+    ///
+    /// [`std::io::ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    /// [`bgzf::Reader::check_eof`]: struct.Reader.html#method.check_eof
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::MultithreadedReader::new(Cursor::new(data));
+    /// reader.check_eof()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn check_eof(&mut self) -> io::Result<()> {
+        check_eof(&mut self.inner)
+    }
+}
+
+impl<R> Read for MultithreadedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.block.data_mut().read(buf)? {
+                0 => {
+                    if !self.next_block()? {
+                        return Ok(0);
+                    }
+                }
+                n => return Ok(n),
+            }
+        }
+    }
+}
+
+impl<R> Drop for MultithreadedReader<R> {
+    fn drop(&mut self) {
+        self.queue.clear();
+
+        // Dropping the job sender closes the channel, which stops the workers.
+        self.tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn spawn_inflater(rx: Arc<Mutex<Receiver<Job>>>) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        let job = {
+            let rx = match rx.lock() {
+                Ok(rx) => rx,
+                Err(_) => break,
+            };
+
+            match rx.recv() {
+                Ok(job) => job,
+                Err(_) => break,
+            }
+        };
+
+        let mut block = Block::default();
+        block.set_position(job.position);
+
+        let result = inflate_block(&job.cdata, &mut block).map(|_| block);
+
+        // The receiver is dropped when the reader discards read-ahead blocks, e.g., on seek.
+        let _ = job.tx.send(result);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use crate::Writer;
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+
+        writer.finish()
+    }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let data = build_data()?;
+
+        for worker_count in 1..=4 {
+            let worker_count = NonZeroUsize::new(worker_count).unwrap();
+            let mut reader = MultithreadedReader::with_worker_count(worker_count, &data[..]);
+
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+
+            assert_eq!(buf, b"noodles-bgzf");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_virtual_position() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut expected_reader = crate::Reader::new(&data[..]);
+        let mut reader = MultithreadedReader::new(&data[..]);
+
+        for len in &[7, 1, 1, 3] {
+            let mut expected_buf = vec![0; *len];
+            expected_reader.read_exact(&mut expected_buf)?;
+
+            let mut buf = vec![0; *len];
+            reader.read_exact(&mut buf)?;
+
+            assert_eq!(buf, expected_buf);
+            assert_eq!(
+                reader.virtual_position(),
+                expected_reader.virtual_position()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let data = build_data()?;
+        let mut reader = MultithreadedReader::new(Cursor::new(data));
+
+        let mut buf = [0; 9];
+        reader.read_exact(&mut buf)?;
+        let pos = reader.virtual_position();
+
+        reader.seek(VirtualPosition::from(3))?;
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(3));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"dles-bgzf");
+
+        reader.seek(pos)?;
+        assert_eq!(reader.virtual_position(), pos);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"gzf");
+
+        Ok(())
+    }
+}
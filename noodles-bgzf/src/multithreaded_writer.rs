@@ -0,0 +1,453 @@
+//! Multithreaded BGZF writer.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    cmp,
+    collections::VecDeque,
+    io::{self, Write},
+    mem,
+    num::NonZeroUsize,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use flate2::Compression;
+
+use super::writer::{deflate_block, CompressionLevel, BGZF_EOF, MAX_BGZF_BLOCK_SIZE};
+
+type FrameResult = io::Result<Vec<u8>>;
+
+struct Job {
+    data: Vec<u8>,
+    tx: Sender<FrameResult>,
+}
+
+/// A multithreaded BGZF writer.
+///
+/// Uncompressed data is buffered into blocks, which are compressed concurrently by a pool of
+/// worker threads. Compressed blocks are written to the underlying stream in the same order as
+/// the uncompressed data was written, so the output is the same regardless of the number of
+/// workers.
+///
+/// This implements [`std::io::Write`], consuming uncompressed data and emitting compressed data.
+///
+/// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::MultithreadedWriter::new(Vec::new());
+/// writer.write_all(b"noodles-bgzf")?;
+///
+/// let data = writer.finish()?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct MultithreadedWriter<W>
+where
+    W: Write,
+{
+    inner: Option<W>,
+    buf: Vec<u8>,
+    queue: VecDeque<Receiver<FrameResult>>,
+    max_queue_len: usize,
+    tx: Option<Sender<Job>>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl<W> MultithreadedWriter<W>
+where
+    W: Write,
+{
+    /// Creates a multithreaded BGZF writer with a worker count equal to the available
+    /// parallelism of the system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::MultithreadedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::with_worker_count(default_worker_count(), inner)
+    }
+
+    /// Returns a builder to create a writer with options, e.g., a compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::MultithreadedWriter::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    /// Creates a multithreaded BGZF writer with a given number of worker threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let writer = bgzf::MultithreadedWriter::with_worker_count(worker_count, Vec::new());
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: W) -> Self {
+        Self::with_options(worker_count, CompressionLevel::default(), inner)
+    }
+
+    fn with_options(
+        worker_count: NonZeroUsize,
+        compression_level: CompressionLevel,
+        inner: W,
+    ) -> Self {
+        let worker_count = worker_count.get();
+        let compression_level = Compression::from(compression_level);
+
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        let handles = (0..worker_count)
+            .map(|_| spawn_deflater(Arc::clone(&rx), compression_level))
+            .collect();
+
+        Self {
+            inner: Some(inner),
+            buf: Vec::with_capacity(MAX_BGZF_BLOCK_SIZE as usize),
+            queue: VecDeque::with_capacity(worker_count),
+            max_queue_len: worker_count,
+            tx: Some(tx),
+            handles,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// Compressed blocks that are still being processed by workers are not yet written to the
+    /// underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::MultithreadedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("missing inner writer")
+    }
+
+    /// Attempts to finish the output stream by compressing and writing any remaining buffers.
+    ///
+    /// This then appends the final BGZF EOF block and stops the workers. Finishing an already
+    /// finished stream does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::MultithreadedWriter::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    ///
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        if self.is_finished() {
+            return Ok(());
+        }
+
+        self.flush()?;
+
+        let inner = self.inner.as_mut().expect("missing inner writer");
+        inner.write_all(BGZF_EOF)?;
+        inner.flush()?;
+
+        self.shutdown();
+
+        Ok(())
+    }
+
+    /// Returns the underlying writer after finishing the output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::MultithreadedWriter::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    ///
+    /// let data = writer.finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        Ok(self.inner.take().expect("missing inner writer"))
+    }
+
+    // The job sender is dropped when the stream is finished, i.e., after the EOF block is written.
+    fn is_finished(&self) -> bool {
+        self.tx.is_none()
+    }
+
+    // Sends the current buffer to the workers as a single block.
+    fn send_block(&mut self) -> io::Result<()> {
+        let capacity = MAX_BGZF_BLOCK_SIZE as usize;
+        let data = mem::replace(&mut self.buf, Vec::with_capacity(capacity));
+
+        let (tx, rx) = mpsc::channel();
+
+        self.tx
+            .as_ref()
+            .ok_or_else(finished_error)?
+            .send(Job { data, tx })
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "deflater hung up"))?;
+
+        self.queue.push_back(rx);
+
+        while self.queue.len() > self.max_queue_len {
+            self.write_next_frame()?;
+        }
+
+        Ok(())
+    }
+
+    // Waits for the oldest compressed block and writes it to the underlying stream.
+    fn write_next_frame(&mut self) -> io::Result<()> {
+        if let Some(rx) = self.queue.pop_front() {
+            let frame = rx
+                .recv()
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "deflater hung up"))??;
+
+            self.inner
+                .as_mut()
+                .expect("missing inner writer")
+                .write_all(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    fn shutdown(&mut self) {
+        self.queue.clear();
+
+        // Dropping the job sender closes the channel, which stops the workers.
+        self.tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<W> Write for MultithreadedWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.is_finished() {
+            return Err(finished_error());
+        }
+
+        let max_block_size = MAX_BGZF_BLOCK_SIZE as usize;
+
+        if self.buf.len() >= max_block_size {
+            self.send_block()?;
+        }
+
+        let n = cmp::min(max_block_size - self.buf.len(), buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.send_block()?;
+        }
+
+        while !self.queue.is_empty() {
+            self.write_next_frame()?;
+        }
+
+        self.inner.as_mut().expect("missing inner writer").flush()
+    }
+}
+
+impl<W> Drop for MultithreadedWriter<W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if self.inner.is_some() && !self.is_finished() {
+            let _ = self.try_finish();
+        }
+
+        self.shutdown();
+    }
+}
+
+fn finished_error() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "writer is finished")
+}
+
+fn default_worker_count() -> NonZeroUsize {
+    thread::available_parallelism().unwrap_or_else(|_| NonZeroUsize::MIN)
+}
+
+fn spawn_deflater(rx: Arc<Mutex<Receiver<Job>>>, compression_level: Compression) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        let job = {
+            let rx = match rx.lock() {
+                Ok(rx) => rx,
+                Err(_) => break,
+            };
+
+            match rx.recv() {
+                Ok(job) => job,
+                Err(_) => break,
+            }
+        };
+
+        let result = deflate_block(&job.data, compression_level);
+
+        // The receiver is dropped if the writer is shut down before the block is written.
+        let _ = job.tx.send(result);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::{Reader, Writer};
+
+    use super::*;
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let data: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+
+        let mut expected_writer = Writer::new(Vec::new());
+        expected_writer.write_all(&data)?;
+        let expected = expected_writer.finish()?;
+
+        for worker_count in 1..=4 {
+            let worker_count = NonZeroUsize::new(worker_count).unwrap();
+            let mut writer = MultithreadedWriter::with_worker_count(worker_count, Vec::new());
+            writer.write_all(&data)?;
+            let actual = writer.finish()?;
+
+            assert_eq!(actual, expected);
+
+            let mut reader = Reader::new(&actual[..]);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            assert_eq!(buf, data);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_compression_level() -> io::Result<()> {
+        let data: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+
+        for compression_level in &[CompressionLevel::none(), CompressionLevel::best()] {
+            let mut expected_writer = Writer::builder(Vec::new())
+                .set_compression_level(*compression_level)
+                .build();
+            expected_writer.write_all(&data)?;
+            let expected = expected_writer.finish()?;
+
+            let mut writer = MultithreadedWriter::builder(Vec::new())
+                .set_worker_count(NonZeroUsize::new(2).unwrap())
+                .set_compression_level(*compression_level)
+                .build();
+            writer.write_all(&data)?;
+            let actual = writer.finish()?;
+
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flush() -> io::Result<()> {
+        let mut writer = MultithreadedWriter::new(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        assert!(!writer.get_ref().is_empty());
+
+        writer.write_all(b"-bgzf")?;
+
+        let data = writer.finish()?;
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, b"noodles-bgzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish() -> io::Result<()> {
+        let mut writer = MultithreadedWriter::new(Vec::new());
+        writer.write_all(b"noodles")?;
+
+        let data = writer.finish()?;
+        let eof_start = data.len() - BGZF_EOF.len();
+
+        assert_eq!(&data[eof_start..], BGZF_EOF);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_finish_after_finishing() -> io::Result<()> {
+        let mut writer = MultithreadedWriter::new(Vec::new());
+        writer.write_all(b"noodles")?;
+
+        writer.try_finish()?;
+        let expected = writer.get_ref().clone();
+
+        writer.try_finish()?;
+        assert_eq!(writer.get_ref(), &expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_after_finishing() -> io::Result<()> {
+        let mut writer = MultithreadedWriter::new(Vec::new());
+        writer.try_finish()?;
+
+        assert!(matches!(
+            writer.write(b"noodles"),
+            Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use std::{io::Write, num::NonZeroUsize};
+
+use crate::writer::CompressionLevel;
+
+use super::MultithreadedWriter;
+
+/// A multithreaded BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder<W>
+where
+    W: Write,
+{
+    inner: W,
+    worker_count: Option<NonZeroUsize>,
+    compression_level: CompressionLevel,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    /// Creates a multithreaded BGZF writer builder.
+    ///
+    /// Typically, [`bgzf::MultithreadedWriter::builder`] is used instead of calling
+    /// [`bgzf::multithreaded_writer::Builder::new`].
+    ///
+    /// [`bgzf::MultithreadedWriter::builder`]: ../struct.MultithreadedWriter.html#method.builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::MultithreadedWriter::builder(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            worker_count: None,
+            compression_level: CompressionLevel::default(),
+        }
+    }
+
+    /// Sets the number of worker threads.
+    ///
+    /// By default, the worker count is equal to the available parallelism of the system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let writer = bgzf::MultithreadedWriter::builder(Vec::new())
+    ///     .set_worker_count(NonZeroUsize::new(4).unwrap())
+    ///     .build();
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::MultithreadedWriter::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::best())
+    ///     .build();
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Builds a multithreaded BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::MultithreadedWriter::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> MultithreadedWriter<W> {
+        let worker_count = self
+            .worker_count
+            .unwrap_or_else(super::default_worker_count);
+
+        MultithreadedWriter::with_options(worker_count, self.compression_level, self.inner)
+    }
+}
@@ -0,0 +1,93 @@
+//! Raw (compressed) BGZF blocks.
+//!
+//! Unlike [`bgzf::Reader`] and [`bgzf::Writer`], which abstract away blocks, this reads and writes
+//! BGZF blocks as-is, i.e., without decompressing or compressing their data. This is useful for
+//! tools that work at the block level, e.g., to quickly concatenate BGZF files, verify the
+//! integrity of each block, or salvage data from a corrupt file.
+//!
+//! [`bgzf::Reader`]: ../struct.Reader.html
+//! [`bgzf::Writer`]: ../struct.Writer.html
+//!
+//! # Examples
+//!
+//! ## Concatenate BGZF files
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bgzf::raw;
+//!
+//! let mut writer = File::create("out.gz").map(raw::Writer::new)?;
+//!
+//! for src in &["a.gz", "b.gz"] {
+//!     let mut reader = File::open(src).map(raw::Reader::new)?;
+//!
+//!     for result in reader.blocks() {
+//!         let block = result?;
+//!
+//!         if !block.is_eof() {
+//!             writer.write_block(&block)?;
+//!         }
+//!     }
+//! }
+//!
+//! writer.finish()?;
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! ## Verify the integrity of each block
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bgzf::raw;
+//!
+//! let mut reader = File::open("data.gz").map(raw::Reader::new)?;
+//!
+//! for result in reader.blocks() {
+//!     let block = result?;
+//!
+//!     if let Err(e) = block.inflate() {
+//!         eprintln!("block at {}: {}", block.position(), e);
+//!     }
+//! }
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! ## Salvage blocks from a corrupt file
+//!
+//! Blocks with corrupt data are detected by inflating them. Corrupt or truncated blocks whose
+//! headers cannot be read are skipped by resyncing the reader to the next block header.
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bgzf::raw;
+//!
+//! let mut reader = File::open("data.gz").map(raw::Reader::new)?;
+//! let mut writer = File::create("out.gz").map(raw::Writer::new)?;
+//!
+//! loop {
+//!     match reader.read_block() {
+//!         Ok(Some(block)) => match block.inflate() {
+//!             Ok(_) if !block.is_eof() => writer.write_block(&block)?,
+//!             Ok(_) => {}
+//!             Err(e) => eprintln!("block at {}: {}", block.position(), e),
+//!         },
+//!         Ok(None) => break,
+//!         Err(e) => match e.kind() {
+//!             io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => {
+//!                 let skipped = reader.resync()?;
+//!                 eprintln!("skipped {:?}: {}", skipped, e);
+//!             }
+//!             _ => return Err(e),
+//!         },
+//!     }
+//! }
+//!
+//! writer.finish()?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+mod block;
+pub mod reader;
+mod writer;
+
+pub use self::{block::Block, reader::Reader, writer::Writer};
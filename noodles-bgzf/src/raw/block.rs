@@ -0,0 +1,272 @@
+use std::io::{self, Read};
+
+use byteorder::{ByteOrder, LittleEndian};
+use flate2::{read::DeflateDecoder, Crc};
+
+use crate::{gz, writer::BGZF_EOF, BGZF_HEADER_SIZE};
+
+/// A raw BGZF block.
+///
+/// This holds the entire block, i.e., the gzip header, compressed data, and gzip trailer, and its
+/// position in the compressed stream.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+    position: u64,
+    data: Vec<u8>,
+}
+
+impl Block {
+    pub(crate) fn new(position: u64, data: Vec<u8>) -> Self {
+        debug_assert!(data.len() >= BGZF_HEADER_SIZE + gz::TRAILER_SIZE);
+        Self { position, data }
+    }
+
+    /// Returns the position of the block in the compressed stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.position(), 0);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the total size of the block in the compressed stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.size(), 28);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the compressed (deflate) data of the block.
+    ///
+    /// This excludes the gzip header and trailer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.cdata(), [0x03, 0x00]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn cdata(&self) -> &[u8] {
+        let end = self.data.len() - gz::TRAILER_SIZE;
+        &self.data[BGZF_HEADER_SIZE..end]
+    }
+
+    /// Returns the CRC32 of the uncompressed data, as stored in the gzip trailer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.crc32(), 0);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn crc32(&self) -> u32 {
+        let start = self.data.len() - gz::TRAILER_SIZE;
+        LittleEndian::read_u32(&self.data[start..])
+    }
+
+    /// Returns the size of the uncompressed data, as stored in the gzip trailer (ISIZE).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.uncompressed_size(), 7);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn uncompressed_size(&self) -> u32 {
+        let start = self.data.len() - 4;
+        LittleEndian::read_u32(&self.data[start..])
+    }
+
+    /// Returns whether this block is the BGZF EOF marker block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert!(block.is_eof());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn is_eof(&self) -> bool {
+        self.data == BGZF_EOF
+    }
+
+    /// Decompresses the block and verifies its data.
+    ///
+    /// This returns an error of kind [`std::io::ErrorKind::InvalidData`] if the data cannot be
+    /// decompressed or if its size or CRC32 do not match those stored in the gzip trailer.
+    ///
+    /// [`std::io::ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// assert_eq!(block.inflate()?, b"noodles");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn inflate(&self) -> io::Result<Vec<u8>> {
+        let mut decoder = DeflateDecoder::new(self.cdata());
+        let mut buf = Vec::with_capacity(self.uncompressed_size() as usize);
+
+        decoder
+            .read_to_end(&mut buf)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if buf.len() != self.uncompressed_size() as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "uncompressed size mismatch: expected {}, got {}",
+                    self.uncompressed_size(),
+                    buf.len()
+                ),
+            ));
+        }
+
+        let mut crc = Crc::new();
+        crc.update(&buf);
+
+        if crc.sum() != self.crc32() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "CRC32 mismatch: expected {:08x}, got {:08x}",
+                    self.crc32(),
+                    crc.sum()
+                ),
+            ));
+        }
+
+        Ok(buf)
+    }
+}
+
+impl AsRef<[u8]> for Block {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::Writer;
+
+    use super::*;
+
+    fn build_block() -> io::Result<Block> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        Ok(Block::new(8, writer.get_ref().clone()))
+    }
+
+    #[test]
+    fn test_accessors() -> io::Result<()> {
+        let block = build_block()?;
+
+        assert_eq!(block.position(), 8);
+        assert_eq!(block.size(), block.as_ref().len());
+        assert_eq!(
+            block.cdata().len(),
+            block.size() - BGZF_HEADER_SIZE - gz::TRAILER_SIZE
+        );
+        assert_eq!(block.uncompressed_size(), 7);
+        assert!(!block.is_eof());
+
+        let mut crc = Crc::new();
+        crc.update(b"noodles");
+        assert_eq!(block.crc32(), crc.sum());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inflate() -> io::Result<()> {
+        let block = build_block()?;
+        assert_eq!(block.inflate()?, b"noodles");
+
+        let mut data = block.as_ref().to_vec();
+        let crc32_start = data.len() - gz::TRAILER_SIZE;
+        data[crc32_start] ^= 0xff;
+        let block = Block::new(0, data);
+        assert_eq!(
+            block.inflate().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut data = build_block()?.as_ref().to_vec();
+        let isize_start = data.len() - 4;
+        data[isize_start] = 8;
+        let block = Block::new(0, data);
+        assert_eq!(
+            block.inflate().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        Ok(())
+    }
+}
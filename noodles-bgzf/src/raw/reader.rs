@@ -0,0 +1,397 @@
+//! Raw BGZF block reader.
+
+mod blocks;
+
+pub use self::blocks::Blocks;
+
+use std::{
+    io::{self, Read},
+    mem,
+    ops::Range,
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{gz, writer::BGZF_FLG, BGZF_HEADER_SIZE};
+
+use super::Block;
+
+// BGZF extra subfield identifiers ("BC")
+const BGZF_SI1: u8 = 0x42;
+const BGZF_SI2: u8 = 0x43;
+
+/// A raw BGZF block reader.
+///
+/// This reads blocks as-is, without decompressing their data.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::raw;
+///
+/// let mut reader = File::open("data.gz").map(raw::Reader::new)?;
+///
+/// for result in reader.blocks() {
+///     let block = result?;
+///     println!("{}\t{}\t{}", block.position(), block.size(), block.uncompressed_size());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Reader<R> {
+    inner: R,
+    position: u64,
+    buf: Vec<u8>,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a raw BGZF block reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw;
+    /// let data = [];
+    /// let reader = raw::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns the current position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw;
+    /// let data = [];
+    /// let reader = raw::Reader::new(&data[..]);
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads a raw block.
+    ///
+    /// This returns `None` if the stream reached EOF.
+    ///
+    /// If the block header is invalid, an error is returned, and the stream position is not
+    /// advanced. Use [`resync`] to skip to the next block.
+    ///
+    /// [`resync`]: #method.resync
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = raw::Reader::new(&data[..]);
+    ///
+    /// let block = reader.read_block()?.unwrap();
+    /// assert_eq!(block.uncompressed_size(), 7);
+    ///
+    /// let block = reader.read_block()?.unwrap();
+    /// assert!(block.is_eof());
+    ///
+    /// assert!(reader.read_block()?.is_none());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_block(&mut self) -> io::Result<Option<Block>> {
+        let n = self.fill_buf(BGZF_HEADER_SIZE)?;
+
+        if n == 0 {
+            return Ok(None);
+        } else if n < BGZF_HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF in block header",
+            ));
+        }
+
+        let block_size = parse_header(&self.buf[..BGZF_HEADER_SIZE])?;
+
+        if self.fill_buf(block_size)? < block_size {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF in block",
+            ));
+        }
+
+        let rest = self.buf.split_off(block_size);
+        let data = mem::replace(&mut self.buf, rest);
+
+        let block = Block::new(self.position, data);
+        self.position += block_size as u64;
+
+        Ok(Some(block))
+    }
+
+    /// Skips to the start of the next valid block header.
+    ///
+    /// This scans forward from the current position for a BGZF block header, i.e., a gzip header
+    /// with a `BC` extra subfield and a valid block size. If none is found, the rest of the stream
+    /// is skipped. This returns the range of skipped bytes, which is empty if the current position
+    /// is already at the start of a block header.
+    ///
+    /// This is typically used to recover after [`read_block`] fails with a corrupt block header.
+    /// Note that a header that is found is not guaranteed to be the start of a real block.
+    ///
+    /// [`read_block`]: #method.read_block
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let mut data = b"noodles".to_vec();
+    /// data.extend(bgzf::Writer::new(Vec::new()).finish()?);
+    ///
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// assert!(reader.read_block().is_err());
+    ///
+    /// assert_eq!(reader.resync()?, 0..7);
+    /// assert!(reader.read_block()?.map(|block| block.is_eof()).unwrap_or(false));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn resync(&mut self) -> io::Result<Range<u64>> {
+        let start = self.position;
+
+        loop {
+            let n = self.fill_buf(BGZF_HEADER_SIZE)?;
+
+            if n < BGZF_HEADER_SIZE {
+                self.position += n as u64;
+                self.buf.clear();
+                break;
+            } else if parse_header(&self.buf[..BGZF_HEADER_SIZE]).is_ok() {
+                break;
+            }
+
+            self.buf.remove(0);
+            self.position += 1;
+        }
+
+        Ok(start..self.position)
+    }
+
+    /// Returns an iterator over raw blocks starting from the current stream position.
+    ///
+    /// Iteration stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let data = bgzf::Writer::new(Vec::new()).finish()?;
+    /// let mut reader = raw::Reader::new(&data[..]);
+    ///
+    /// let blocks: Vec<_> = reader.blocks().collect::<io::Result<_>>()?;
+    /// assert_eq!(blocks.len(), 1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn blocks(&mut self) -> Blocks<'_, R> {
+        Blocks::new(self)
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    // Reads from the underlying reader until the buffer has at least `len` bytes or the stream
+    // reaches EOF, returning the buffer length.
+    fn fill_buf(&mut self, len: usize) -> io::Result<usize> {
+        let start = self.buf.len();
+
+        if start < len {
+            self.buf.resize(len, 0);
+            let n = read_fully(&mut self.inner, &mut self.buf[start..])?;
+            self.buf.truncate(start + n);
+        }
+
+        Ok(self.buf.len())
+    }
+}
+
+// Reads until `buf` is full or the stream reaches EOF, returning the number of bytes read.
+fn read_fully<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: Read,
+{
+    let mut n = 0;
+
+    while n < buf.len() {
+        match reader.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(m) => n += m,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(n)
+}
+
+// Validates a BGZF block header and returns the total block size.
+fn parse_header(header: &[u8]) -> io::Result<usize> {
+    if header[0..2] != gz::MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid gzip magic number",
+        ));
+    }
+
+    if header[2] != gz::CompressionMethod::Deflate as u8 || header[3] != BGZF_FLG {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BGZF header",
+        ));
+    }
+
+    if header[12] != BGZF_SI1 || header[13] != BGZF_SI2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing BGZF extra subfield",
+        ));
+    }
+
+    // Add 1 because BSIZE is "total Block SIZE minus 1".
+    let block_size = usize::from(LittleEndian::read_u16(&header[16..18])) + 1;
+
+    if block_size < BGZF_HEADER_SIZE + gz::TRAILER_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid block size",
+        ));
+    }
+
+    Ok(block_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::Writer;
+
+    use super::*;
+
+    #[test]
+    fn test_read_block() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+
+        let block = reader.read_block()?.unwrap();
+        assert_eq!(block.position(), 0);
+        assert_eq!(block.inflate()?, b"noodles");
+
+        let block = reader.read_block()?.unwrap();
+        assert_eq!(block.position(), 35);
+        assert_eq!(block.inflate()?, b"bgzf");
+
+        let block = reader.read_block()?.unwrap();
+        assert!(block.is_eof());
+
+        assert!(reader.read_block()?.is_none());
+        assert_eq!(reader.position(), data.len() as u64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_block_with_invalid_header() {
+        let data = [0x1f, 0x8c, 0x08, 0x04];
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(
+            reader.read_block().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        let mut data = crate::writer::BGZF_EOF.to_vec();
+        data[1] = 0x8c;
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(
+            reader.read_block().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut data = crate::writer::BGZF_EOF.to_vec();
+        data[16] = 0x00;
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(
+            reader.read_block().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_resync() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let block = writer.get_ref().clone();
+        let data = writer.finish()?;
+
+        // A corrupt block (bad magic number), followed by junk, a valid block, and the EOF block.
+        let mut corrupt_block = block.clone();
+        corrupt_block[1] = 0x8c;
+
+        let mut src = corrupt_block;
+        src.extend_from_slice(b"bgzf");
+        src.extend_from_slice(&data);
+
+        let mut reader = Reader::new(&src[..]);
+
+        assert_eq!(
+            reader.read_block().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(reader.position(), 0);
+
+        let skipped = reader.resync()?;
+        let end = block.len() as u64 + 4;
+        assert_eq!(skipped, 0..end);
+
+        let actual = reader.read_block()?.unwrap();
+        assert_eq!(actual.position(), end);
+        assert_eq!(actual.inflate()?, b"noodles");
+
+        assert_eq!(reader.resync()?, reader.position()..reader.position());
+        assert!(reader.read_block()?.unwrap().is_eof());
+        assert!(reader.read_block()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resync_without_block() -> io::Result<()> {
+        let data = b"noodles";
+        let mut reader = Reader::new(&data[..]);
+
+        assert_eq!(reader.resync()?, 0..7);
+        assert!(reader.read_block()?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::{self, Read};
+
+use super::{Block, Reader};
+
+/// An iterator over raw blocks of a raw BGZF reader.
+///
+/// This is created by calling [`raw::Reader::blocks`].
+///
+/// [`raw::Reader::blocks`]: struct.Reader.html#method.blocks
+pub struct Blocks<'a, R>
+where
+    R: Read,
+{
+    reader: &'a mut Reader<R>,
+    is_done: bool,
+}
+
+impl<'a, R> Blocks<'a, R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self {
+            reader,
+            is_done: false,
+        }
+    }
+}
+
+impl<'a, R> Iterator for Blocks<'a, R>
+where
+    R: Read,
+{
+    type Item = io::Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        match self.reader.read_block() {
+            Ok(Some(block)) => Some(Ok(block)),
+            Ok(None) => {
+                self.is_done = true;
+                None
+            }
+            Err(e) => {
+                self.is_done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
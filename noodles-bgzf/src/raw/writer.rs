@@ -0,0 +1,193 @@
+use std::io::{self, Write};
+
+use crate::writer::BGZF_EOF;
+
+use super::Block;
+
+/// A raw BGZF block writer.
+///
+/// This writes blocks as-is, without compressing their data. The final BGZF EOF block is appended
+/// when the writer is finished.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf::{self as bgzf, raw};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let src = writer.finish()?;
+///
+/// let mut reader = raw::Reader::new(&src[..]);
+/// let mut writer = raw::Writer::new(Vec::new());
+///
+/// for result in reader.blocks() {
+///     let block = result?;
+///
+///     if !block.is_eof() {
+///         writer.write_block(&block)?;
+///     }
+/// }
+///
+/// let dst = writer.finish()?;
+/// assert_eq!(dst, src);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: W,
+    position: u64,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a raw BGZF block writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw;
+    /// let writer = raw::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw;
+    /// let writer = raw::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the current position of the stream.
+    ///
+    /// This is the position the next block will be written to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::raw;
+    /// let writer = raw::Writer::new(Vec::new());
+    /// assert_eq!(writer.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Writes a raw block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, raw};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = raw::Reader::new(&data[..]);
+    /// let block = reader.read_block()?.unwrap();
+    ///
+    /// let mut writer = raw::Writer::new(Vec::new());
+    /// writer.write_block(&block)?;
+    /// assert_eq!(writer.position(), block.size() as u64);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_block(&mut self, block: &Block) -> io::Result<()> {
+        self.inner.write_all(block.as_ref())?;
+        self.position += block.size() as u64;
+        Ok(())
+    }
+
+    /// Attempts to finish the output stream by appending the final BGZF EOF block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::raw;
+    /// let mut writer = raw::Writer::new(Vec::new());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.write_all(BGZF_EOF)?;
+        self.position += BGZF_EOF.len() as u64;
+        self.inner.flush()
+    }
+
+    /// Returns the underlying writer after finishing the output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::raw;
+    /// let writer = raw::Writer::new(Vec::new());
+    /// let data = writer.finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::raw::Reader;
+
+    use super::*;
+
+    #[test]
+    fn test_concatenate() -> io::Result<()> {
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        let a = writer.finish()?;
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"-bgzf")?;
+        let b = writer.finish()?;
+
+        let mut writer = Writer::new(Vec::new());
+
+        for src in &[a, b] {
+            let mut reader = Reader::new(&src[..]);
+
+            for result in reader.blocks() {
+                let block = result?;
+
+                if !block.is_eof() {
+                    writer.write_block(&block)?;
+                }
+            }
+        }
+
+        let data = writer.finish()?;
+
+        let mut reader = crate::Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        let eof_start = data.len() - BGZF_EOF.len();
+        assert_eq!(&data[eof_start..], BGZF_EOF);
+
+        Ok(())
+    }
+}
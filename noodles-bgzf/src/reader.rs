@@ -1,9 +1,9 @@
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
 
 use byteorder::{ByteOrder, LittleEndian};
 use flate2::read::DeflateDecoder;
 
-use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use super::{gz, gzi, writer::BGZF_EOF, Block, VirtualPosition, BGZF_HEADER_SIZE};
 
 /// A BGZF reader.
 ///
@@ -11,8 +11,11 @@ use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
 /// validation is also disabled when decompressing data.
 ///
 /// This implements [`std::io::Read`], consuming compressed data and emitting uncompressed data.
+/// It also implements [`std::io::BufRead`] using the current block as its buffer, which keeps the
+/// virtual position exact after each consumed read, e.g., when reading lines.
 ///
 /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`std::io::BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
 ///
 /// # Examples
 ///
@@ -107,7 +110,9 @@ where
         self.inner.seek(SeekFrom::Start(compressed_pos))?;
         self.position = compressed_pos;
 
-        read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        self.block.set_position(self.position);
+        self.position += block_size as u64;
 
         self.block
             .data_mut()
@@ -115,6 +120,72 @@ where
 
         Ok(pos)
     }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The virtual position of the uncompressed position is looked up in the given gzip index
+    /// (GZI). This is typically used for files addressed by uncompressed offsets, e.g., using a
+    /// FASTA index (FAI) with a bgzip-compressed FASTA file.
+    ///
+    /// This returns the resulting virtual position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Read, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"-bgzf")?;
+    /// writer.try_finish()?;
+    ///
+    /// let index = writer.gzi_index();
+    /// let mut reader = bgzf::Reader::new(Cursor::new(writer.get_ref()));
+    /// reader.seek_by_uncompressed_position(index, 8)?;
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    /// assert_eq!(buf, b"bgzf");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek_by_uncompressed_position(
+        &mut self,
+        index: &gzi::Index,
+        pos: u64,
+    ) -> io::Result<VirtualPosition> {
+        let virtual_position = index.query(pos)?;
+        self.seek(virtual_position)
+    }
+
+    /// Checks whether the stream ends with a BGZF EOF block.
+    ///
+    /// A BGZF stream is terminated by an empty block (the EOF marker). If it is missing, the
+    /// stream is likely truncated, e.g., from an incomplete copy or write. This returns an error
+    /// of kind [`std::io::ErrorKind::UnexpectedEof`] in that case.
+    ///
+    /// The position of the underlying stream is restored after the check.
+    ///
+    /// [`std::io::ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(data));
+    /// reader.check_eof()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn check_eof(&mut self) -> io::Result<()> {
+        check_eof(&mut self.inner)
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -138,6 +209,83 @@ where
     }
 }
 
+impl<R> BufRead for Reader<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        loop {
+            let data = self.block.data_mut();
+
+            if (data.position() as usize) < data.get_ref().len() {
+                break;
+            }
+
+            match read_block(&mut self.inner, &mut self.cdata, &mut self.block)? {
+                0 => break,
+                bs => {
+                    self.block.set_position(self.position);
+                    self.position += bs as u64;
+                }
+            }
+        }
+
+        let data = self.block.data_mut();
+        let pos = data.position() as usize;
+        let buf = data.get_ref();
+
+        Ok(&buf[pos.min(buf.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let data = self.block.data_mut();
+        let pos = data.position();
+        data.set_position(pos + amt as u64);
+    }
+}
+
+/// Checks that the given stream ends with a BGZF EOF block, restoring its position afterward.
+pub(crate) fn check_eof<R>(reader: &mut R) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let result = read_eof_block(reader);
+    reader.seek(SeekFrom::Start(pos))?;
+
+    if result? {
+        Ok(())
+    } else {
+        Err(missing_eof_block_error())
+    }
+}
+
+pub(crate) fn missing_eof_block_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "missing BGZF EOF block; the file may be truncated",
+    )
+}
+
+fn read_eof_block<R>(reader: &mut R) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    let eof_block_len = BGZF_EOF.len() as u64;
+    let len = reader.seek(SeekFrom::End(0))?;
+
+    if len < eof_block_len {
+        return Ok(false);
+    }
+
+    reader.seek(SeekFrom::Start(len - eof_block_len))?;
+
+    let mut buf = vec![0; BGZF_EOF.len()];
+    reader.read_exact(&mut buf)?;
+
+    Ok(buf == BGZF_EOF)
+}
+
 fn read_block_size<R>(reader: &mut R) -> io::Result<u16>
 where
     R: Read,
@@ -154,14 +302,6 @@ where
     Ok(LittleEndian::read_u16(bsize) + 1)
 }
 
-fn read_trailer<R>(reader: &mut R) -> io::Result<()>
-where
-    R: Read,
-{
-    let mut trailer = [0; gz::TRAILER_SIZE];
-    reader.read_exact(&mut trailer)
-}
-
 fn inflate_data<R>(reader: R, writer: &mut Vec<u8>) -> io::Result<usize>
 where
     R: Read,
@@ -170,7 +310,10 @@ where
     decoder.read_to_end(writer)
 }
 
-fn read_block<R>(reader: &mut R, cdata: &mut Vec<u8>, block: &mut Block) -> io::Result<usize>
+/// Reads the compressed data of a block, discarding its header and trailer.
+///
+/// This returns the total size of the block. If the returned size is 0, the stream reached EOF.
+pub(crate) fn read_compressed_block<R>(reader: &mut R, cdata: &mut Vec<u8>) -> io::Result<usize>
 where
     R: Read,
 {
@@ -180,19 +323,173 @@ where
         Err(e) => return Err(e),
     };
 
-    let cdata_len = block_size - BGZF_HEADER_SIZE - gz::TRAILER_SIZE;
+    let cdata_len = block_size
+        .checked_sub(BGZF_HEADER_SIZE + gz::TRAILER_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid block size"))?;
+
     cdata.resize(cdata_len, Default::default());
     reader.read_exact(cdata)?;
 
-    read_trailer(reader)?;
+    let mut trailer = [0; gz::TRAILER_SIZE];
+    reader.read_exact(&mut trailer)?;
+
+    Ok(block_size)
+}
 
+/// Decompresses the given compressed data into a block, resetting its cursor to the start.
+pub(crate) fn inflate_block(cdata: &[u8], block: &mut Block) -> io::Result<()> {
     let udata = block.data_mut();
     let udata_buf = udata.get_mut();
     udata_buf.clear();
 
-    inflate_data(&cdata[..], udata_buf)?;
+    inflate_data(cdata, udata_buf)?;
 
     udata.set_position(0);
 
+    Ok(())
+}
+
+fn read_block<R>(reader: &mut R, cdata: &mut Vec<u8>, block: &mut Block) -> io::Result<usize>
+where
+    R: Read,
+{
+    let block_size = read_compressed_block(reader, cdata)?;
+
+    if block_size > 0 {
+        inflate_block(cdata, block)?;
+    }
+
     Ok(block_size)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use crate::Writer;
+
+    use super::*;
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let mut buf = [0; 7];
+        reader.read_exact(&mut buf)?;
+        let eob = reader.virtual_position();
+
+        let mut buf = [0; 1];
+        reader.read_exact(&mut buf)?;
+        let pos = reader.virtual_position();
+
+        reader.seek(pos)?;
+        assert_eq!(reader.virtual_position(), pos);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"gzf");
+
+        reader.seek(eob)?;
+        assert_eq!(reader.virtual_position(), eob);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"bgzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_by_uncompressed_position() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.try_finish()?;
+
+        let index = writer.gzi_index();
+        let mut reader = Reader::new(Cursor::new(writer.get_ref()));
+
+        for (pos, expected) in [
+            (0, &b"noodles-bgzf"[..]),
+            (3, b"dles-bgzf"),
+            (7, b"-bgzf"),
+            (10, b"zf"),
+        ]
+        .iter()
+        {
+            reader.seek_by_uncompressed_position(index, *pos)?;
+
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+            assert_eq!(&buf[..], *expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_eof() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles-bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(data.clone()));
+        reader.check_eof()?;
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        let truncated_data = &data[..data.len() - BGZF_EOF.len()];
+        let mut reader = Reader::new(Cursor::new(truncated_data));
+        let e = reader.check_eof().unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut reader = Reader::new(Cursor::new(&data[..2]));
+        assert!(reader.check_eof().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_line() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles\n")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf\nreader\n")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = String::new();
+
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "noodles\n");
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(8));
+
+        buf.clear();
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "bgzf\n");
+
+        let (compressed_pos, uncompressed_pos) = reader.virtual_position().into();
+        assert!(compressed_pos > 0);
+        assert_eq!(uncompressed_pos, 5);
+
+        buf.clear();
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "reader\n");
+
+        buf.clear();
+        assert_eq!(reader.read_line(&mut buf)?, 0);
+
+        Ok(())
+    }
+}
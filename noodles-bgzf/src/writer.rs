@@ -1,16 +1,27 @@
+//! BGZF writer.
+
+mod builder;
+mod compression_level;
+
+pub use self::{builder::Builder, compression_level::CompressionLevel};
+
 use std::{
     cmp,
+    convert::TryFrom,
     io::{self, Write},
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::{write::DeflateEncoder, Compression, Crc};
 
-use super::{gz, BGZF_HEADER_SIZE};
+use super::{gz, gzi, VirtualPosition, BGZF_HEADER_SIZE};
 
-const MAX_BGZF_BLOCK_SIZE: u32 = 65536; // bytes
+// The maximum uncompressed size of a block. This is less than 64 KiB to leave room for the
+// overhead of stored (uncompressed) deflate blocks, e.g., when the compression level is 0 or the
+// data is incompressible, while still keeping the total block size (BSIZE + 1) within 64 KiB.
+pub(crate) const MAX_BGZF_BLOCK_SIZE: u32 = 65280; // bytes
 
-const BGZF_FLG: u8 = 0x04; // FEXTRA
+pub(crate) const BGZF_FLG: u8 = 0x04; // FEXTRA
 const BGZF_XFL: u8 = 0x00; // none
 const BGZF_XLEN: u16 = 6;
 
@@ -19,7 +30,7 @@ const BGZF_SI2: u8 = 0x43;
 const BGZF_SLEN: u16 = 2;
 
 // Sequence Alignment/Map Format Specification § 4.1.2 (accessed 2020-04-15)
-static BGZF_EOF: &[u8] = &[
+pub(crate) static BGZF_EOF: &[u8] = &[
     0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
     0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
 ];
@@ -50,6 +61,9 @@ where
     inner: W,
     encoder: DeflateEncoder<Vec<u8>>,
     crc: Crc,
+    position: u64,
+    uncompressed_position: u64,
+    gzi_index: gzi::Index,
 }
 
 impl<W> Writer<W>
@@ -65,10 +79,32 @@ where
     /// let writer = bgzf::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
+        Self::with_compression_level(inner, CompressionLevel::default())
+    }
+
+    /// Returns a builder to create a writer with options, e.g., a compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    fn with_compression_level(inner: W, compression_level: CompressionLevel) -> Self {
         Self {
             inner,
-            encoder: DeflateEncoder::new(Vec::new(), Compression::default()),
+            encoder: DeflateEncoder::new(Vec::new(), Compression::from(compression_level)),
             crc: Crc::new(),
+            position: 0,
+            uncompressed_position: 0,
+            gzi_index: gzi::Index::default(),
         }
     }
 
@@ -85,14 +121,70 @@ where
         &self.inner
     }
 
+    /// Returns the gzip index (GZI) of the blocks written so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"bgzf")?;
+    /// writer.flush()?;
+    ///
+    /// assert_eq!(writer.gzi_index().entries(), [(35, 7)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> &gzi::Index {
+        &self.gzi_index
+    }
+
+    /// Returns the current virtual position of the stream.
+    ///
+    /// The compressed position is the start of the current (unflushed) block, and the
+    /// uncompressed position is the number of bytes written to that block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(0));
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(7));
+    ///
+    /// writer.flush()?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(35 << 16));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        // The uncompressed block size is always < 64 KiB, so the casts cannot truncate.
+        VirtualPosition::try_from((self.position, self.crc.amount() as u16))
+            .expect("invalid virtual position")
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
         self.encoder.try_finish()?;
         let data = self.encoder.get_ref();
 
+        if self.position > 0 {
+            self.gzi_index
+                .push((self.position, self.uncompressed_position));
+        }
+
         write_header(&mut self.inner, data.len())?;
         self.inner.write_all(&data[..])?;
         write_trailer(&mut self.inner, self.crc.sum(), self.crc.amount())?;
 
+        self.position += (BGZF_HEADER_SIZE + data.len() + gz::TRAILER_SIZE) as u64;
+        self.uncompressed_position += u64::from(self.crc.amount());
+
         self.encoder.reset(Vec::new())?;
         self.crc.reset();
 
@@ -171,7 +263,7 @@ where
     }
 }
 
-fn write_header<W>(writer: &mut W, cdata_len: usize) -> io::Result<()>
+pub(crate) fn write_header<W>(writer: &mut W, cdata_len: usize) -> io::Result<()>
 where
     W: Write,
 {
@@ -193,7 +285,11 @@ where
     Ok(())
 }
 
-fn write_trailer<W>(writer: &mut W, checksum: u32, uncompressed_size: u32) -> io::Result<()>
+pub(crate) fn write_trailer<W>(
+    writer: &mut W,
+    checksum: u32,
+    uncompressed_size: u32,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -202,6 +298,23 @@ where
     Ok(())
 }
 
+// Compresses the given data as a complete BGZF block, i.e., with a gzip header and trailer.
+pub(crate) fn deflate_block(data: &[u8], compression_level: Compression) -> io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), compression_level);
+    encoder.write_all(data)?;
+    let cdata = encoder.finish()?;
+
+    let mut crc = Crc::new();
+    crc.update(data);
+
+    let mut frame = Vec::new();
+    write_header(&mut frame, cdata.len())?;
+    frame.extend_from_slice(&cdata);
+    write_trailer(&mut frame, crc.sum(), crc.amount())?;
+
+    Ok(frame)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +331,39 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_with_compression_level_none() -> io::Result<()> {
+        use std::io::Read;
+
+        use crate::Reader;
+
+        // Pseudorandom data is incompressible and fills more than one block.
+        let mut state = 1u32;
+        let data: Vec<u8> = (0..150_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+
+        for compression_level in &[CompressionLevel::none(), CompressionLevel::default()] {
+            let mut writer = Writer::builder(Vec::new())
+                .set_compression_level(*compression_level)
+                .build();
+
+            writer.write_all(&data)?;
+            let compressed_data = writer.finish()?;
+
+            let mut reader = Reader::new(&compressed_data[..]);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+
+            assert_eq!(buf, data);
+        }
+
+        Ok(())
+    }
 }
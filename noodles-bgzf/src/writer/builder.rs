@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use super::{CompressionLevel, Writer};
+
+/// A BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder<W>
+where
+    W: Write,
+{
+    inner: W,
+    compression_level: CompressionLevel,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    /// Creates a BGZF writer builder.
+    ///
+    /// Typically, [`bgzf::Writer::builder`] is used instead of calling
+    /// [`bgzf::writer::Builder::new`].
+    ///
+    /// [`bgzf::Writer::builder`]: struct.Writer.html#method.builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: CompressionLevel::default(),
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::none())
+    ///     .build();
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Builds a BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        Writer::with_compression_level(self.inner, self.compression_level)
+    }
+}
@@ -0,0 +1,127 @@
+use std::{convert::TryFrom, error, fmt};
+
+use flate2::Compression;
+
+const MAX_LEVEL: u8 = 9;
+const DEFAULT_LEVEL: u8 = 6;
+
+/// A BGZF writer compression level.
+///
+/// A compression level ranges from 0 to 9 (inclusive), where 0 is no compression and 9 is the best
+/// compression. Level 0 writes blocks as stored (uncompressed) deflate data, which is useful for
+/// fast, temporary files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CompressionLevel(u8);
+
+impl CompressionLevel {
+    /// Returns a compression level that disables compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// assert_eq!(u8::from(CompressionLevel::none()), 0);
+    /// ```
+    pub fn none() -> Self {
+        Self(0)
+    }
+
+    /// Returns a compression level optimized for speed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// assert_eq!(u8::from(CompressionLevel::fast()), 1);
+    /// ```
+    pub fn fast() -> Self {
+        Self(1)
+    }
+
+    /// Returns a compression level optimized for compression ratio.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// assert_eq!(u8::from(CompressionLevel::best()), 9);
+    /// ```
+    pub fn best() -> Self {
+        Self(MAX_LEVEL)
+    }
+}
+
+impl Default for CompressionLevel {
+    fn default() -> Self {
+        Self(DEFAULT_LEVEL)
+    }
+}
+
+/// An error returned when the conversion from a byte to a compression level fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryFromUByteError(u8);
+
+impl error::Error for TryFromUByteError {}
+
+impl fmt::Display for TryFromUByteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid compression level: expected {{0..={}}}, got {}",
+            MAX_LEVEL, self.0
+        )
+    }
+}
+
+impl TryFrom<u8> for CompressionLevel {
+    type Error = TryFromUByteError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        if n <= MAX_LEVEL {
+            Ok(Self(n))
+        } else {
+            Err(TryFromUByteError(n))
+        }
+    }
+}
+
+impl From<CompressionLevel> for u8 {
+    fn from(compression_level: CompressionLevel) -> Self {
+        compression_level.0
+    }
+}
+
+impl From<CompressionLevel> for Compression {
+    fn from(compression_level: CompressionLevel) -> Self {
+        Self::new(u32::from(compression_level.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(CompressionLevel::default(), CompressionLevel(6));
+    }
+
+    #[test]
+    fn test_try_from_u8_for_compression_level() {
+        assert_eq!(CompressionLevel::try_from(0), Ok(CompressionLevel(0)));
+        assert_eq!(CompressionLevel::try_from(9), Ok(CompressionLevel(9)));
+        assert_eq!(CompressionLevel::try_from(10), Err(TryFromUByteError(10)));
+    }
+
+    #[test]
+    fn test_from_compression_level_for_compression() {
+        assert_eq!(
+            Compression::from(CompressionLevel::none()),
+            Compression::none()
+        );
+        assert_eq!(
+            Compression::from(CompressionLevel::default()),
+            Compression::default()
+        );
+    }
+}
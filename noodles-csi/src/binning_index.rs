@@ -0,0 +1,114 @@
+//! Binning index trait and chunk optimization.
+
+use std::io;
+
+use noodles_bgzf::VirtualPosition;
+
+use super::index::reference_sequence::bin::Chunk;
+
+/// A binning index.
+///
+/// A binning index maps genomic intervals to the chunks of an associated file that may contain
+/// records that intersect the interval. This is implemented by the coordinate-sorted index (CSI)
+/// and can be implemented by other indices that use a similar binning scheme, e.g., BAI and TBI.
+pub trait BinningIndex {
+    /// Returns the optimized chunks that may contain records intersecting the given interval.
+    ///
+    /// Chunks that end before the minimum offset of the interval start (e.g., from a linear
+    /// index) are already removed.
+    ///
+    /// The reference sequence ID is 0-based, and the start and end positions are 1-based,
+    /// inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    ///
+    /// let index = csi::Index::default();
+    /// assert!(index.query(0, 8, 13).is_err());
+    /// ```
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>>;
+}
+
+/// Optimizes a list of chunks into a list of sorted, non-overlapping chunks.
+///
+/// Chunks that end before `min_offset` (typically from a linear index) are removed.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf::VirtualPosition;
+/// use noodles_csi::{binning_index::optimize_chunks, index::reference_sequence::bin::Chunk};
+///
+/// let chunks = [
+///     Chunk::new(VirtualPosition::from(2), VirtualPosition::from(5)),
+///     Chunk::new(VirtualPosition::from(3), VirtualPosition::from(7)),
+///     Chunk::new(VirtualPosition::from(9), VirtualPosition::from(12)),
+/// ];
+///
+/// let actual = optimize_chunks(&chunks, VirtualPosition::from(8));
+/// let expected = [Chunk::new(VirtualPosition::from(9), VirtualPosition::from(12))];
+/// assert_eq!(actual, expected);
+/// ```
+pub fn optimize_chunks(chunks: &[Chunk], min_offset: VirtualPosition) -> Vec<Chunk> {
+    let mut chunks: Vec<_> = chunks
+        .iter()
+        .filter(|c| c.end() > min_offset)
+        .copied()
+        .collect();
+
+    if chunks.is_empty() {
+        return chunks;
+    }
+
+    chunks.sort_unstable_by_key(|c| c.start());
+
+    // At worst, no chunks are merged, and the resulting list will be the same size as the input.
+    let mut merged_chunks = Vec::with_capacity(chunks.len());
+
+    // `chunks` is guaranteed to be non-empty.
+    let mut current_chunk = chunks[0];
+
+    for next_chunk in chunks.iter().skip(1) {
+        if next_chunk.start() > current_chunk.end() {
+            merged_chunks.push(current_chunk);
+            current_chunk = *next_chunk;
+        } else if current_chunk.end() < next_chunk.end() {
+            current_chunk = Chunk::new(current_chunk.start(), next_chunk.end());
+        }
+    }
+
+    merged_chunks.push(current_chunk);
+
+    merged_chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_chunks() -> Vec<Chunk> {
+        vec![
+            Chunk::new(VirtualPosition::from(2), VirtualPosition::from(5)),
+            Chunk::new(VirtualPosition::from(3), VirtualPosition::from(4)),
+            Chunk::new(VirtualPosition::from(5), VirtualPosition::from(7)),
+            Chunk::new(VirtualPosition::from(9), VirtualPosition::from(12)),
+            Chunk::new(VirtualPosition::from(10), VirtualPosition::from(15)),
+            Chunk::new(VirtualPosition::from(16), VirtualPosition::from(21)),
+        ]
+    }
+
+    #[test]
+    fn test_optimize_chunks() {
+        let chunks = build_chunks();
+        let actual = optimize_chunks(&chunks, VirtualPosition::from(10));
+
+        let expected = [
+            Chunk::new(VirtualPosition::from(9), VirtualPosition::from(15)),
+            Chunk::new(VirtualPosition::from(16), VirtualPosition::from(21)),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
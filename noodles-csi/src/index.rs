@@ -0,0 +1,212 @@
+//! Coordinate-sorted index (CSI) and fields.
+
+mod builder;
+mod indexer;
+pub mod reference_sequence;
+
+pub use self::{builder::Builder, indexer::Indexer, reference_sequence::ReferenceSequence};
+
+use std::io;
+
+use super::{binning_index::optimize_chunks, BinningIndex};
+
+use self::reference_sequence::bin::Chunk;
+
+// The default minimum shift and depth are equivalent to the BAI/TBI binning scheme.
+pub(crate) const DEFAULT_MIN_SHIFT: u8 = 14;
+pub(crate) const DEFAULT_DEPTH: u8 = 5;
+
+// The largest depth whose bin IDs (including the metadata pseudo-bin) fit in a u32.
+pub(crate) const MAX_DEPTH: u8 = 9;
+
+/// A coordinate-sorted index (CSI).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Index {
+    min_shift: u8,
+    depth: u8,
+    aux: Vec<u8>,
+    reference_sequences: Vec<ReferenceSequence>,
+    unplaced_unmapped_record_count: Option<u64>,
+}
+
+impl Index {
+    /// Returns a builder to create an index from each of its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let builder = csi::Index::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the number of bits for the minimum interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_min_shift(16).build();
+    /// assert_eq!(index.min_shift(), 16);
+    /// ```
+    pub fn min_shift(&self) -> u8 {
+        self.min_shift
+    }
+
+    /// Returns the depth of the binning index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_depth(6).build();
+    /// assert_eq!(index.depth(), 6);
+    /// ```
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the auxiliary data.
+    ///
+    /// The format of the auxiliary data is defined by the indexed file format, e.g., a tabix
+    /// header for tab-delimited files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_aux(b"ndls".to_vec()).build();
+    /// assert_eq!(index.aux(), b"ndls");
+    /// ```
+    pub fn aux(&self) -> &[u8] {
+        &self.aux
+    }
+
+    /// Returns a list of indexed reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::default();
+    /// assert!(index.reference_sequences().is_empty());
+    /// ```
+    pub fn reference_sequences(&self) -> &[ReferenceSequence] {
+        &self.reference_sequences
+    }
+
+    /// Returns the number of unplaced, unmapped records in the associated file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_unplaced_unmapped_record_count(21)
+    ///     .build();
+    ///
+    /// assert_eq!(index.unplaced_unmapped_record_count(), Some(21));
+    /// ```
+    pub fn unplaced_unmapped_record_count(&self) -> Option<u64> {
+        self.unplaced_unmapped_record_count
+    }
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+impl BinningIndex for Index {
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>> {
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let max_position = reference_sequence::max_position(self.min_shift, self.depth);
+
+        if start < 1 || start > end || end > max_position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid interval: expected 1 <= start <= end <= {}, got [{}, {}]",
+                    max_position, start, end
+                ),
+            ));
+        }
+
+        let query_bins = reference_sequence.query(self.min_shift, self.depth, start, end);
+
+        let chunks: Vec<_> = query_bins
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(self.min_shift, self.depth, start);
+
+        Ok(optimize_chunks(&chunks, min_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::{reference_sequence::Bin, *};
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let bins = vec![
+            Bin::new(
+                0,
+                bgzf::VirtualPosition::from(0),
+                vec![Chunk::new(
+                    bgzf::VirtualPosition::from(5),
+                    bgzf::VirtualPosition::from(8),
+                )],
+            ),
+            Bin::new(
+                4681,
+                bgzf::VirtualPosition::from(13),
+                vec![Chunk::new(
+                    bgzf::VirtualPosition::from(13),
+                    bgzf::VirtualPosition::from(21),
+                )],
+            ),
+        ];
+
+        let index = Index::builder()
+            .set_reference_sequences(vec![ReferenceSequence::new(bins, None)])
+            .build();
+
+        // The chunk in bin 0 ends before the min offset of bin 4681.
+        assert_eq!(
+            index.query(0, 8, 13)?,
+            [Chunk::new(
+                bgzf::VirtualPosition::from(13),
+                bgzf::VirtualPosition::from(21),
+            )]
+        );
+
+        assert!(index.query(1, 8, 13).is_err());
+        assert!(index.query(0, 0, 13).is_err());
+        assert!(index.query(0, 8, 1 << 30).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use super::{Index, ReferenceSequence, DEFAULT_DEPTH, DEFAULT_MIN_SHIFT};
+
+/// A CSI builder.
+#[derive(Debug)]
+pub struct Builder {
+    min_shift: u8,
+    depth: u8,
+    aux: Vec<u8>,
+    reference_sequences: Vec<ReferenceSequence>,
+    unplaced_unmapped_record_count: Option<u64>,
+}
+
+impl Builder {
+    /// Sets a CSI min shift.
+    ///
+    /// By default, this is 14.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_min_shift(16).build();
+    /// assert_eq!(index.min_shift(), 16);
+    /// ```
+    pub fn set_min_shift(mut self, min_shift: u8) -> Self {
+        self.min_shift = min_shift;
+        self
+    }
+
+    /// Sets a CSI depth.
+    ///
+    /// By default, this is 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_depth(6).build();
+    /// assert_eq!(index.depth(), 6);
+    /// ```
+    pub fn set_depth(mut self, depth: u8) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets CSI auxiliary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_aux(b"ndls".to_vec()).build();
+    /// assert_eq!(index.aux(), b"ndls");
+    /// ```
+    pub fn set_aux(mut self, aux: Vec<u8>) -> Self {
+        self.aux = aux;
+        self
+    }
+
+    /// Sets CSI reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, index::ReferenceSequence};
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), None)])
+    ///     .build();
+    ///
+    /// assert_eq!(index.reference_sequences().len(), 1);
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<ReferenceSequence>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets a CSI unplaced, unmapped record count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_unplaced_unmapped_record_count(21)
+    ///     .build();
+    ///
+    /// assert_eq!(index.unplaced_unmapped_record_count(), Some(21));
+    /// ```
+    pub fn set_unplaced_unmapped_record_count(
+        mut self,
+        unplaced_unmapped_record_count: u64,
+    ) -> Self {
+        self.unplaced_unmapped_record_count = Some(unplaced_unmapped_record_count);
+        self
+    }
+
+    /// Builds a CSI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().build();
+    /// ```
+    pub fn build(self) -> Index {
+        Index {
+            min_shift: self.min_shift,
+            depth: self.depth,
+            aux: self.aux,
+            reference_sequences: self.reference_sequences,
+            unplaced_unmapped_record_count: self.unplaced_unmapped_record_count,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            min_shift: DEFAULT_MIN_SHIFT,
+            depth: DEFAULT_DEPTH,
+            aux: Vec::new(),
+            reference_sequences: Vec::new(),
+            unplaced_unmapped_record_count: None,
+        }
+    }
+}
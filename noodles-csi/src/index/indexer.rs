@@ -0,0 +1,360 @@
+use std::{cmp, collections::BTreeMap, io};
+
+use noodles_bgzf as bgzf;
+
+use super::{
+    reference_sequence::{self, bin::Chunk, Bin, Metadata},
+    Index, ReferenceSequence, DEFAULT_DEPTH, DEFAULT_MIN_SHIFT,
+};
+
+/// A CSI indexer.
+///
+/// An indexer builds a CSI from the positions of records in a coordinate-sorted file. Records are
+/// expected to be added in the same order as they appear in the file.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+///
+/// let mut indexer = csi::index::Indexer::new(14, 6);
+///
+/// let chunk = Chunk::new(bgzf::VirtualPosition::from(0), bgzf::VirtualPosition::from(55));
+/// indexer.add_record(Some(0), 1 << 30, (1 << 30) + 100, true, chunk)?;
+///
+/// let index = indexer.build(1);
+/// assert_eq!(index.depth(), 6);
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Indexer {
+    min_shift: u8,
+    depth: u8,
+    current_reference_sequence_id: Option<usize>,
+    current_start: u64,
+    reference_sequence_builders: Vec<ReferenceSequenceBuilder>,
+    unplaced_unmapped_record_count: u64,
+}
+
+impl Indexer {
+    /// Creates a CSI indexer with the given min shift and depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let indexer = csi::index::Indexer::new(14, 6);
+    /// ```
+    pub fn new(min_shift: u8, depth: u8) -> Self {
+        Self {
+            min_shift,
+            depth,
+            current_reference_sequence_id: None,
+            current_start: 0,
+            reference_sequence_builders: Vec::new(),
+            unplaced_unmapped_record_count: 0,
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// A record without a reference sequence ID is counted as unplaced and unmapped. Otherwise,
+    /// the start and end positions are 1-based, inclusive, and `chunk` is the range of virtual
+    /// positions of the record in the associated file.
+    ///
+    /// This returns an error if the record is out of order or its interval cannot be represented
+    /// by the binning scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+    ///
+    /// let mut indexer = csi::index::Indexer::default();
+    ///
+    /// let chunk = Chunk::new(bgzf::VirtualPosition::from(0), bgzf::VirtualPosition::from(55));
+    /// indexer.add_record(Some(0), 8, 13, true, chunk)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn add_record(
+        &mut self,
+        reference_sequence_id: Option<usize>,
+        start: u64,
+        end: u64,
+        is_mapped: bool,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let reference_sequence_id = match reference_sequence_id {
+            Some(id) => id,
+            None => {
+                self.unplaced_unmapped_record_count += 1;
+                return Ok(());
+            }
+        };
+
+        let max_position = reference_sequence::max_position(self.min_shift, self.depth);
+
+        if start < 1 || start > end || end > max_position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid record interval: expected 1 <= start <= end <= {}, got [{}, {}]",
+                    max_position, start, end
+                ),
+            ));
+        }
+
+        match self.current_reference_sequence_id {
+            Some(id) if reference_sequence_id < id => {
+                return Err(unsorted_error());
+            }
+            Some(id) if reference_sequence_id == id && start < self.current_start => {
+                return Err(unsorted_error());
+            }
+            _ => {}
+        }
+
+        while self.reference_sequence_builders.len() <= reference_sequence_id {
+            self.reference_sequence_builders
+                .push(ReferenceSequenceBuilder::default());
+        }
+
+        self.current_reference_sequence_id = Some(reference_sequence_id);
+        self.current_start = start;
+
+        let builder = &mut self.reference_sequence_builders[reference_sequence_id];
+        builder.add_record(self.min_shift, self.depth, start, end, is_mapped, chunk);
+
+        Ok(())
+    }
+
+    /// Builds a CSI.
+    ///
+    /// `reference_sequence_count` is the number of reference sequences in the associated file.
+    /// Reference sequences without records are included as empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let indexer = csi::index::Indexer::default();
+    /// let index = indexer.build(2);
+    /// assert_eq!(index.reference_sequences().len(), 2);
+    /// ```
+    pub fn build(mut self, reference_sequence_count: usize) -> Index {
+        while self.reference_sequence_builders.len() < reference_sequence_count {
+            self.reference_sequence_builders
+                .push(ReferenceSequenceBuilder::default());
+        }
+
+        let min_shift = self.min_shift;
+        let depth = self.depth;
+
+        let reference_sequences = self
+            .reference_sequence_builders
+            .into_iter()
+            .map(|builder| builder.build(min_shift, depth))
+            .collect();
+
+        Index::builder()
+            .set_min_shift(min_shift)
+            .set_depth(depth)
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(self.unplaced_unmapped_record_count)
+            .build()
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_SHIFT, DEFAULT_DEPTH)
+    }
+}
+
+fn unsorted_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "records are not coordinate sorted",
+    )
+}
+
+#[derive(Debug)]
+struct ReferenceSequenceBuilder {
+    bins: BTreeMap<u32, Vec<Chunk>>,
+    // The smallest start virtual position of records overlapping each window of size
+    // 2^min_shift.
+    linear_index: Vec<Option<bgzf::VirtualPosition>>,
+    start_position: bgzf::VirtualPosition,
+    end_position: bgzf::VirtualPosition,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl ReferenceSequenceBuilder {
+    fn add_record(
+        &mut self,
+        min_shift: u8,
+        depth: u8,
+        start: u64,
+        end: u64,
+        is_mapped: bool,
+        chunk: Chunk,
+    ) {
+        if is_mapped {
+            self.mapped_record_count += 1;
+        } else {
+            self.unmapped_record_count += 1;
+        }
+
+        self.start_position = cmp::min(self.start_position, chunk.start());
+        self.end_position = cmp::max(self.end_position, chunk.end());
+
+        let id = reference_sequence::reg2bin(start - 1, end, min_shift, depth);
+        let chunks = self.bins.entry(id).or_default();
+
+        match chunks.last_mut() {
+            Some(last_chunk) if chunk.start() <= last_chunk.end() => {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+            }
+            _ => chunks.push(chunk),
+        }
+
+        let start_window = ((start - 1) >> min_shift) as usize;
+        let end_window = ((end - 1) >> min_shift) as usize;
+
+        if self.linear_index.len() <= end_window {
+            self.linear_index.resize(end_window + 1, None);
+        }
+
+        for offset in &mut self.linear_index[start_window..=end_window] {
+            if offset.is_none() {
+                *offset = Some(chunk.start());
+            }
+        }
+    }
+
+    fn build(self, min_shift: u8, depth: u8) -> ReferenceSequence {
+        if self.bins.is_empty() {
+            return ReferenceSequence::default();
+        }
+
+        let linear_index = self.linear_index;
+
+        let bins = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| {
+                let (bin_start, _) = reference_sequence::bin_range(id, min_shift, depth);
+                let window = (bin_start >> min_shift) as usize;
+
+                let loffset = linear_index
+                    .get(window)
+                    .copied()
+                    .flatten()
+                    .unwrap_or_default();
+
+                Bin::new(id, loffset, chunks)
+            })
+            .collect();
+
+        let metadata = Metadata::new(
+            self.start_position,
+            self.end_position,
+            self.mapped_record_count,
+            self.unmapped_record_count,
+        );
+
+        ReferenceSequence::new(bins, Some(metadata))
+    }
+}
+
+impl Default for ReferenceSequenceBuilder {
+    fn default() -> Self {
+        Self {
+            bins: BTreeMap::new(),
+            linear_index: Vec::new(),
+            start_position: bgzf::VirtualPosition::max(),
+            end_position: bgzf::VirtualPosition::default(),
+            mapped_record_count: 0,
+            unmapped_record_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BinningIndex;
+
+    use super::*;
+
+    fn chunk(start: u64, end: u64) -> Chunk {
+        Chunk::new(
+            bgzf::VirtualPosition::from(start),
+            bgzf::VirtualPosition::from(end),
+        )
+    }
+
+    #[test]
+    fn test_add_record() -> io::Result<()> {
+        let mut indexer = Indexer::default();
+
+        indexer.add_record(Some(0), 8, 13, true, chunk(0, 10))?;
+        indexer.add_record(Some(0), 16384, 16390, true, chunk(10, 20))?;
+        indexer.add_record(Some(0), 20000, 20010, false, chunk(20, 30))?;
+        indexer.add_record(None, 0, 0, false, chunk(30, 40))?;
+
+        assert!(indexer
+            .add_record(Some(0), 8, 13, true, chunk(40, 50))
+            .is_err());
+
+        let index = indexer.build(2);
+
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        let reference_sequence = &index.reference_sequences()[0];
+
+        let ids: Vec<_> = reference_sequence.bins().iter().map(|b| b.id()).collect();
+        assert_eq!(ids, [585, 4681, 4682]);
+
+        assert_eq!(
+            reference_sequence.metadata(),
+            Some(&Metadata::new(
+                bgzf::VirtualPosition::from(0),
+                bgzf::VirtualPosition::from(30),
+                2,
+                1
+            ))
+        );
+
+        assert!(index.reference_sequences()[1].bins().is_empty());
+
+        assert_eq!(index.query(0, 20005, 20005)?, [chunk(10, 30)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_with_large_position() -> io::Result<()> {
+        let mut indexer = Indexer::new(14, 6);
+        indexer.add_record(Some(0), 1 << 30, (1 << 30) + 100, true, chunk(0, 10))?;
+
+        let index = indexer.build(1);
+        assert_eq!(
+            index.query(0, (1 << 30) + 1, (1 << 30) + 2)?,
+            [chunk(0, 10)]
+        );
+
+        let mut indexer = Indexer::default();
+        assert!(indexer
+            .add_record(Some(0), 1 << 30, (1 << 30) + 100, true, chunk(0, 10))
+            .is_err());
+
+        Ok(())
+    }
+}
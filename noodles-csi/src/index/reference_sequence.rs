@@ -0,0 +1,245 @@
+//! CSI reference sequence and fields.
+
+pub mod bin;
+pub mod metadata;
+
+pub use self::{bin::Bin, metadata::Metadata};
+
+use noodles_bgzf as bgzf;
+
+/// A CSI reference sequence.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferenceSequence {
+    bins: Vec<Bin>,
+    metadata: Option<Metadata>,
+}
+
+impl ReferenceSequence {
+    /// Creates a CSI reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// ```
+    pub fn new(bins: Vec<Bin>, metadata: Option<Metadata>) -> Self {
+        Self { bins, metadata }
+    }
+
+    /// Returns the list of bins in the reference sequence.
+    ///
+    /// This list does not include the metadata pseudo-bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// assert!(reference_sequence.bins().is_empty());
+    /// ```
+    pub fn bins(&self) -> &[Bin] {
+        &self.bins
+    }
+
+    /// Returns the optional metadata for the reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// assert!(reference_sequence.metadata().is_none());
+    /// ```
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns a list of bins in this reference sequence that intersect the given range.
+    ///
+    /// The start and end positions are 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::{reference_sequence::Bin, ReferenceSequence};
+    ///
+    /// let bins = vec![
+    ///     Bin::new(0, bgzf::VirtualPosition::default(), Vec::new()),
+    ///     Bin::new(4681, bgzf::VirtualPosition::default(), Vec::new()),
+    ///     Bin::new(4682, bgzf::VirtualPosition::default(), Vec::new()),
+    /// ];
+    /// let reference_sequence = ReferenceSequence::new(bins, None);
+    ///
+    /// let query_bins = reference_sequence.query(14, 5, 8, 13);
+    /// let ids: Vec<_> = query_bins.iter().map(|bin| bin.id()).collect();
+    /// assert_eq!(ids, [0, 4681]);
+    /// ```
+    pub fn query(&self, min_shift: u8, depth: u8, start: u64, end: u64) -> Vec<&Bin> {
+        let (beg, end) = (start - 1, end);
+
+        self.bins()
+            .iter()
+            .filter(|bin| {
+                let (bin_start, bin_end) = bin_range(bin.id(), min_shift, depth);
+                beg < bin_end && bin_start < end
+            })
+            .collect()
+    }
+
+    /// Returns the smallest virtual position of records that may intersect the given start
+    /// position.
+    ///
+    /// This is the `loffset` of the smallest existing bin that contains the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::{reference_sequence::Bin, ReferenceSequence};
+    ///
+    /// let bins = vec![
+    ///     Bin::new(0, bgzf::VirtualPosition::from(8), Vec::new()),
+    ///     Bin::new(4681, bgzf::VirtualPosition::from(13), Vec::new()),
+    /// ];
+    /// let reference_sequence = ReferenceSequence::new(bins, None);
+    ///
+    /// assert_eq!(reference_sequence.min_offset(14, 5, 8), bgzf::VirtualPosition::from(13));
+    /// assert_eq!(reference_sequence.min_offset(14, 5, 17711), bgzf::VirtualPosition::from(8));
+    /// ```
+    pub fn min_offset(&self, min_shift: u8, depth: u8, start: u64) -> bgzf::VirtualPosition {
+        let mut id = reg2bin(start - 1, start, min_shift, depth);
+
+        loop {
+            if let Some(bin) = self.bins.iter().find(|b| b.id() == id) {
+                return bin.loffset();
+            }
+
+            if id == 0 {
+                break;
+            }
+
+            id = parent_id(id);
+        }
+
+        bgzf::VirtualPosition::default()
+    }
+}
+
+/// Returns the ID of the metadata pseudo-bin for the given depth.
+///
+/// This is one more than the maximum bin ID.
+pub(crate) fn metadata_bin_id(depth: u8) -> u32 {
+    bin_limit(depth) + 1
+}
+
+// § 3 Coordinate Sorted Index (CSI) format: the number of bins (2020-07-19)
+fn bin_limit(depth: u8) -> u32 {
+    ((1 << ((u32::from(depth) + 1) * 3)) - 1) / 7
+}
+
+// Returns the ID of the first bin in the given level.
+fn level_offset(level: u8) -> u32 {
+    ((1 << (u32::from(level) * 3)) - 1) / 7
+}
+
+fn parent_id(id: u32) -> u32 {
+    (id - 1) >> 3
+}
+
+/// Returns the maximum position (exclusive) that can be indexed in the binning scheme.
+pub(crate) fn max_position(min_shift: u8, depth: u8) -> u64 {
+    1 << (u32::from(min_shift) + 3 * u32::from(depth))
+}
+
+// Returns the 0-based, half-open range of positions covered by the given bin.
+pub(crate) fn bin_range(id: u32, min_shift: u8, depth: u8) -> (u64, u64) {
+    let mut level = 0;
+
+    while level < depth && id >= level_offset(level + 1) {
+        level += 1;
+    }
+
+    let shift = u32::from(min_shift) + 3 * u32::from(depth - level);
+    let start = u64::from(id - level_offset(level)) << shift;
+    let end = start + (1 << shift);
+
+    (start, end)
+}
+
+/// Calculates the bin that contains the given 0-based, half-open range.
+///
+/// § 3 Coordinate Sorted Index (CSI) format: `reg2bin` (2020-07-19)
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi::index::reference_sequence::reg2bin;
+/// assert_eq!(reg2bin(7, 13, 14, 5), 4681);
+/// assert_eq!(reg2bin(0, 1 << 29, 14, 5), 0);
+/// ```
+pub fn reg2bin(beg: u64, end: u64, min_shift: u8, depth: u8) -> u32 {
+    let end = end - 1;
+    let mut shift = u32::from(min_shift);
+
+    for level in (1..=depth).rev() {
+        if beg >> shift == end >> shift {
+            return level_offset(level) + (beg >> shift) as u32;
+        }
+
+        shift += 3;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_bin_id() {
+        assert_eq!(metadata_bin_id(5), 37450);
+        assert_eq!(metadata_bin_id(6), 299594);
+    }
+
+    #[test]
+    fn test_reg2bin() {
+        assert_eq!(reg2bin(7, 13, 14, 5), 4681);
+        assert_eq!(reg2bin(63245985, 63245986, 14, 5), 8541);
+        assert_eq!(reg2bin(0, 1 << 29, 14, 5), 0);
+        assert_eq!(reg2bin(16383, 16385, 14, 5), 585);
+
+        // depth 6 allows positions >= 2^29.
+        assert_eq!(reg2bin(1 << 30, (1 << 30) + 1, 14, 6), 37449 + (1 << 16));
+    }
+
+    #[test]
+    fn test_bin_range() {
+        assert_eq!(bin_range(0, 14, 5), (0, 1 << 29));
+        assert_eq!(bin_range(1, 14, 5), (0, 1 << 26));
+        assert_eq!(bin_range(8, 14, 5), (7 << 26, 8 << 26));
+        assert_eq!(bin_range(4681, 14, 5), (0, 1 << 14));
+        assert_eq!(bin_range(8541, 14, 5), (3860 << 14, 3861 << 14));
+    }
+
+    #[test]
+    fn test_query() {
+        let bins = vec![
+            Bin::new(0, bgzf::VirtualPosition::default(), Vec::new()),
+            Bin::new(1, bgzf::VirtualPosition::default(), Vec::new()),
+            Bin::new(16, bgzf::VirtualPosition::default(), Vec::new()),
+            Bin::new(8541, bgzf::VirtualPosition::default(), Vec::new()),
+        ];
+        let reference_sequence = ReferenceSequence::new(bins, None);
+
+        let ids: Vec<_> = reference_sequence
+            .query(14, 5, 63245986, 63245986)
+            .iter()
+            .map(|bin| bin.id())
+            .collect();
+
+        assert_eq!(ids, [0, 1, 16, 8541]);
+    }
+}
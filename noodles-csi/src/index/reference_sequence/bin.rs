@@ -0,0 +1,79 @@
+//! CSI reference sequence bin and fields.
+
+mod chunk;
+
+pub use self::chunk::Chunk;
+
+use noodles_bgzf as bgzf;
+
+/// A CSI reference sequence bin.
+///
+/// Unlike a BAI bin, a CSI bin stores the smallest virtual position of records that overlap the
+/// start of the bin (`loffset`), which replaces the linear index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bin {
+    id: u32,
+    loffset: bgzf::VirtualPosition,
+    chunks: Vec<Chunk>,
+}
+
+impl Bin {
+    /// Creates a CSI reference sequence bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// ```
+    pub fn new(id: u32, loffset: bgzf::VirtualPosition, chunks: Vec<Chunk>) -> Self {
+        Self {
+            id,
+            loffset,
+            chunks,
+        }
+    }
+
+    /// Returns the bin ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// assert_eq!(bin.id(), 10946);
+    /// ```
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the smallest virtual position of records that overlap the start of the bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::from(233), Vec::new());
+    /// assert_eq!(bin.loffset(), bgzf::VirtualPosition::from(233));
+    /// ```
+    pub fn loffset(&self) -> bgzf::VirtualPosition {
+        self.loffset
+    }
+
+    /// Returns the list of chunks in the bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// assert!(bin.chunks().is_empty());
+    /// ```
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+}
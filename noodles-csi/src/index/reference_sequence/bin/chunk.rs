@@ -0,0 +1,70 @@
+use noodles_bgzf as bgzf;
+
+/// A CSI reference sequence bin chunk.
+///
+/// A chunk is a range of virtual positions in the associated file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Chunk {
+    chunk_beg: bgzf::VirtualPosition,
+    chunk_end: bgzf::VirtualPosition,
+}
+
+impl Chunk {
+    /// Creates a CSI reference sequence bin chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    ///
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(13),
+    ///     bgzf::VirtualPosition::from(21),
+    /// );
+    /// ```
+    pub fn new(start: bgzf::VirtualPosition, end: bgzf::VirtualPosition) -> Self {
+        Self {
+            chunk_beg: start,
+            chunk_end: end,
+        }
+    }
+
+    /// Returns the start virtual position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    ///
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(13),
+    ///     bgzf::VirtualPosition::from(21),
+    /// );
+    ///
+    /// assert_eq!(chunk.start(), bgzf::VirtualPosition::from(13));
+    /// ```
+    pub fn start(&self) -> bgzf::VirtualPosition {
+        self.chunk_beg
+    }
+
+    /// Returns the end virtual position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    ///
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(13),
+    ///     bgzf::VirtualPosition::from(21),
+    /// );
+    ///
+    /// assert_eq!(chunk.end(), bgzf::VirtualPosition::from(21));
+    /// ```
+    pub fn end(&self) -> bgzf::VirtualPosition {
+        self.chunk_end
+    }
+}
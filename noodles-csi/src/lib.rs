@@ -0,0 +1,55 @@
+#![deny(missing_docs)]
+
+//! **noodles-csi** handles the reading and writing of the coordinate-sorted index (CSI) format.
+//!
+//! A coordinate-sorted index (CSI) is a generalization of the BAM index (BAI) and tabix index
+//! (TBI) binning schemes. The size of the smallest bin (`min_shift`) and the number of levels in
+//! the binning scheme (`depth`) are configurable, which allows indexing reference sequences longer
+//! than the 2^29 (512 Mbp) limit of BAI and TBI.
+//!
+//! A CSI can be used in place of a BAI or TBI, e.g., when querying a BAM file.
+//!
+//! # Examples
+//!
+//! ## Read a CSI
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_csi as csi;
+//! let index = csi::read("sample.bam.csi")?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+pub mod binning_index;
+pub mod index;
+pub mod query;
+mod reader;
+mod writer;
+
+pub use self::{binning_index::BinningIndex, index::Index, reader::Reader, writer::Writer};
+
+use std::{fs::File, io, path::Path};
+
+static MAGIC_NUMBER: &[u8] = b"CSI\x01";
+
+/// Reads the entire contents of a coordinate-sorted index (CSI).
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_csi as csi;
+/// let index = csi::read("sample.bam.csi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_header()?;
+    reader.read_index()
+}
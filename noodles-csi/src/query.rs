@@ -0,0 +1,5 @@
+//! Binning index query iterators.
+
+mod lines;
+
+pub use self::lines::Lines;
@@ -0,0 +1,134 @@
+use std::{
+    io::{self, BufRead, Read, Seek},
+    str,
+};
+
+use noodles_bgzf as bgzf;
+
+use crate::index::reference_sequence::bin::Chunk;
+
+const LINE_FEED: u8 = b'\n';
+const CARRIAGE_RETURN: u8 = b'\r';
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    End,
+}
+
+/// An iterator over the raw lines of a bgzipped file that are in a list of chunks.
+///
+/// Each line is returned without its trailing newline. The chunks typically come from querying a
+/// binning index, and lines are not filtered by region. Format readers use this to parse and
+/// filter records of a region query.
+pub struct Lines<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut bgzf::Reader<R>,
+    chunks: Vec<Chunk>,
+    i: usize,
+    state: State,
+    line_buf: Vec<u8>,
+}
+
+impl<'a, R> Lines<'a, R>
+where
+    R: Read + Seek,
+{
+    /// Creates an iterator over the lines in the given chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    /// use noodles_csi::query::Lines;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"sq0\t7\t13\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(data));
+    /// let chunks = vec![Chunk::new(
+    ///     bgzf::VirtualPosition::from(0),
+    ///     bgzf::VirtualPosition::from(9),
+    /// )];
+    ///
+    /// let lines: Vec<_> = Lines::new(&mut reader, chunks).collect::<io::Result<_>>()?;
+    /// assert_eq!(lines, ["sq0\t7\t13"]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(reader: &'a mut bgzf::Reader<R>, chunks: Vec<Chunk>) -> Self {
+        Self {
+            reader,
+            chunks,
+            i: 0,
+            state: State::Seek,
+            line_buf: Vec::new(),
+        }
+    }
+
+    fn next_chunk(&mut self) -> io::Result<Option<bgzf::VirtualPosition>> {
+        if self.i >= self.chunks.len() {
+            return Ok(None);
+        }
+
+        let chunk = self.chunks[self.i];
+        self.reader.seek(chunk.start())?;
+
+        self.i += 1;
+
+        Ok(Some(chunk.end()))
+    }
+
+    fn read_line(&mut self) -> io::Result<usize> {
+        self.line_buf.clear();
+        self.reader.read_until(b'\n', &mut self.line_buf)
+    }
+}
+
+impl<'a, R> Iterator for Lines<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.next_chunk() {
+                        Ok(Some(chunk_end)) => State::Read(chunk_end),
+                        Ok(None) => State::End,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                State::Read(chunk_end) => match self.read_line() {
+                    Ok(0) => self.state = State::Seek,
+                    Ok(_) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        let line = trim_newline(&self.line_buf);
+
+                        return Some(
+                            str::from_utf8(line)
+                                .map(|s| s.into())
+                                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                        );
+                    }
+                    Err(e) => return Some(Err(e)),
+                },
+                State::End => return None,
+            }
+        }
+    }
+}
+
+fn trim_newline(buf: &[u8]) -> &[u8] {
+    let buf = buf.strip_suffix(&[LINE_FEED]).unwrap_or(buf);
+    buf.strip_suffix(&[CARRIAGE_RETURN]).unwrap_or(buf)
+}
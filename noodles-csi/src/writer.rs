@@ -0,0 +1,256 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use noodles_bgzf as bgzf;
+
+use super::{
+    index::{
+        reference_sequence::{self, bin::Chunk, Metadata},
+        ReferenceSequence,
+    },
+    Index, MAGIC_NUMBER,
+};
+
+/// A CSI writer.
+///
+/// The CSI format is BGZF-compressed, so the output of this writer is compressed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_csi as csi;
+///
+/// let index = csi::Index::default();
+///
+/// let mut writer = File::create("sample.bam.csi").map(csi::Writer::new)?;
+/// writer.write_header()?;
+/// writer.write_index(&index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: bgzf::Writer<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a CSI writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let writer = csi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: bgzf::Writer::new(inner),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let writer = csi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Attempts to finish the output stream.
+    ///
+    /// This is typically only manually called if the underlying stream is needed before the writer
+    /// is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    /// let mut writer = csi::Writer::new(Vec::new());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.try_finish()
+    }
+
+    /// Writes a CSI header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    /// let mut writer = csi::Writer::new(Vec::new());
+    /// writer.write_header()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_header(&mut self) -> io::Result<()> {
+        self.inner.write_all(MAGIC_NUMBER)
+    }
+
+    /// Writes a CSI index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::default();
+    ///
+    /// let mut writer = csi::Writer::new(Vec::new());
+    /// writer.write_header()?;
+    /// writer.write_index(&index)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        self.inner
+            .write_i32::<LittleEndian>(i32::from(index.min_shift()))?;
+        self.inner
+            .write_i32::<LittleEndian>(i32::from(index.depth()))?;
+
+        let l_aux = index.aux().len() as i32;
+        self.inner.write_i32::<LittleEndian>(l_aux)?;
+        self.inner.write_all(index.aux())?;
+
+        let n_ref = index.reference_sequences().len() as i32;
+        self.inner.write_i32::<LittleEndian>(n_ref)?;
+
+        for reference_sequence in index.reference_sequences() {
+            write_reference_sequence(&mut self.inner, index.depth(), reference_sequence)?;
+        }
+
+        if let Some(n_no_coor) = index.unplaced_unmapped_record_count() {
+            self.inner.write_u64::<LittleEndian>(n_no_coor)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_reference_sequence<W>(
+    writer: &mut W,
+    depth: u8,
+    reference_sequence: &ReferenceSequence,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut n_bin = reference_sequence.bins().len() as i32;
+
+    if reference_sequence.metadata().is_some() {
+        n_bin += 1;
+    }
+
+    writer.write_i32::<LittleEndian>(n_bin)?;
+
+    for bin in reference_sequence.bins() {
+        write_bin(writer, bin.id(), bin.loffset(), bin.chunks())?;
+    }
+
+    if let Some(metadata) = reference_sequence.metadata() {
+        let id = reference_sequence::metadata_bin_id(depth);
+        let chunks = metadata_to_chunks(metadata);
+        write_bin(writer, id, bgzf::VirtualPosition::default(), &chunks)?;
+    }
+
+    Ok(())
+}
+
+fn write_bin<W>(
+    writer: &mut W,
+    id: u32,
+    loffset: bgzf::VirtualPosition,
+    chunks: &[Chunk],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u32::<LittleEndian>(id)?;
+    writer.write_u64::<LittleEndian>(u64::from(loffset))?;
+
+    let n_chunk = chunks.len() as i32;
+    writer.write_i32::<LittleEndian>(n_chunk)?;
+
+    for chunk in chunks {
+        writer.write_u64::<LittleEndian>(u64::from(chunk.start()))?;
+        writer.write_u64::<LittleEndian>(u64::from(chunk.end()))?;
+    }
+
+    Ok(())
+}
+
+fn metadata_to_chunks(metadata: &Metadata) -> [Chunk; 2] {
+    [
+        Chunk::new(metadata.start_position(), metadata.end_position()),
+        Chunk::new(
+            bgzf::VirtualPosition::from(metadata.mapped_record_count()),
+            bgzf::VirtualPosition::from(metadata.unmapped_record_count()),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        index::{reference_sequence::Bin, ReferenceSequence},
+        Reader,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let chunks = vec![Chunk::new(
+            bgzf::VirtualPosition::from(509268599425),
+            bgzf::VirtualPosition::from(509268599570),
+        )];
+        let bins = vec![Bin::new(
+            299594 - 2,
+            bgzf::VirtualPosition::from(509268599425),
+            chunks,
+        )];
+        let metadata = Metadata::new(
+            bgzf::VirtualPosition::from(509268599425),
+            bgzf::VirtualPosition::from(509268599570),
+            1,
+            0,
+        );
+        let reference_sequences = vec![
+            ReferenceSequence::new(bins, Some(metadata)),
+            ReferenceSequence::default(),
+        ];
+
+        let index = Index::builder()
+            .set_min_shift(14)
+            .set_depth(6)
+            .set_aux(b"ndls".to_vec())
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(3)
+            .build();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header()?;
+        writer.write_index(&index)?;
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        reader.read_header()?;
+        let actual = reader.read_index()?;
+
+        assert_eq!(actual, index);
+
+        Ok(())
+    }
+}
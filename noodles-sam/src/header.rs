@@ -66,8 +66,9 @@ pub mod program;
 pub mod read_group;
 pub mod record;
 pub mod reference_sequence;
+pub mod validation;
 
-use std::{convert::TryFrom, error, fmt, str::FromStr};
+use std::{collections::HashSet, convert::TryFrom, error, fmt, str::FromStr};
 
 use indexmap::IndexMap;
 
@@ -91,7 +92,7 @@ pub type Programs = IndexMap<String, Program>;
 ///
 /// Records are grouped by their types: header, reference seqeuence, read group, program, and
 /// comment.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Header {
     header: Option<header::Header>,
     reference_sequences: ReferenceSequences,
@@ -296,6 +297,74 @@ impl Header {
         &mut self.programs
     }
 
+    /// Appends a program to each program chain of this SAM header.
+    ///
+    /// A program chain is a list of programs linked by their previous program IDs (`PP`). The
+    /// last program of a chain, i.e., a program that is not the previous program of any other
+    /// program, is a leaf. A new program is added after each leaf, in header order, or once
+    /// without a previous program ID if there are no programs.
+    ///
+    /// The program name (`PN`), version (`VN`), and command line (`CL`) are set to the given
+    /// values. The program ID is the program name or, if it is already used, the program name
+    /// with the first unused numeric suffix, e.g., `noodles.1`, as samtools does.
+    ///
+    /// This returns the IDs of the added programs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let mut header: sam::Header = "\
+    /// @PG\tID:noodles\tPN:noodles
+    /// @PG\tID:pg0\tPP:noodles
+    /// "
+    /// .parse()?;
+    ///
+    /// let ids = header.append_program("noodles", "0.1.0", "noodles view sample.sam");
+    /// assert_eq!(ids, ["noodles.1"]);
+    ///
+    /// let program = &header.programs()["noodles.1"];
+    /// assert_eq!(program.name(), Some("noodles"));
+    /// assert_eq!(program.version(), Some("0.1.0"));
+    /// assert_eq!(program.command_line(), Some("noodles view sample.sam"));
+    /// assert_eq!(program.previous_id(), Some("pg0"));
+    /// # Ok::<(), sam::header::ParseError>(())
+    /// ```
+    pub fn append_program(&mut self, name: &str, version: &str, command_line: &str) -> Vec<String> {
+        let leaves: Vec<String> = program_chain_leaves(&self.programs)
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let previous_ids: Vec<_> = if leaves.is_empty() {
+            vec![None]
+        } else {
+            leaves.into_iter().map(Some).collect()
+        };
+
+        let mut ids = Vec::with_capacity(previous_ids.len());
+
+        for previous_id in previous_ids {
+            let id = unique_id(&self.programs, name);
+
+            let mut builder = Program::builder()
+                .set_id(id.clone())
+                .set_name(name)
+                .set_version(version)
+                .set_command_line(command_line);
+
+            if let Some(previous_id) = previous_id {
+                builder = builder.set_previous_id(previous_id);
+            }
+
+            self.programs.insert(id.clone(), builder.build());
+            ids.push(id);
+        }
+
+        ids
+    }
+
     /// Returns the SAM header comments.
     ///
     /// # Examples
@@ -347,6 +416,62 @@ impl Header {
             && self.programs.is_empty()
             && self.comments.is_empty()
     }
+
+    /// Checks the invariants between records of this SAM header.
+    ///
+    /// This checks that the format version is valid; the group and subsort orders agree with the
+    /// sort order; the reference sequence lengths are in range; and the previous program of each
+    /// program exists and does not form a cycle. Diagnostics are returned in that order.
+    ///
+    /// Duplicate IDs are merged when a header is parsed. Use [`validation::validate`] to check a
+    /// raw header for duplicates.
+    ///
+    /// [`validation::validate`]: validation/fn.validate.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::validation::Diagnostic};
+    ///
+    /// let header: sam::Header = "@HD\tVN:1.6\n@PG\tID:pg0\tPP:pg1\n".parse()?;
+    ///
+    /// assert_eq!(
+    ///     header.validate(),
+    ///     [Diagnostic::MissingPreviousProgram {
+    ///         id: String::from("pg0"),
+    ///         previous_id: String::from("pg1"),
+    ///     }]
+    /// );
+    /// # Ok::<(), sam::header::ParseError>(())
+    /// ```
+    pub fn validate(&self) -> Vec<validation::Diagnostic> {
+        validation::validate_header(self)
+    }
+
+    /// Checks a SAM record against this SAM header.
+    ///
+    /// This checks that the reference sequence names (`RNAME` and `RNEXT`) are in the reference
+    /// sequences and that the read group (`RG`) and program (`PG`) data fields, if present, are in
+    /// the read groups and programs, respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::validation::Diagnostic};
+    ///
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\n@RG\tID:rg0\n".parse()?;
+    ///
+    /// let record: sam::Record = "r0\t0\tsq0\t1\t255\t4M\t*\t0\t0\t*\t*\tRG:Z:rg1".parse()?;
+    ///
+    /// assert_eq!(
+    ///     header.validate_record(&record),
+    ///     [Diagnostic::UnknownReadGroup(String::from("rg1"))]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_record(&self, record: &crate::Record) -> Vec<validation::Diagnostic> {
+        validation::validate_record(self, record)
+    }
 }
 
 impl fmt::Display for Header {
@@ -497,6 +622,47 @@ impl FromStr for Header {
     }
 }
 
+// Returns the IDs of the programs that are not the previous program of any other program.
+fn program_chain_leaves(programs: &Programs) -> Vec<&str> {
+    let previous_ids: HashSet<_> = programs
+        .values()
+        .filter_map(|program| program.previous_id())
+        .collect();
+
+    programs
+        .keys()
+        .map(|id| id.as_str())
+        .filter(|id| !previous_ids.contains(id))
+        .collect()
+}
+
+/// Returns an ID that is not used by the given header records.
+///
+/// This is the given ID if it is unused; otherwise, `<id>.<n>` with the smallest `n` >= 1 that is
+/// unused. It is used to resolve ID collisions, e.g., when adding programs or merging headers.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{self, Program, Programs};
+///
+/// let mut programs = Programs::default();
+/// assert_eq!(header::unique_id(&programs, "noodles"), "noodles");
+///
+/// programs.insert(String::from("noodles"), Program::new(String::from("noodles")));
+/// assert_eq!(header::unique_id(&programs, "noodles"), "noodles.1");
+/// ```
+pub fn unique_id<V>(records: &IndexMap<String, V>, id: &str) -> String {
+    if !records.contains_key(id) {
+        return id.into();
+    }
+
+    (1..)
+        .map(|n| format!("{}.{}", id, n))
+        .find(|new_id| !records.contains_key(new_id))
+        .expect("no unused ID")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,6 +697,35 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_append_program() -> Result<(), ParseError> {
+        let mut header = Header::default();
+        assert_eq!(
+            header.append_program("noodles", "0.1.0", "noodles"),
+            ["noodles"]
+        );
+        assert_eq!(header.programs()["noodles"].previous_id(), None);
+
+        let mut header: Header = "\
+@PG\tID:noodles
+@PG\tID:pg0\tPP:noodles
+@PG\tID:noodles.1
+@PG\tID:pg1
+"
+        .parse()?;
+
+        let ids = header.append_program("noodles", "0.1.0", "noodles");
+        assert_eq!(ids, ["noodles.2", "noodles.3", "noodles.4"]);
+
+        let previous_ids: Vec<_> = ids
+            .iter()
+            .map(|id| header.programs()[id].previous_id())
+            .collect();
+        assert_eq!(previous_ids, [Some("pg0"), Some("noodles.1"), Some("pg1")]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<(), ParseError> {
         let s = "\
@@ -586,4 +781,16 @@ mod tests {
 
         assert!(s.parse::<Header>().is_err());
     }
+
+    #[test]
+    fn test_unique_id() {
+        let mut read_groups = ReadGroups::default();
+        assert_eq!(unique_id(&read_groups, "rg0"), "rg0");
+
+        read_groups.insert(String::from("rg0"), ReadGroup::new(String::from("rg0")));
+        assert_eq!(unique_id(&read_groups, "rg0"), "rg0.1");
+
+        read_groups.insert(String::from("rg0.1"), ReadGroup::new(String::from("rg0.1")));
+        assert_eq!(unique_id(&read_groups, "rg0"), "rg0.2");
+    }
 }
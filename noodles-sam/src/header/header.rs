@@ -107,6 +107,22 @@ impl Header {
         self.sort_order
     }
 
+    /// Returns a mutable reference to the sort order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::header::{Header, SortOrder};
+    ///
+    /// let mut header = Header::default();
+    /// *header.sort_order_mut() = Some(SortOrder::Coordinate);
+    ///
+    /// assert_eq!(header.sort_order(), Some(SortOrder::Coordinate));
+    /// ```
+    pub fn sort_order_mut(&mut self) -> &mut Option<SortOrder> {
+        &mut self.sort_order
+    }
+
     /// Returns the group order.
     ///
     /// # Examples
@@ -120,6 +136,22 @@ impl Header {
         self.group_order
     }
 
+    /// Returns a mutable reference to the group order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::header::{GroupOrder, Header};
+    ///
+    /// let mut header = Header::default();
+    /// *header.group_order_mut() = Some(GroupOrder::Query);
+    ///
+    /// assert_eq!(header.group_order(), Some(GroupOrder::Query));
+    /// ```
+    pub fn group_order_mut(&mut self) -> &mut Option<GroupOrder> {
+        &mut self.group_order
+    }
+
     /// Returns the subsort order.
     ///
     /// # Examples
@@ -133,6 +165,25 @@ impl Header {
         self.subsort_order.as_ref()
     }
 
+    /// Returns a mutable reference to the subsort order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::header::{Header, SubsortOrder};
+    ///
+    /// let mut header = Header::default();
+    /// *header.subsort_order_mut() = Some(SubsortOrder::QueryName(String::from("natural")));
+    ///
+    /// assert_eq!(
+    ///     header.subsort_order(),
+    ///     Some(&SubsortOrder::QueryName(String::from("natural")))
+    /// );
+    /// ```
+    pub fn subsort_order_mut(&mut self) -> &mut Option<SubsortOrder> {
+        &mut self.subsort_order
+    }
+
     /// Returns the raw fields of the header.
     ///
     /// This includes any field that is not specially handled by the structure itself. For example,
@@ -226,7 +277,7 @@ impl fmt::Display for Header {
             write!(f, "\t{}:{}", Tag::GroupOrder, group_order)?;
         }
 
-        if let Some(subsort_order) = self.group_order {
+        if let Some(subsort_order) = &self.subsort_order {
             write!(f, "\t{}:{}", Tag::SubsortOrder, subsort_order)?;
         }
 
@@ -346,6 +397,18 @@ mod tests {
             .build();
 
         assert_eq!(header.to_string(), "@HD\tVN:1.6\tSO:unknown");
+
+        let header = Header::builder()
+            .set_version("1.6")
+            .set_sort_order(SortOrder::Unsorted)
+            .set_group_order(GroupOrder::Query)
+            .set_subsort_order(SubsortOrder::Unsorted(String::from("template-coordinate")))
+            .build();
+
+        assert_eq!(
+            header.to_string(),
+            "@HD\tVN:1.6\tSO:unsorted\tGO:query\tSS:unsorted:template-coordinate"
+        );
     }
 
     #[test]
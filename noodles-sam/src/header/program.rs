@@ -127,6 +127,23 @@ impl Program {
         self.previous_id.as_deref()
     }
 
+    /// Returns a mutable reference to the previous program ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::Program;
+    ///
+    /// let mut program = Program::new(String::from("pg1"));
+    /// assert!(program.previous_id().is_none());
+    ///
+    /// *program.previous_id_mut() = Some(String::from("pg0"));
+    /// assert_eq!(program.previous_id(), Some("pg0"));
+    /// ```
+    pub fn previous_id_mut(&mut self) -> &mut Option<String> {
+        &mut self.previous_id
+    }
+
     /// Returns the description.
     ///
     /// # Examples
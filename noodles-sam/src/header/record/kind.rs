@@ -3,7 +3,7 @@
 use std::{error, fmt, str::FromStr};
 
 /// A SAM header record kind.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Kind {
     /// Header (`HD`).
     Header,
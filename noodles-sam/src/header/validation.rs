@@ -0,0 +1,485 @@
+//! SAM header validation.
+//!
+//! A parsed [`Header`] holds records that are each valid on their own, but the header as a whole
+//! may still be inconsistent, e.g., a program may refer to a previous program that does not
+//! exist. [`Header::validate`] checks these cross-record invariants, and
+//! [`Header::validate_record`] checks a SAM record against the header.
+//!
+//! Duplicate reference sequence names, read group IDs, and program IDs are merged when a header
+//! is parsed, so they can only be found in the raw header using [`validate`].
+//!
+//! [`Header`]: ../struct.Header.html
+//! [`Header::validate`]: ../struct.Header.html#method.validate
+//! [`Header::validate_record`]: ../struct.Header.html#method.validate_record
+//! [`validate`]: fn.validate.html
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use crate::record::data::{field::Tag, TypeError};
+
+use super::{
+    header::{GroupOrder, SortOrder, SubsortOrder},
+    record, Header, ParseError, Record,
+};
+
+/// A SAM header validation diagnostic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// A reference sequence name (`@SQ SN`) is used more than once.
+    DuplicateReferenceSequenceName(String),
+    /// A read group ID (`@RG ID`) is used more than once.
+    DuplicateReadGroupId(String),
+    /// A program ID (`@PG ID`) is used more than once.
+    DuplicateProgramId(String),
+    /// The format version (`@HD VN`) is not of the form `<major>.<minor>`.
+    InvalidVersion(String),
+    /// The group order (`@HD GO`) contradicts the sort order (`@HD SO`).
+    ConflictingGroupOrder {
+        /// The sort order.
+        sort_order: SortOrder,
+        /// The group order.
+        group_order: GroupOrder,
+    },
+    /// The primary sort order of the subsort order (`@HD SS`) is not the sort order (`@HD SO`).
+    ConflictingSubsortOrder {
+        /// The sort order.
+        sort_order: SortOrder,
+        /// The subsort order.
+        subsort_order: SubsortOrder,
+    },
+    /// A reference sequence length (`@SQ LN`) is not in [1, 2^31 - 1].
+    InvalidReferenceSequenceLength {
+        /// The reference sequence name.
+        name: String,
+        /// The reference sequence length.
+        len: i32,
+    },
+    /// A program refers to a previous program (`@PG PP`) that does not exist.
+    MissingPreviousProgram {
+        /// The program ID.
+        id: String,
+        /// The previous program ID.
+        previous_id: String,
+    },
+    /// A chain of programs (`@PG PP`) loops back on itself at the given program ID.
+    ProgramCycle(String),
+    /// A record reference sequence name (`RNAME`) is not in the reference sequences.
+    UnknownReferenceSequenceName(String),
+    /// A record mate reference sequence name (`RNEXT`) is not in the reference sequences.
+    UnknownMateReferenceSequenceName(String),
+    /// A record read group (`RG`) is not in the read groups.
+    UnknownReadGroup(String),
+    /// A record program (`PG`) is not in the programs.
+    UnknownProgram(String),
+    /// A record read group (`RG`) or program (`PG`) does not have the required type.
+    InvalidDataFieldType(TypeError),
+}
+
+impl error::Error for Diagnostic {}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "duplicate reference sequence name: {}", name)
+            }
+            Self::DuplicateReadGroupId(id) => write!(f, "duplicate read group ID: {}", id),
+            Self::DuplicateProgramId(id) => write!(f, "duplicate program ID: {}", id),
+            Self::InvalidVersion(version) => write!(
+                f,
+                "invalid version: expected <major>.<minor>, got {}",
+                version
+            ),
+            Self::ConflictingGroupOrder {
+                sort_order,
+                group_order,
+            } => write!(
+                f,
+                "group order ({}) conflicts with sort order ({})",
+                group_order, sort_order
+            ),
+            Self::ConflictingSubsortOrder {
+                sort_order,
+                subsort_order,
+            } => write!(
+                f,
+                "subsort order ({}) conflicts with sort order ({})",
+                subsort_order, sort_order
+            ),
+            Self::InvalidReferenceSequenceLength { name, len } => write!(
+                f,
+                "invalid reference sequence length for {}: expected 1..={}, got {}",
+                name,
+                i32::MAX,
+                len
+            ),
+            Self::MissingPreviousProgram { id, previous_id } => write!(
+                f,
+                "previous program of {} does not exist: {}",
+                id, previous_id
+            ),
+            Self::ProgramCycle(id) => write!(f, "program chain has a cycle at {}", id),
+            Self::UnknownReferenceSequenceName(name) => {
+                write!(f, "reference sequence does not exist in header: {}", name)
+            }
+            Self::UnknownMateReferenceSequenceName(name) => write!(
+                f,
+                "mate reference sequence does not exist in header: {}",
+                name
+            ),
+            Self::UnknownReadGroup(id) => write!(f, "read group does not exist in header: {}", id),
+            Self::UnknownProgram(id) => write!(f, "program does not exist in header: {}", id),
+            Self::InvalidDataFieldType(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Parses and validates a raw SAM header.
+///
+/// This returns the diagnostics for duplicate reference sequence names, read group IDs, and
+/// program IDs, in line order, followed by those of [`Header::validate`].
+///
+/// [`Header::validate`]: ../struct.Header.html#method.validate
+///
+/// # Errors
+///
+/// An error is returned if the header fails to parse.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::validation::{self, Diagnostic};
+///
+/// let s = "\
+/// @HD\tVN:1.6
+/// @SQ\tSN:sq0\tLN:8
+/// @SQ\tSN:sq0\tLN:13
+/// ";
+///
+/// let diagnostics = validation::validate(s)?;
+///
+/// assert_eq!(
+///     diagnostics,
+///     [Diagnostic::DuplicateReferenceSequenceName(String::from("sq0"))]
+/// );
+/// # Ok::<(), noodles_sam::header::ParseError>(())
+/// ```
+pub fn validate(s: &str) -> Result<Vec<Diagnostic>, ParseError> {
+    let header: Header = s.parse()?;
+
+    let mut diagnostics = Vec::new();
+    let mut ids: HashMap<record::Kind, HashSet<String>> = HashMap::new();
+
+    for line in s.lines() {
+        let record: Record = line.parse().map_err(ParseError::InvalidRecord)?;
+
+        let (id_tag, duplicate): (_, fn(String) -> Diagnostic) = match record.kind() {
+            record::Kind::ReferenceSequence => ("SN", Diagnostic::DuplicateReferenceSequenceName),
+            record::Kind::ReadGroup => ("ID", Diagnostic::DuplicateReadGroupId),
+            record::Kind::Program => ("ID", Diagnostic::DuplicateProgramId),
+            _ => continue,
+        };
+
+        let id = match record.value() {
+            record::Value::Map(fields) => fields
+                .iter()
+                .find(|(tag, _)| tag == id_tag)
+                .map(|(_, value)| value),
+            record::Value::String(_) => None,
+        };
+
+        if let Some(id) = id {
+            if !ids.entry(record.kind()).or_default().insert(id.clone()) {
+                diagnostics.push(duplicate(id.clone()));
+            }
+        }
+    }
+
+    diagnostics.extend(validate_header(&header));
+
+    Ok(diagnostics)
+}
+
+pub(crate) fn validate_header(header: &Header) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if let Some(hd) = header.header() {
+        if !is_valid_version(hd.version()) {
+            diagnostics.push(Diagnostic::InvalidVersion(hd.version().into()));
+        }
+
+        if let (Some(sort_order), Some(group_order)) = (hd.sort_order(), hd.group_order()) {
+            let is_conflicting = matches!(
+                (sort_order, group_order),
+                (SortOrder::Coordinate, GroupOrder::Query)
+                    | (SortOrder::QueryName, GroupOrder::Reference)
+            );
+
+            if is_conflicting {
+                diagnostics.push(Diagnostic::ConflictingGroupOrder {
+                    sort_order,
+                    group_order,
+                });
+            }
+        }
+
+        if let (Some(sort_order), Some(subsort_order)) = (hd.sort_order(), hd.subsort_order()) {
+            let is_conflicting = !matches!(
+                (sort_order, subsort_order),
+                (SortOrder::Unsorted, SubsortOrder::Unsorted(_))
+                    | (SortOrder::QueryName, SubsortOrder::QueryName(_))
+                    | (SortOrder::Coordinate, SubsortOrder::Coordinate(_))
+            );
+
+            if is_conflicting {
+                diagnostics.push(Diagnostic::ConflictingSubsortOrder {
+                    sort_order,
+                    subsort_order: subsort_order.clone(),
+                });
+            }
+        }
+    }
+
+    for reference_sequence in header.reference_sequences().values() {
+        if reference_sequence.len() < 1 {
+            diagnostics.push(Diagnostic::InvalidReferenceSequenceLength {
+                name: reference_sequence.name().into(),
+                len: reference_sequence.len(),
+            });
+        }
+    }
+
+    diagnostics.extend(validate_programs(header));
+
+    diagnostics
+}
+
+// Walks the chain of previous programs from each program, marking every program visited, so that
+// each missing previous program and each cycle is reported once.
+fn validate_programs(header: &Header) -> Vec<Diagnostic> {
+    let programs = header.programs();
+
+    let mut diagnostics = Vec::new();
+    let mut visited = HashSet::new();
+
+    for id in programs.keys() {
+        let mut chain = HashSet::new();
+        let mut id = id.as_str();
+
+        while visited.insert(id) {
+            chain.insert(id);
+
+            let previous_id = match programs.get(id).and_then(|program| program.previous_id()) {
+                Some(previous_id) => previous_id,
+                None => break,
+            };
+
+            if !programs.contains_key(previous_id) {
+                diagnostics.push(Diagnostic::MissingPreviousProgram {
+                    id: id.into(),
+                    previous_id: previous_id.into(),
+                });
+
+                break;
+            }
+
+            if chain.contains(previous_id) {
+                diagnostics.push(Diagnostic::ProgramCycle(previous_id.into()));
+                break;
+            }
+
+            id = previous_id;
+        }
+    }
+
+    diagnostics
+}
+
+pub(crate) fn validate_record(header: &Header, record: &crate::Record) -> Vec<Diagnostic> {
+    use crate::record::MateReferenceSequenceName;
+
+    let mut diagnostics = Vec::new();
+
+    if let Some(name) = record.reference_sequence_name() {
+        if !header.reference_sequences().contains_key(name.as_str()) {
+            diagnostics.push(Diagnostic::UnknownReferenceSequenceName(name.to_string()));
+        }
+    }
+
+    if let MateReferenceSequenceName::Some(name) = record.mate_reference_sequence_name() {
+        if !header.reference_sequences().contains_key(name) {
+            diagnostics.push(Diagnostic::UnknownMateReferenceSequenceName(name.clone()));
+        }
+    }
+
+    match record.data().get_str(&Tag::ReadGroup) {
+        Ok(Some(id)) => {
+            if !header.read_groups().contains_key(id) {
+                diagnostics.push(Diagnostic::UnknownReadGroup(id.into()));
+            }
+        }
+        Ok(None) => {}
+        Err(e) => diagnostics.push(Diagnostic::InvalidDataFieldType(e)),
+    }
+
+    match record.data().get_str(&Tag::Program) {
+        Ok(Some(id)) => {
+            if !header.programs().contains_key(id) {
+                diagnostics.push(Diagnostic::UnknownProgram(id.into()));
+            }
+        }
+        Ok(None) => {}
+        Err(e) => diagnostics.push(Diagnostic::InvalidDataFieldType(e)),
+    }
+
+    diagnostics
+}
+
+// § 1.3 The header section (2020-05-29): VN must match /^[0-9]+\.[0-9]+$/.
+fn is_valid_version(s: &str) -> bool {
+    let is_number = |t: &str| !t.is_empty() && t.bytes().all(|b| b.is_ascii_digit());
+
+    let mut components = s.splitn(2, '.');
+
+    match (components.next(), components.next()) {
+        (Some(major), Some(minor)) => is_number(major) && is_number(minor),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<(), ParseError> {
+        let s = "\
+@HD\tVN:1.6
+@SQ\tSN:sq0\tLN:8
+@SQ\tSN:sq1\tLN:13
+@SQ\tSN:sq0\tLN:21
+@RG\tID:rg0
+@RG\tID:rg0
+@PG\tID:pg0
+@PG\tID:pg1\tPP:pg0
+@PG\tID:pg1\tPP:pg0
+";
+
+        assert_eq!(
+            validate(s)?,
+            [
+                Diagnostic::DuplicateReferenceSequenceName(String::from("sq0")),
+                Diagnostic::DuplicateReadGroupId(String::from("rg0")),
+                Diagnostic::DuplicateProgramId(String::from("pg1")),
+            ]
+        );
+
+        assert!(validate("@HD\tVN:1.6\n@HD\tVN:1.6\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_header() -> Result<(), ParseError> {
+        let header: Header = "@HD\tVN:1.6\tSO:coordinate\tGO:reference\tSS:coordinate:queryname
+@SQ\tSN:sq0\tLN:8
+@PG\tID:pg0
+@PG\tID:pg1\tPP:pg0
+"
+        .parse()?;
+
+        assert!(validate_header(&header).is_empty());
+
+        let header: Header = "@HD\tVN:1.x\tSO:coordinate\tGO:query\tSS:queryname:MI
+@SQ\tSN:sq0\tLN:0
+@SQ\tSN:sq1\tLN:-8
+@PG\tID:pg0\tPP:pg3
+@PG\tID:pg1\tPP:pg2
+@PG\tID:pg2\tPP:pg1
+@PG\tID:pg3\tPP:pg4
+"
+        .parse()?;
+
+        assert_eq!(
+            validate_header(&header),
+            [
+                Diagnostic::InvalidVersion(String::from("1.x")),
+                Diagnostic::ConflictingGroupOrder {
+                    sort_order: SortOrder::Coordinate,
+                    group_order: GroupOrder::Query,
+                },
+                Diagnostic::ConflictingSubsortOrder {
+                    sort_order: SortOrder::Coordinate,
+                    subsort_order: SubsortOrder::QueryName(String::from("MI")),
+                },
+                Diagnostic::InvalidReferenceSequenceLength {
+                    name: String::from("sq0"),
+                    len: 0,
+                },
+                Diagnostic::InvalidReferenceSequenceLength {
+                    name: String::from("sq1"),
+                    len: -8,
+                },
+                Diagnostic::MissingPreviousProgram {
+                    id: String::from("pg3"),
+                    previous_id: String::from("pg4"),
+                },
+                Diagnostic::ProgramCycle(String::from("pg1")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = "@SQ\tSN:sq0\tLN:8\n@RG\tID:rg0\n@PG\tID:pg0\n".parse()?;
+
+        let record: crate::Record =
+            "r0\t0\tsq0\t1\t255\t4M\t=\t5\t0\t*\t*\tRG:Z:rg0\tPG:Z:pg0".parse()?;
+        assert!(validate_record(&header, &record).is_empty());
+
+        let record: crate::Record =
+            "r0\t0\tsq1\t1\t255\t4M\tsq2\t5\t0\t*\t*\tRG:Z:rg1\tPG:Z:pg1".parse()?;
+
+        assert_eq!(
+            validate_record(&header, &record),
+            [
+                Diagnostic::UnknownReferenceSequenceName(String::from("sq1")),
+                Diagnostic::UnknownMateReferenceSequenceName(String::from("sq2")),
+                Diagnostic::UnknownReadGroup(String::from("rg1")),
+                Diagnostic::UnknownProgram(String::from("pg1")),
+            ]
+        );
+
+        let record: crate::Record = "r0\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\tRG:i:0\tPG:A:p".parse()?;
+        let diagnostics = validate_record(&header, &record);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(matches!(
+            &diagnostics[0],
+            Diagnostic::InvalidDataFieldType(e) if e.tag() == &Tag::ReadGroup
+        ));
+        assert!(matches!(
+            &diagnostics[1],
+            Diagnostic::InvalidDataFieldType(e) if e.tag() == &Tag::Program
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_version() {
+        assert!(is_valid_version("1.6"));
+        assert!(is_valid_version("10.23"));
+        assert!(!is_valid_version(""));
+        assert!(!is_valid_version("1"));
+        assert!(!is_valid_version("1."));
+        assert!(!is_valid_version(".6"));
+        assert!(!is_valid_version("1.6.1"));
+        assert!(!is_valid_version("v1.6"));
+    }
+}
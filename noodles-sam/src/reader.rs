@@ -1,12 +1,14 @@
 //! SAM reader and iterators.
 
+mod query;
 mod records;
 
-pub use self::records::Records;
+pub use self::{query::Query, records::Records};
 
 use std::io::{self, BufRead, BufReader, Read, Seek};
 
 use noodles_bgzf as bgzf;
+use noodles_csi::BinningIndex;
 
 const HEADER_PREFIX: u8 = b'@';
 const NEWLINE: u8 = b'\n';
@@ -225,6 +227,129 @@ where
     }
 }
 
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    ///
+    /// let data = Vec::new();
+    /// let reader = sam::Reader::new(bgzf::Reader::new(&data[..]));
+    /// let virtual_position = reader.virtual_position();
+    ///
+    /// assert_eq!(virtual_position.compressed(), 0);
+    /// assert_eq!(virtual_position.uncompressed(), 0);
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(sam::Reader::new)?;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The input must be a bgzip-compressed SAM with an associated index. The index can be any
+    /// binning index, e.g., a tabix index (TBI) or a coordinate-sorted index (CSI).
+    ///
+    /// The region is given as a reference sequence name and a 1-based, inclusive interval. The
+    /// reference sequence ID used to query the index is the position of the name in
+    /// `reference_sequence_names`, which are typically the names listed in the index.
+    ///
+    /// Only lines in the index chunks that may overlap the region are parsed. The span of a record
+    /// starts at its position and ends at the last reference base of its alignment, as given by
+    /// its CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(sam::Reader::new)?;
+    ///
+    /// reader.read_header()?;
+    ///
+    /// let index = tabix::read("sample.sam.gz.tbi")?;
+    /// let query = reader.query(index.reference_sequence_names(), &index, "sq0", 8, 13)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I>(
+        &mut self,
+        reference_sequence_names: &[String],
+        index: &I,
+        reference_sequence_name: &str,
+        start: u64,
+        end: u64,
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex,
+    {
+        let reference_sequence_id = reference_sequence_names
+            .iter()
+            .position(|name| name == reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequence names: {}",
+                        reference_sequence_name
+                    ),
+                )
+            })?;
+
+        let chunks = index.query(reference_sequence_id, start, end)?;
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            reference_sequence_name.into(),
+            start,
+            end,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,4 +370,56 @@ mod tests {
         assert_eq!(header, "@HD\tVN1.6\n");
         Ok(())
     }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::{Cursor, Write};
+
+        use noodles_tabix as tabix;
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(
+            b"@HD\tVN:1.6\tSO:coordinate
+@SQ\tSN:sq0\tLN:34
+@SQ\tSN:sq1\tLN:21
+@SQ\tSN:sq2\tLN:13
+r0\t0\tsq0\t1\t255\t4M\t*\t0\t0\t*\t*
+r1\t0\tsq0\t3\t255\t2M5D2M\t*\t0\t0\t*\t*
+r2\t0\tsq0\t5\t255\t1S4M\t*\t0\t0\t*\t*
+r3\t0\tsq0\t13\t255\t4M\t*\t0\t0\t*\t*
+r4\t0\tsq2\t8\t255\t4M\t*\t0\t0\t*\t*
+",
+        )?;
+        let data = writer.finish()?;
+
+        let index = tabix::index::Indexer::sam().index(&data[..])?;
+        let reference_sequence_names = index.reference_sequence_names();
+
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let positions: Vec<_> = reader
+            .query(reference_sequence_names, &index, "sq0", 7, 9)?
+            .map(|result| result.map(|record| i32::from(record.position())))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(positions, [3, 5]);
+
+        // `sq1` is in the header but has no records, so the index lists `sq2` as its second
+        // reference sequence.
+        let names: Vec<_> = reader
+            .query(reference_sequence_names, &index, "sq2", 1, 13)?
+            .map(|result| result.map(|record| record.read_name().to_string()))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(names, ["r4"]);
+
+        assert!(reader
+            .query(reference_sequence_names, &index, "sq1", 1, 21)
+            .is_err());
+
+        assert!(reader
+            .query(reference_sequence_names, &index, "sq3", 1, 8)
+            .is_err());
+
+        Ok(())
+    }
 }
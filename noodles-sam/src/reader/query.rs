@@ -0,0 +1,85 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{index::reference_sequence::bin::Chunk, query::Lines};
+
+use crate::Record;
+
+/// An iterator over records of a SAM reader that intersect a given region.
+///
+/// This is created by calling [`sam::Reader::query`].
+///
+/// [`sam::Reader::query`]: struct.Reader.html#method.query
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    lines: Lines<'a, R>,
+    reference_sequence_name: String,
+    start: u64,
+    end: u64,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut bgzf::Reader<R>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        Self {
+            lines: Lines::new(reader, chunks),
+            reference_sequence_name,
+            start,
+            end,
+        }
+    }
+
+    fn intersects(&self, record: &Record) -> bool {
+        let is_same_reference_sequence = record
+            .reference_sequence_name()
+            .map(|name| **name == self.reference_sequence_name)
+            .unwrap_or(false);
+
+        let record_start = match *record.position() {
+            Some(position) => position as u64,
+            None => return false,
+        };
+
+        let record_reference_len = u64::from(record.cigar().reference_len()).max(1);
+        let record_end = record_start + record_reference_len - 1;
+
+        is_same_reference_sequence && in_interval(record_start, record_end, self.start, self.end)
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = match self.lines.next()?.and_then(|line| {
+                line.parse::<Record>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }) {
+                Ok(record) => record,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if self.intersects(&record) {
+                return Some(Ok(record));
+            }
+        }
+    }
+}
+
+fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
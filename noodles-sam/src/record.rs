@@ -43,7 +43,7 @@ const MAX_FIELDS: usize = 12;
 ///   11. quality scores (`QUAL`).
 ///
 /// Additionally, optional data fields can be included with any record.
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Record {
     qname: ReadName,
     flag: Flags,
@@ -339,6 +339,25 @@ impl Record {
     pub fn data(&self) -> &Data {
         &self.data
     }
+
+    /// Returns a mutable reference to the optional data fields for this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::data};
+    ///
+    /// let mut record = sam::Record::default();
+    /// record.data_mut().insert(data::Field::new(
+    ///     data::field::Tag::AlignmentHitCount,
+    ///     data::field::Value::Int32(1),
+    /// ));
+    ///
+    /// assert_eq!(record.data().to_string(), "NH:i:1");
+    /// ```
+    pub fn data_mut(&mut self) -> &mut Data {
+        &mut self.data
+    }
 }
 
 impl Default for Record {
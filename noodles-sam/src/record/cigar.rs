@@ -1,10 +1,11 @@
 //! SAM CIGAR and operations.
 
+mod aligned_pairs;
 pub mod op;
 
-use std::{error, fmt, ops::Deref, str::FromStr};
+use std::{error, fmt, iter::Copied, ops::Deref, slice, str::FromStr};
 
-pub use self::op::Op;
+pub use self::{aligned_pairs::AlignedPairs, op::Op};
 
 use super::NULL_FIELD;
 
@@ -35,15 +36,326 @@ impl Cigar {
     /// assert_eq!(cigar.reference_len(), 40);
     /// ```
     pub fn reference_len(&self) -> u32 {
-        self.iter()
-            .filter_map(|op| match op.kind() {
-                Kind::Match | Kind::Deletion | Kind::Skip | Kind::SeqMatch | Kind::SeqMismatch => {
-                    Some(op.len())
+        reference_len(self.iter().copied())
+    }
+
+    /// Calculates the read length.
+    ///
+    /// This sums the lengths of the CIGAR operations that consume the read, i.e., alignment
+    /// matches (`M`), insertions (`I`), soft clips (`S`), sequence matches (`=`), and sequence
+    /// mismatches (`X`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{cigar::{op::Kind, Op}, Cigar};
+    ///
+    /// let cigar = Cigar::from(vec![
+    ///     Op::new(Kind::Match, 36),
+    ///     Op::new(Kind::Deletion, 4),
+    ///     Op::new(Kind::SoftClip, 8),
+    /// ]);
+    ///
+    /// assert_eq!(cigar.query_len(), 44);
+    /// ```
+    pub fn query_len(&self) -> u32 {
+        query_len(self.iter().copied())
+    }
+
+    /// Returns the length of the soft clip at the start of the read.
+    ///
+    /// Hard clips (`H`) before the soft clip are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    /// let cigar: Cigar = "2H3S8M5S".parse()?;
+    /// assert_eq!(cigar.leading_soft_clip_len(), 3);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn leading_soft_clip_len(&self) -> u32 {
+        soft_clip_len(self.iter().copied())
+    }
+
+    /// Returns the length of the soft clip at the end of the read.
+    ///
+    /// Hard clips (`H`) after the soft clip are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    /// let cigar: Cigar = "2H3S8M5S".parse()?;
+    /// assert_eq!(cigar.trailing_soft_clip_len(), 5);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn trailing_soft_clip_len(&self) -> u32 {
+        soft_clip_len(self.iter().rev().copied())
+    }
+
+    /// Calculates the start position of the alignment if the clipped bases were aligned.
+    ///
+    /// This is the 1-based alignment start minus the lengths of the soft and hard clips at the
+    /// start of the read. The result may be less than 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    /// let cigar: Cigar = "2H3S8M5S".parse()?;
+    /// assert_eq!(cigar.unclipped_start(13), 8);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn unclipped_start(&self, alignment_start: i32) -> i32 {
+        alignment_start - clip_len(self.iter().copied()) as i32
+    }
+
+    /// Calculates the end position of the alignment if the clipped bases were aligned.
+    ///
+    /// This is the 1-based, inclusive alignment end plus the lengths of the soft and hard clips at
+    /// the end of the read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    /// let cigar: Cigar = "2H3S8M5S".parse()?;
+    /// assert_eq!(cigar.unclipped_end(13), 25);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn unclipped_end(&self, alignment_start: i32) -> i32 {
+        let alignment_end = alignment_start + self.reference_len() as i32 - 1;
+        alignment_end + clip_len(self.iter().rev().copied()) as i32
+    }
+
+    /// Returns an iterator over the aligned read offset and reference position pairs.
+    ///
+    /// Read offsets are 0-based and include soft clipped bases. `alignment_start` and the
+    /// reference positions are 1-based. See [`AlignedPairs`] for the pairs emitted by each
+    /// operation.
+    ///
+    /// [`AlignedPairs`]: struct.AlignedPairs.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    /// let pairs: Vec<_> = cigar.aligned_pairs(8).collect();
+    ///
+    /// assert_eq!(pairs, [
+    ///     (Some(0), None),
+    ///     (Some(1), Some(8)),
+    ///     (Some(2), Some(9)),
+    ///     (Some(3), None),
+    ///     (None, Some(10)),
+    ///     (Some(4), Some(11)),
+    /// ]);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn aligned_pairs(&self, alignment_start: i32) -> AlignedPairs<Copied<slice::Iter<'_, Op>>> {
+        AlignedPairs::new(self.iter().copied(), alignment_start)
+    }
+
+    /// Returns the reference position aligned to the given read offset.
+    ///
+    /// The read offset is 0-based and includes soft clipped bases. `alignment_start` and the
+    /// returned position are 1-based. This returns `None` if the read offset is in an insertion
+    /// or soft clip or is past the end of the read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    ///
+    /// assert_eq!(cigar.reference_position(8, 0), None);
+    /// assert_eq!(cigar.reference_position(8, 2), Some(9));
+    /// assert_eq!(cigar.reference_position(8, 3), None);
+    /// assert_eq!(cigar.reference_position(8, 4), Some(11));
+    /// assert_eq!(cigar.reference_position(8, 5), None);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn reference_position(&self, alignment_start: i32, read_offset: usize) -> Option<i32> {
+        reference_position(self.iter().copied(), alignment_start, read_offset)
+    }
+
+    /// Returns the read offset aligned to the given reference position.
+    ///
+    /// `alignment_start` and the reference position are 1-based. The returned read offset is
+    /// 0-based and includes soft clipped bases. This returns `None` if the reference position is
+    /// in a deletion or skipped region or is outside the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Cigar;
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    ///
+    /// assert_eq!(cigar.read_offset(8, 7), None);
+    /// assert_eq!(cigar.read_offset(8, 9), Some(2));
+    /// assert_eq!(cigar.read_offset(8, 10), None);
+    /// assert_eq!(cigar.read_offset(8, 11), Some(4));
+    /// assert_eq!(cigar.read_offset(8, 12), None);
+    /// # Ok::<(), noodles_sam::record::cigar::ParseError>(())
+    /// ```
+    pub fn read_offset(&self, alignment_start: i32, reference_position: i32) -> Option<usize> {
+        read_offset(self.iter().copied(), alignment_start, reference_position)
+    }
+}
+
+/// Calculates the alignment span over the reference sequence of the given operations.
+///
+/// See [`Cigar::reference_len`].
+///
+/// [`Cigar::reference_len`]: struct.Cigar.html#method.reference_len
+pub fn reference_len<I>(ops: I) -> u32
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    ops.into_iter()
+        .map(|op| op.into())
+        .filter(|op| op.kind().consumes_reference())
+        .map(|op| op.len())
+        .sum()
+}
+
+/// Calculates the read length of the given operations.
+///
+/// See [`Cigar::query_len`].
+///
+/// [`Cigar::query_len`]: struct.Cigar.html#method.query_len
+pub fn query_len<I>(ops: I) -> u32
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    ops.into_iter()
+        .map(|op| op.into())
+        .filter(|op| op.kind().consumes_read())
+        .map(|op| op.len())
+        .sum()
+}
+
+/// Returns the length of the soft clip at the start of the given operations.
+///
+/// Hard clips (`H`) before the soft clip are skipped. Pass reversed operations to get the length
+/// of the soft clip at the end of the read.
+pub fn soft_clip_len<I>(ops: I) -> u32
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    ops.into_iter()
+        .map(|op| op.into())
+        .skip_while(|op| op.kind() == Kind::HardClip)
+        .take_while(|op| op.kind() == Kind::SoftClip)
+        .map(|op| op.len())
+        .sum()
+}
+
+/// Returns the length of the soft and hard clips at the start of the given operations.
+///
+/// Pass reversed operations to get the length of the clips at the end of the read.
+pub fn clip_len<I>(ops: I) -> u32
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    ops.into_iter()
+        .map(|op| op.into())
+        .take_while(|op| matches!(op.kind(), Kind::SoftClip | Kind::HardClip))
+        .map(|op| op.len())
+        .sum()
+}
+
+/// Returns the reference position aligned to the given read offset in the given operations.
+///
+/// See [`Cigar::reference_position`].
+///
+/// [`Cigar::reference_position`]: struct.Cigar.html#method.reference_position
+pub fn reference_position<I>(ops: I, alignment_start: i32, read_offset: usize) -> Option<i32>
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    let mut start = 0;
+    let mut position = alignment_start;
+
+    for op in ops.into_iter().map(|op| op.into()) {
+        let len = op.len() as usize;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if read_offset < start + len {
+                    return Some(position + (read_offset - start) as i32);
                 }
-                _ => None,
-            })
-            .sum()
+
+                start += len;
+                position += len as i32;
+            }
+            Kind::Insertion | Kind::SoftClip => {
+                if read_offset < start + len {
+                    return None;
+                }
+
+                start += len;
+            }
+            Kind::Deletion | Kind::Skip => position += len as i32,
+            Kind::HardClip | Kind::Pad => {}
+        }
     }
+
+    None
+}
+
+/// Returns the read offset aligned to the given reference position in the given operations.
+///
+/// See [`Cigar::read_offset`].
+///
+/// [`Cigar::read_offset`]: struct.Cigar.html#method.read_offset
+pub fn read_offset<I>(ops: I, alignment_start: i32, reference_position: i32) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Into<Op>,
+{
+    if reference_position < alignment_start {
+        return None;
+    }
+
+    let mut read_offset = 0;
+    let mut start = alignment_start;
+
+    for op in ops.into_iter().map(|op| op.into()) {
+        let len = op.len() as i32;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if reference_position < start + len {
+                    return Some(read_offset + (reference_position - start) as usize);
+                }
+
+                read_offset += len as usize;
+                start += len;
+            }
+            Kind::Insertion | Kind::SoftClip => read_offset += len as usize,
+            Kind::Deletion | Kind::Skip => {
+                if reference_position < start + len {
+                    return None;
+                }
+
+                start += len;
+            }
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    None
 }
 
 impl Deref for Cigar {
@@ -132,6 +444,56 @@ mod tests {
         assert!(!cigar.is_empty());
     }
 
+    #[test]
+    fn test_aligned_pairs() -> Result<(), ParseError> {
+        let cigar: Cigar = "2H1S1=1P1X2N1I1M3H".parse()?;
+        let pairs: Vec<_> = cigar.aligned_pairs(5).collect();
+
+        assert_eq!(
+            pairs,
+            [
+                (Some(0), None),
+                (Some(1), Some(5)),
+                (Some(2), Some(6)),
+                (None, Some(7)),
+                (None, Some(8)),
+                (Some(3), None),
+                (Some(4), Some(9)),
+            ]
+        );
+
+        assert!(Cigar::default().aligned_pairs(5).next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coordinates_with_clips() -> Result<(), ParseError> {
+        let cigar: Cigar = "2H1S1=1P1X2N1I1M3H".parse()?;
+
+        assert_eq!(cigar.query_len(), 5);
+        assert_eq!(cigar.leading_soft_clip_len(), 1);
+        assert_eq!(cigar.trailing_soft_clip_len(), 0);
+        assert_eq!(cigar.unclipped_start(5), 2);
+        assert_eq!(cigar.unclipped_end(5), 12);
+
+        for (read_offset, reference_position) in cigar.aligned_pairs(5) {
+            if let Some(read_offset) = read_offset {
+                assert_eq!(cigar.reference_position(5, read_offset), reference_position);
+            }
+
+            if let Some(reference_position) = reference_position {
+                assert_eq!(cigar.read_offset(5, reference_position), read_offset);
+            }
+        }
+
+        assert_eq!(cigar.reference_position(5, 5), None);
+        assert_eq!(cigar.read_offset(5, 4), None);
+        assert_eq!(cigar.read_offset(5, 10), None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_fmt() {
         let cigar = Cigar::from(vec![
@@ -0,0 +1,95 @@
+use super::{op::Kind, Op};
+
+/// An iterator over the aligned read offset and reference position pairs of a CIGAR.
+///
+/// Each item is a pair of a 0-based read offset and a 1-based reference position. Alignment
+/// matches (`M`), sequence matches (`=`), and sequence mismatches (`X`) have both. Insertions
+/// (`I`) and soft clips (`S`) only have a read offset; and deletions (`D`) and skipped regions
+/// (`N`), only a reference position. Hard clips (`H`) and padding (`P`) are not emitted.
+///
+/// This is created by calling [`Cigar::aligned_pairs`].
+///
+/// [`Cigar::aligned_pairs`]: struct.Cigar.html#method.aligned_pairs
+pub struct AlignedPairs<I> {
+    ops: I,
+    kind: Kind,
+    remaining: u32,
+    read_offset: usize,
+    reference_position: i32,
+}
+
+impl<I> AlignedPairs<I>
+where
+    I: Iterator,
+    I::Item: Into<Op>,
+{
+    /// Creates an aligned pairs iterator over CIGAR operations.
+    ///
+    /// `alignment_start` is the 1-based position of the first aligned base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::cigar::{op::Kind, AlignedPairs, Op};
+    ///
+    /// let ops = vec![Op::new(Kind::Match, 1), Op::new(Kind::Insertion, 1)];
+    /// let pairs: Vec<_> = AlignedPairs::new(ops.into_iter(), 8).collect();
+    ///
+    /// assert_eq!(pairs, [(Some(0), Some(8)), (Some(1), None)]);
+    /// ```
+    pub fn new(ops: I, alignment_start: i32) -> Self {
+        Self {
+            ops,
+            kind: Kind::Match,
+            remaining: 0,
+            read_offset: 0,
+            reference_position: alignment_start,
+        }
+    }
+}
+
+impl<I> Iterator for AlignedPairs<I>
+where
+    I: Iterator,
+    I::Item: Into<Op>,
+{
+    type Item = (Option<usize>, Option<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining == 0 {
+                let op = self.ops.next()?.into();
+                self.kind = op.kind();
+                self.remaining = op.len();
+                continue;
+            }
+
+            let read_offset = self.read_offset;
+            let reference_position = self.reference_position;
+
+            let pair = match self.kind {
+                Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                    self.read_offset += 1;
+                    self.reference_position += 1;
+                    (Some(read_offset), Some(reference_position))
+                }
+                Kind::Insertion | Kind::SoftClip => {
+                    self.read_offset += 1;
+                    (Some(read_offset), None)
+                }
+                Kind::Deletion | Kind::Skip => {
+                    self.reference_position += 1;
+                    (None, Some(reference_position))
+                }
+                Kind::HardClip | Kind::Pad => {
+                    self.remaining = 0;
+                    continue;
+                }
+            };
+
+            self.remaining -= 1;
+
+            return Some(pair);
+        }
+    }
+}
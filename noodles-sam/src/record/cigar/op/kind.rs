@@ -23,6 +23,46 @@ pub enum Kind {
     SeqMismatch,
 }
 
+impl Kind {
+    /// Returns whether the operation kind causes the alignment to consume the read.
+    ///
+    /// These are alignment matches (`M`), insertions (`I`), soft clips (`S`), sequence matches
+    /// (`=`), and sequence mismatches (`X`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::cigar::op::Kind;
+    /// assert!(Kind::Insertion.consumes_read());
+    /// assert!(!Kind::Deletion.consumes_read());
+    /// ```
+    pub fn consumes_read(self) -> bool {
+        matches!(
+            self,
+            Self::Match | Self::Insertion | Self::SoftClip | Self::SeqMatch | Self::SeqMismatch
+        )
+    }
+
+    /// Returns whether the operation kind causes the alignment to consume the reference.
+    ///
+    /// These are alignment matches (`M`), deletions (`D`), skipped regions (`N`), sequence
+    /// matches (`=`), and sequence mismatches (`X`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::cigar::op::Kind;
+    /// assert!(Kind::Deletion.consumes_reference());
+    /// assert!(!Kind::Insertion.consumes_reference());
+    /// ```
+    pub fn consumes_reference(self) -> bool {
+        matches!(
+            self,
+            Self::Match | Self::Deletion | Self::Skip | Self::SeqMatch | Self::SeqMismatch
+        )
+    }
+}
+
 impl fmt::Display for Kind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", char::from(*self))
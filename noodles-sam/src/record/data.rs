@@ -4,7 +4,12 @@ pub mod field;
 
 pub use self::field::Field;
 
-use std::{error, fmt, ops::Deref, str::FromStr};
+use std::{error, fmt, mem, ops::Deref, str::FromStr};
+
+use self::field::{
+    value::{Subtype, Type},
+    Tag, Value,
+};
 
 const DELIMITER: char = '\t';
 
@@ -14,6 +19,271 @@ const DELIMITER: char = '\t';
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Data(Vec<Field>);
 
+impl Data {
+    /// Returns the value of the field with the given tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::{Tag, Value}, Data};
+    ///
+    /// let data: Data = "NH:i:1".parse()?;
+    /// assert_eq!(data.get(&Tag::AlignmentHitCount), Some(&Value::Int32(1)));
+    /// assert!(data.get(&Tag::ReadGroup).is_none());
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get(&self, tag: &Tag) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|field| field.tag() == tag)
+            .map(|field| field.value())
+    }
+
+    /// Returns the character value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not a character (`A`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "TS:A:+".parse()?;
+    /// assert_eq!(data.get_char(&Tag::TranscriptStrand), Ok(Some('+')));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_char(&self, tag: &Tag) -> Result<Option<char>, TypeError> {
+        self.get_as(tag, Type::Char, Value::as_char)
+    }
+
+    /// Returns the 32-bit integer value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not a 32-bit integer (`i`) or is greater than
+    /// `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    ///
+    /// let data: Data = "NM:i:3".parse()?;
+    /// assert_eq!(data.get_int32(&Tag::EditDistance), Ok(Some(3)));
+    ///
+    /// let data: Data = "NM:Z:3".parse()?;
+    /// assert!(data.get_int32(&Tag::EditDistance).is_err());
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_int32(&self, tag: &Tag) -> Result<Option<i32>, TypeError> {
+        self.get_as(tag, Type::Int32, Value::as_int32)
+    }
+
+    /// Returns the single-precision floating-point value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not a single-precision floating-point (`f`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "zf:f:0.5".parse()?;
+    /// assert_eq!(data.get_float(&Tag::Other(String::from("zf"))), Ok(Some(0.5)));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_float(&self, tag: &Tag) -> Result<Option<f32>, TypeError> {
+        self.get_as(tag, Type::Float, Value::as_float)
+    }
+
+    /// Returns the string value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not a string (`Z`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "RG:Z:rg0".parse()?;
+    /// assert_eq!(data.get_str(&Tag::ReadGroup), Ok(Some("rg0")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_str(&self, tag: &Tag) -> Result<Option<&str>, TypeError> {
+        self.get_as(tag, Type::String, Value::as_str)
+    }
+
+    /// Returns the hex string value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not a hex string (`H`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "zh:H:CAFE".parse()?;
+    /// assert_eq!(data.get_hex(&Tag::Other(String::from("zh"))), Ok(Some("CAFE")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_hex(&self, tag: &Tag) -> Result<Option<&str>, TypeError> {
+        self.get_as(tag, Type::Hex, Value::as_hex)
+    }
+
+    fn get_as<'a, T, F>(&'a self, tag: &Tag, ty: Type, f: F) -> Result<Option<T>, TypeError>
+    where
+        F: Fn(&'a Value) -> Option<T>,
+    {
+        match self.get(tag) {
+            Some(value) => f(value).map(Some).ok_or_else(|| TypeError {
+                tag: tag.clone(),
+                expected: ty,
+                expected_subtype: None,
+                actual: value.ty(),
+                actual_subtype: value.subtype(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Inserts a field into the data.
+    ///
+    /// If a field with the same tag exists, it is replaced, keeping its position, and the old
+    /// field is returned.
+    ///
+    /// This does not check the value type. Use [`set`] to insert a field with a type check.
+    ///
+    /// [`set`]: #method.set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     data::{
+    ///         field::{Tag, Value},
+    ///         Field,
+    ///     },
+    ///     Data,
+    /// };
+    ///
+    /// let mut data = Data::default();
+    ///
+    /// let nh = Field::new(Tag::AlignmentHitCount, Value::Int32(1));
+    /// assert!(data.insert(nh.clone()).is_none());
+    ///
+    /// let old_nh = data.insert(Field::new(Tag::AlignmentHitCount, Value::Int32(2)));
+    /// assert_eq!(old_nh, Some(nh));
+    /// assert_eq!(data.to_string(), "NH:i:2");
+    /// ```
+    pub fn insert(&mut self, field: Field) -> Option<Field> {
+        match self.0.iter_mut().find(|f| f.tag() == field.tag()) {
+            Some(f) => Some(mem::replace(f, field)),
+            None => {
+                self.0.push(field);
+                None
+            }
+        }
+    }
+
+    /// Sets the value of the field with the given tag.
+    ///
+    /// The value must have the type the specification requires for the tag. Reserved and
+    /// non-standard tags accept any type. If a field with the same tag exists, it is replaced,
+    /// and the old field is returned.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value does not have the type (and, for arrays, the subtype)
+    /// required for the tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::{Tag, Value}, Data};
+    ///
+    /// let mut data = Data::default();
+    ///
+    /// assert!(data.set(Tag::EditDistance, Value::Int32(3)).is_ok());
+    /// assert!(data.set(Tag::EditDistance, Value::String(String::from("3"))).is_err());
+    ///
+    /// assert_eq!(data.to_string(), "NM:i:3");
+    /// ```
+    pub fn set(&mut self, tag: Tag, value: Value) -> Result<Option<Field>, TypeError> {
+        check_type(&tag, &value)?;
+        Ok(self.insert(Field::new(tag, value)))
+    }
+
+    /// Removes the field with the given tag and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    ///
+    /// let mut data: Data = "RG:Z:rg0\tNH:i:1".parse()?;
+    ///
+    /// assert!(data.remove(&Tag::ReadGroup).is_some());
+    /// assert!(data.remove(&Tag::ReadGroup).is_none());
+    /// assert_eq!(data.to_string(), "NH:i:1");
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn remove(&mut self, tag: &Tag) -> Option<Field> {
+        let i = self.0.iter().position(|field| field.tag() == tag)?;
+        Some(self.0.remove(i))
+    }
+
+    /// Checks the value types of all fields against the types the specification requires.
+    ///
+    /// This returns an error for each field with an invalid type, in field order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::{value::Type, Tag}, Data};
+    ///
+    /// let data: Data = "RG:Z:rg0\tNM:Z:3\tNH:i:1".parse()?;
+    /// let errors = data.validate();
+    ///
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].tag(), &Tag::EditDistance);
+    /// assert_eq!(errors[0].expected(), Type::Int32);
+    /// assert_eq!(errors[0].actual(), Type::String);
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn validate(&self) -> Vec<TypeError> {
+        self.0
+            .iter()
+            .filter_map(|field| check_type(field.tag(), field.value()).err())
+            .collect()
+    }
+}
+
+fn check_type(tag: &Tag, value: &Value) -> Result<(), TypeError> {
+    let expected = match tag.ty() {
+        Some(ty) => ty,
+        None => return Ok(()),
+    };
+
+    let expected_subtype = tag.subtype();
+
+    if value.ty() != expected || (expected_subtype.is_some() && value.subtype() != expected_subtype)
+    {
+        Err(TypeError {
+            tag: tag.clone(),
+            expected,
+            expected_subtype,
+            actual: value.ty(),
+            actual_subtype: value.subtype(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
 impl Deref for Data {
     type Target = [Field];
 
@@ -42,6 +312,61 @@ impl From<Vec<Field>> for Data {
     }
 }
 
+/// An error returned when a data field value does not have the expected type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeError {
+    tag: Tag,
+    expected: Type,
+    expected_subtype: Option<Subtype>,
+    actual: Type,
+    actual_subtype: Option<Subtype>,
+}
+
+impl TypeError {
+    /// Returns the tag of the field.
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns the expected value type.
+    pub fn expected(&self) -> Type {
+        self.expected
+    }
+
+    /// Returns the expected array subtype, if the tag requires one.
+    pub fn expected_subtype(&self) -> Option<Subtype> {
+        self.expected_subtype
+    }
+
+    /// Returns the actual value type.
+    pub fn actual(&self) -> Type {
+        self.actual
+    }
+
+    /// Returns the actual array subtype, if the value is an array.
+    pub fn actual_subtype(&self) -> Option<Subtype> {
+        self.actual_subtype
+    }
+}
+
+impl error::Error for TypeError {}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid data field type for {}: expected ", self.tag)?;
+        fmt_type(f, self.expected, self.expected_subtype)?;
+        f.write_str(", got ")?;
+        fmt_type(f, self.actual, self.actual_subtype)
+    }
+}
+
+fn fmt_type(f: &mut fmt::Formatter<'_>, ty: Type, subtype: Option<Subtype>) -> fmt::Result {
+    match subtype {
+        Some(subtype) => write!(f, "{}:{}", ty, subtype),
+        None => write!(f, "{}", ty),
+    }
+}
+
 /// An error returned when raw SAM record data fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -104,4 +429,83 @@ mod tests {
 
         assert_eq!("".parse::<Data>(), Ok(Data::default()));
     }
+
+    #[test]
+    fn test_get_int32() -> Result<(), ParseError> {
+        let data: Data = "NM:i:3\tAS:Z:8".parse()?;
+
+        assert_eq!(data.get_int32(&Tag::EditDistance), Ok(Some(3)));
+        assert_eq!(data.get_int32(&Tag::AlignmentHitCount), Ok(None));
+        assert_eq!(
+            data.get_int32(&Tag::AlignmentScore),
+            Err(TypeError {
+                tag: Tag::AlignmentScore,
+                expected: Type::Int32,
+                expected_subtype: None,
+                actual: Type::String,
+                actual_subtype: None,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set() {
+        let mut data = Data::default();
+
+        assert_eq!(
+            data.set(Tag::ReadGroup, Value::String(String::from("rg0"))),
+            Ok(None)
+        );
+        assert_eq!(
+            data.set(Tag::ReadGroup, Value::Int32(0)),
+            Err(TypeError {
+                tag: Tag::ReadGroup,
+                expected: Type::String,
+                expected_subtype: None,
+                actual: Type::Int32,
+                actual_subtype: None,
+            })
+        );
+
+        assert!(data.set(Tag::Cigar, Value::UInt32Array(vec![0x40])).is_ok());
+
+        let result = data.set(Tag::Cigar, Value::UInt16Array(vec![0x40]));
+        assert_eq!(
+            result,
+            Err(TypeError {
+                tag: Tag::Cigar,
+                expected: Type::Array,
+                expected_subtype: Some(Subtype::UInt32),
+                actual: Type::Array,
+                actual_subtype: Some(Subtype::UInt16),
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid data field type for CG: expected B:I, got B:S"
+        );
+        assert!(data
+            .set(Tag::Other(String::from("zn")), Value::Int32(0))
+            .is_ok());
+
+        assert_eq!(data.to_string(), "RG:Z:rg0\tCG:B:I,64\tzn:i:0");
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), ParseError> {
+        let data: Data = "FZ:B:S,1,2\tCG:B:I,64".parse()?;
+        assert!(data.validate().is_empty());
+
+        let data: Data = "FZ:B:C,1,2\tCG:B:S,64\tNM:i:0".parse()?;
+        let errors = data.validate();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].tag(), &Tag::AlterantiveSequence);
+        assert_eq!(errors[0].actual_subtype(), Some(Subtype::UInt8));
+        assert_eq!(errors[1].tag(), &Tag::Cigar);
+        assert_eq!(errors[1].expected_subtype(), Some(Subtype::UInt32));
+
+        Ok(())
+    }
 }
@@ -2,6 +2,8 @@
 
 use std::{error, fmt, str::FromStr};
 
+use super::value::{Subtype, Type};
+
 const LEN: usize = 2;
 
 /// A SAM record data field tag.
@@ -134,6 +136,108 @@ pub enum Tag {
     Other(String),
 }
 
+impl Tag {
+    /// Returns the value type required by the specification for this tag.
+    ///
+    /// This returns `None` for reserved and non-standard tags, which have no required type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::{value::Type, Tag};
+    ///
+    /// assert_eq!(Tag::EditDistance.ty(), Some(Type::Int32));
+    /// assert_eq!(Tag::ReadGroup.ty(), Some(Type::String));
+    /// assert_eq!(Tag::Cigar.ty(), Some(Type::Array));
+    /// assert_eq!(Tag::Other(String::from("zn")).ty(), None);
+    /// ```
+    pub fn ty(&self) -> Option<Type> {
+        match self {
+            Self::MinMappingQuality
+            | Self::AlignmentScore
+            | Self::ColorEditDistance
+            | Self::NextHitPosition
+            | Self::SegmentIndex
+            | Self::PerfectHitCount
+            | Self::OneDifferenceHitCount
+            | Self::TwoDifferenceHitCount
+            | Self::HitIndex
+            | Self::TotalHitCount
+            | Self::MateMappingQuality
+            | Self::AlignmentHitCount
+            | Self::EditDistance
+            | Self::OriginalPosition
+            | Self::TemplateLikelihood
+            | Self::TemplateMappingQuality
+            | Self::SegmentCount
+            | Self::SegmentLikelihood => Some(Type::Int32),
+            Self::SampleBarcodeSequence
+            | Self::BaseAlignmentQualityOffsets
+            | Self::OriginalUmiQualityScores
+            | Self::CellBarcodeId
+            | Self::NextHitReferenceSequenceName
+            | Self::Comment
+            | Self::ColarQualityScores
+            | Self::CellBarcodeSequence
+            | Self::ColorSequence
+            | Self::CompleteReadAnnotations
+            | Self::CellBarcodeQualityScores
+            | Self::NextHitSequence
+            | Self::SegmentSuffix
+            | Self::Library
+            | Self::MateCigar
+            | Self::MismatchedPositions
+            | Self::UmiId
+            | Self::OriginalAlignment
+            | Self::OriginalCigar
+            | Self::OriginalQualityScores
+            | Self::OriginalUmiBarcodeSequence
+            | Self::Program
+            | Self::PaddedReadAnnotations
+            | Self::PlatformUnit
+            | Self::MateQualityScores
+            | Self::SampleBarcodeQualityScores
+            | Self::UmiQualityScores
+            | Self::MateSequence
+            | Self::ReadGroup
+            | Self::UmiSequence
+            | Self::OtherAlignments
+            | Self::NextHitQualityScores => Some(Type::String),
+            Self::Cigar | Self::AlterantiveSequence => Some(Type::Array),
+            Self::TranscriptStrand => Some(Type::Char),
+            Self::ReservedGC
+            | Self::ReservedGQ
+            | Self::ReservedGS
+            | Self::ReservedMF
+            | Self::ReservedRT
+            | Self::ReservedS2
+            | Self::ReservedSQ
+            | Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the array subtype required by the specification for this tag.
+    ///
+    /// This returns `None` for tags that are not arrays or have no required subtype.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::{value::Subtype, Tag};
+    ///
+    /// assert_eq!(Tag::Cigar.subtype(), Some(Subtype::UInt32));
+    /// assert_eq!(Tag::AlterantiveSequence.subtype(), Some(Subtype::UInt16));
+    /// assert_eq!(Tag::ReadGroup.subtype(), None);
+    /// ```
+    pub fn subtype(&self) -> Option<Subtype> {
+        match self {
+            Self::Cigar => Some(Subtype::UInt32),
+            Self::AlterantiveSequence => Some(Subtype::UInt16),
+            _ => None,
+        }
+    }
+}
+
 impl AsRef<str> for Tag {
     fn as_ref(&self) -> &str {
         match self {
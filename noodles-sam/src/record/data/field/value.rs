@@ -18,6 +18,11 @@ pub enum Value {
     Char(char),
     /// A SAM data field 32-bit integer (`i`).
     Int32(i32),
+    /// A SAM data field 32-bit integer (`i`) that is greater than `i32::MAX`.
+    ///
+    /// SAM integers range from -2^31 to 2^32 - 1. Parsed values that do not fit in an `i32` are
+    /// kept as unsigned integers.
+    UInt32(u32),
     /// A SAM data field single-precision floating-point (`f`).
     Float(f32),
     /// A SAM data field string (`Z`).
@@ -53,6 +58,7 @@ impl Value {
         match *self {
             Self::Char(_) => Type::Char,
             Self::Int32(_) => Type::Int32,
+            Self::UInt32(_) => Type::Int32,
             Self::Float(_) => Type::Float,
             Self::String(_) => Type::String,
             Self::Hex(_) => Type::Hex,
@@ -148,6 +154,36 @@ impl Value {
         self.as_int32().is_some()
     }
 
+    /// Returns the value as a 32-bit unsigned integer if it is a 32-bit integer greater than
+    /// `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Value;
+    /// assert_eq!(Value::UInt32(u32::MAX).as_uint32(), Some(u32::MAX));
+    /// assert_eq!(Value::Int32(0).as_uint32(), None);
+    /// ```
+    pub fn as_uint32(&self) -> Option<u32> {
+        match *self {
+            Self::UInt32(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the value is a 32-bit integer greater than `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Value;
+    /// assert!(Value::UInt32(u32::MAX).is_uint32());
+    /// assert!(!Value::Int32(0).is_uint32());
+    /// ```
+    pub fn is_uint32(&self) -> bool {
+        self.as_uint32().is_some()
+    }
+
     /// Returns the value as a single-precision floating-point if it is a single-precision
     /// float-point.
     ///
@@ -449,6 +485,7 @@ impl fmt::Display for Value {
         match self {
             Self::Char(c) => write!(f, "{}", c),
             Self::Int32(n) => write!(f, "{}", n),
+            Self::UInt32(n) => write!(f, "{}", n),
             Self::Float(n) => write!(f, "{}", n),
             Self::String(s) => f.write_str(s),
             Self::Hex(s) => f.write_str(s),
@@ -572,7 +609,7 @@ impl FromStr for Value {
 
         match ty {
             Type::Char => parse_char(value).map(Value::Char),
-            Type::Int32 => parse_i32(value).map(Value::Int32),
+            Type::Int32 => parse_int(value),
             Type::Float => parse_f32(value).map(Value::Float),
             Type::String => Ok(Value::String(value.into())),
             Type::Hex => Ok(Value::Hex(value.into())),
@@ -605,6 +642,17 @@ fn parse_i32(s: &str) -> Result<i32, ParseError> {
     s.parse().map_err(ParseError::InvalidIntValue)
 }
 
+// Parses a SAM integer (`i`), which ranges from -2^31 to 2^32 - 1.
+fn parse_int(s: &str) -> Result<Value, ParseError> {
+    match s.parse() {
+        Ok(n) => Ok(Value::Int32(n)),
+        Err(e) => s
+            .parse()
+            .map(Value::UInt32)
+            .map_err(|_| ParseError::InvalidIntValue(e)),
+    }
+}
+
 fn parse_u32(s: &str) -> Result<u32, ParseError> {
     s.parse().map_err(ParseError::InvalidIntValue)
 }
@@ -667,6 +715,7 @@ mod tests {
     fn test_ty() {
         assert_eq!(Value::Char('n').ty(), Type::Char);
         assert_eq!(Value::Int32(0).ty(), Type::Int32);
+        assert_eq!(Value::UInt32(u32::MAX).ty(), Type::Int32);
         assert_eq!(Value::Float(0.0).ty(), Type::Float);
         assert_eq!(Value::String(String::from("noodles")).ty(), Type::String);
         assert_eq!(Value::Hex(String::from("cafe")).ty(), Type::Hex);
@@ -699,6 +748,7 @@ mod tests {
     fn test_fmt() {
         assert_eq!(Value::Char('n').to_string(), "n");
         assert_eq!(Value::Int32(13).to_string(), "13");
+        assert_eq!(Value::UInt32(u32::MAX).to_string(), "4294967295");
         assert_eq!(Value::Float(3.14).to_string(), "3.14");
 
         assert_eq!(
@@ -719,4 +769,23 @@ mod tests {
             "f,2.71,3.14"
         );
     }
+
+    #[test]
+    fn test_from_str_with_int32_type() {
+        assert_eq!("i:-2147483648".parse(), Ok(Value::Int32(i32::MIN)));
+        assert_eq!("i:2147483647".parse(), Ok(Value::Int32(i32::MAX)));
+        assert_eq!(
+            "i:2147483648".parse(),
+            Ok(Value::UInt32(i32::MAX as u32 + 1))
+        );
+        assert_eq!("i:4294967295".parse(), Ok(Value::UInt32(u32::MAX)));
+        assert!(matches!(
+            "i:4294967296".parse::<Value>(),
+            Err(ParseError::InvalidIntValue(_))
+        ));
+        assert!(matches!(
+            "i:-2147483649".parse::<Value>(),
+            Err(ParseError::InvalidIntValue(_))
+        ));
+    }
 }
@@ -0,0 +1,262 @@
+use std::{io, str};
+
+use super::index::{format::CoordinateSystem, Format, Index};
+
+pub(crate) const LINE_FEED: u8 = b'\n';
+const CARRIAGE_RETURN: u8 = b'\r';
+const FIELD_DELIMITER: u8 = b'\t';
+
+// The 1-based positions of the fields used to calculate the end position of SAM and VCF records.
+const SAM_CIGAR_INDEX: usize = 6;
+const VCF_REFERENCE_BASES_INDEX: usize = 4;
+const VCF_INFO_INDEX: usize = 8;
+
+/// The columns of a tab-delimited record that define its reference sequence name and interval.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Columns {
+    format: Format,
+    reference_sequence_name_index: usize,
+    start_position_index: usize,
+    end_position_index: usize,
+}
+
+impl Columns {
+    pub fn new(
+        format: Format,
+        reference_sequence_name_index: usize,
+        start_position_index: usize,
+        end_position_index: usize,
+    ) -> Self {
+        Self {
+            format,
+            reference_sequence_name_index,
+            start_position_index,
+            end_position_index,
+        }
+    }
+
+    pub fn reference_sequence_name<'a>(&self, fields: &[&'a [u8]]) -> io::Result<&'a [u8]> {
+        get_field(fields, self.reference_sequence_name_index)
+    }
+
+    // Returns the 1-based, closed interval of the record.
+    pub fn interval(&self, fields: &[&[u8]]) -> io::Result<(u64, u64)> {
+        let raw_start = get_field(fields, self.start_position_index)?;
+        let mut start = parse_position(raw_start)?;
+
+        if self.format.coordinate_system() == CoordinateSystem::Bed {
+            start = start.checked_add(1).ok_or_else(position_overflow_error)?;
+        }
+
+        let end = if self.end_position_index > 0 {
+            let raw_end = get_field(fields, self.end_position_index)?;
+            parse_position(raw_end)?
+        } else {
+            match self.format {
+                Format::Sam => {
+                    let raw_cigar = get_field(fields, SAM_CIGAR_INDEX)?;
+                    let reference_len = parse_cigar_reference_len(raw_cigar)?;
+                    end_position(start, reference_len)?
+                }
+                Format::Vcf => {
+                    let raw_reference_bases = get_field(fields, VCF_REFERENCE_BASES_INDEX)?;
+                    let reference_len = raw_reference_bases.len() as u64;
+
+                    let info_end = match get_field(fields, VCF_INFO_INDEX) {
+                        Ok(raw_info) => parse_info_end(raw_info)?,
+                        Err(_) => None,
+                    };
+
+                    match info_end {
+                        Some(end) => end,
+                        None => end_position(start, reference_len)?,
+                    }
+                }
+                Format::Generic(_) => start,
+            }
+        };
+
+        // Zero-length features (e.g., BED intervals where start == end) and records that end
+        // before they start (e.g., a VCF `END` < `POS`) span at least their start position.
+        Ok((start, end.max(start)))
+    }
+}
+
+impl From<&Index> for Columns {
+    fn from(index: &Index) -> Self {
+        Self::new(
+            index.format(),
+            index.reference_sequence_name_index(),
+            index.start_position_index(),
+            index.end_position_index(),
+        )
+    }
+}
+
+pub(crate) fn trim_newline(buf: &[u8]) -> &[u8] {
+    let buf = buf.strip_suffix(&[LINE_FEED]).unwrap_or(buf);
+    buf.strip_suffix(&[CARRIAGE_RETURN]).unwrap_or(buf)
+}
+
+pub(crate) fn split_fields(buf: &[u8]) -> Vec<&[u8]> {
+    buf.split(|&b| b == FIELD_DELIMITER).collect()
+}
+
+// Returns the end position of a span of `len` (at least 1) starting at `start`.
+fn end_position(start: u64, len: u64) -> io::Result<u64> {
+    start
+        .checked_add(len.max(1) - 1)
+        .ok_or_else(position_overflow_error)
+}
+
+fn position_overflow_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "position overflow")
+}
+
+fn get_field<'a>(fields: &[&'a [u8]], i: usize) -> io::Result<&'a [u8]> {
+    i.checked_sub(1)
+        .and_then(|j| fields.get(j))
+        .copied()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("missing field: {}", i)))
+}
+
+fn parse_position(buf: &[u8]) -> io::Result<u64> {
+    str::from_utf8(buf)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .and_then(|s| {
+            s.parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+}
+
+fn parse_cigar_reference_len(buf: &[u8]) -> io::Result<u64> {
+    if buf == b"*" {
+        return Ok(0);
+    }
+
+    let mut reference_len: u64 = 0;
+    let mut len: u64 = 0;
+
+    for &b in buf {
+        match b {
+            b'0'..=b'9' => {
+                len = len
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(u64::from(b - b'0')))
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "invalid CIGAR op length")
+                    })?;
+            }
+            b'M' | b'D' | b'N' | b'=' | b'X' => {
+                reference_len = reference_len.checked_add(len).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid CIGAR reference length")
+                })?;
+                len = 0;
+            }
+            b'I' | b'S' | b'H' | b'P' => {
+                len = 0;
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid CIGAR op kind",
+                ))
+            }
+        }
+    }
+
+    Ok(reference_len)
+}
+
+fn parse_info_end(buf: &[u8]) -> io::Result<Option<u64>> {
+    const END_PREFIX: &[u8] = b"END=";
+
+    buf.split(|&b| b == b';')
+        .find_map(|field| field.strip_prefix(END_PREFIX))
+        .map(parse_position)
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() -> io::Result<()> {
+        let columns = Columns::new(Format::Generic(CoordinateSystem::Bed), 1, 2, 3);
+        let fields = split_fields(b"sq0\t7\t13");
+        assert_eq!(columns.interval(&fields)?, (8, 13));
+
+        let columns = Columns::new(Format::Generic(CoordinateSystem::Gff), 1, 4, 5);
+        let fields = split_fields(b"sq0\t.\t.\t8\t13");
+        assert_eq!(columns.interval(&fields)?, (8, 13));
+
+        let columns = Columns::new(Format::Generic(CoordinateSystem::Gff), 1, 2, 0);
+        let fields = split_fields(b"sq0\t8\tA");
+        assert_eq!(columns.interval(&fields)?, (8, 8));
+
+        let columns = Columns::new(Format::Vcf, 1, 2, 0);
+        let fields = split_fields(b"sq0\t8\t.\tACGT\t.\t.\tPASS\t.");
+        assert_eq!(columns.interval(&fields)?, (8, 11));
+
+        let columns = Columns::new(Format::Sam, 3, 4, 0);
+        let fields = split_fields(b"r0\t0\tsq0\t8\t60\t2M3D1M");
+        assert_eq!(columns.interval(&fields)?, (8, 13));
+
+        assert!(columns.interval(&fields[..3]).is_err());
+
+        let columns = Columns::new(Format::Generic(CoordinateSystem::Bed), 1, 2, 3);
+        let fields = split_fields(b"sq0\t7\t7");
+        assert_eq!(columns.interval(&fields)?, (8, 8));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_with_position_overflow() {
+        let columns = Columns::new(Format::Generic(CoordinateSystem::Bed), 1, 2, 3);
+        let fields = split_fields(b"sq0\t18446744073709551615\t8");
+        assert!(matches!(
+            columns.interval(&fields),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let columns = Columns::new(Format::Vcf, 1, 2, 0);
+        let fields = split_fields(b"sq0\t18446744073709551615\t.\tAC\t.\t.\tPASS\t.");
+        assert!(matches!(
+            columns.interval(&fields),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let columns = Columns::new(Format::Sam, 3, 4, 0);
+        let fields = split_fields(b"r0\t0\tsq0\t18446744073709551615\t60\t2M");
+        assert!(matches!(
+            columns.interval(&fields),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_trim_newline() {
+        assert_eq!(trim_newline(b"noodles\n"), b"noodles");
+        assert_eq!(trim_newline(b"noodles\r\n"), b"noodles");
+        assert_eq!(trim_newline(b"noodles"), b"noodles");
+    }
+
+    #[test]
+    fn test_parse_cigar_reference_len() -> io::Result<()> {
+        assert_eq!(parse_cigar_reference_len(b"*")?, 0);
+        assert_eq!(parse_cigar_reference_len(b"3S2M1I4D5N1=1X2H")?, 13);
+        assert!(parse_cigar_reference_len(b"2Z").is_err());
+        assert!(parse_cigar_reference_len(b"18446744073709551615M1M").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_info_end() -> io::Result<()> {
+        assert_eq!(parse_info_end(b".")?, None);
+        assert_eq!(parse_info_end(b"SVTYPE=DEL;END=40000")?, Some(40000));
+        assert!(parse_info_end(b"END=.").is_err());
+        Ok(())
+    }
+}
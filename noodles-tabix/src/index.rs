@@ -2,12 +2,21 @@
 
 mod builder;
 pub mod format;
+mod indexer;
 pub mod reference_sequence;
 
-pub use self::{builder::Builder, format::Format, reference_sequence::ReferenceSequence};
+pub use self::{
+    builder::Builder, format::Format, indexer::Indexer, reference_sequence::ReferenceSequence,
+};
+
+use std::io;
+
+use noodles_csi::{
+    binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex,
+};
 
 /// A tabix index.
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Index {
     format: Format,
     reference_sequence_name_index: usize,
@@ -196,3 +205,112 @@ impl Default for Index {
         Builder::default().build()
     }
 }
+
+impl BinningIndex for Index {
+    /// Returns the optimized chunks that may contain records intersecting the given interval.
+    ///
+    /// The reference sequence ID is the position of the reference sequence name in
+    /// [`reference_sequence_names`].
+    ///
+    /// [`reference_sequence_names`]: #method.reference_sequence_names
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi::BinningIndex;
+    /// use noodles_tabix::{self as tabix, index::ReferenceSequence};
+    ///
+    /// let index = tabix::Index::builder()
+    ///     .set_reference_sequence_names(vec![String::from("sq0")])
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), Vec::new())])
+    ///     .build();
+    ///
+    /// assert!(index.query(0, 8, 13)?.is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>> {
+        if start < 1 || start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid interval: [{}, {}]", start, end),
+            ));
+        }
+
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let chunks: Vec<_> = reference_sequence
+            .query(start, end)
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .map(|chunk| Chunk::new(chunk.start(), chunk.end()))
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(start);
+
+        Ok(optimize_chunks(&chunks, min_offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::{reference_sequence::Bin, *};
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        fn chunk(start: u64, end: u64) -> reference_sequence::bin::Chunk {
+            reference_sequence::bin::Chunk::new(
+                bgzf::VirtualPosition::from(start),
+                bgzf::VirtualPosition::from(end),
+            )
+        }
+
+        let bins = vec![
+            Bin::new(585, vec![chunk(21, 34)]),
+            Bin::new(4681, vec![chunk(8, 13), chunk(13, 21)]),
+            Bin::new(4682, vec![chunk(34, 55)]),
+        ];
+        let intervals = vec![
+            bgzf::VirtualPosition::from(8),
+            bgzf::VirtualPosition::from(21),
+        ];
+
+        let index = Index::builder()
+            .set_reference_sequence_names(vec![String::from("sq0")])
+            .set_reference_sequences(vec![ReferenceSequence::new(bins, intervals)])
+            .build();
+
+        let actual = index.query(0, 8, 13)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(8),
+            bgzf::VirtualPosition::from(34),
+        )];
+        assert_eq!(actual, expected);
+
+        let actual = index.query(0, 16385, 16390)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(21),
+            bgzf::VirtualPosition::from(55),
+        )];
+        assert_eq!(actual, expected);
+
+        assert!(index.query(1, 8, 13).is_err());
+        assert!(index.query(0, 13, 8).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,617 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Read},
+    str,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::index::reference_sequence::reg2bin;
+
+use crate::columns::{split_fields, trim_newline, Columns, LINE_FEED};
+
+use super::{
+    format::CoordinateSystem,
+    reference_sequence::{bin::Chunk, Bin},
+    Format, Index, ReferenceSequence,
+};
+
+// § 5.1.1 Basic binning index (2020-07-19)
+const MIN_SHIFT: u8 = 14;
+const DEPTH: u8 = 5;
+const WINDOW_SIZE: u64 = 1 << MIN_SHIFT;
+const MAX_POSITION: u64 = 1 << (MIN_SHIFT + 3 * DEPTH);
+
+const UNMAPPED_REFERENCE_SEQUENCE_NAME: &[u8] = b"*";
+
+/// A tabix indexer.
+///
+/// An indexer scans a bgzipped, tab-delimited file and builds a tabix index from the virtual
+/// positions of its records. The file is expected to be grouped by reference sequence name and
+/// sorted by start position.
+///
+/// The fields used to read each line are the same as those stored in the resulting index: the
+/// format, the reference sequence name, start position, and end position field indices, the line
+/// comment prefix, and the number of header lines to skip. Presets are available for common
+/// formats.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf as bgzf;
+/// use noodles_tabix as tabix;
+///
+/// let index = File::open("sample.vcf.gz")
+///     .and_then(|f| tabix::index::Indexer::vcf().index(f))?;
+///
+/// let mut writer = File::create("sample.vcf.gz.tbi")
+///     .map(bgzf::Writer::new)
+///     .map(tabix::Writer::new)?;
+///
+/// writer.write_index(&index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Indexer {
+    format: Format,
+    reference_sequence_name_index: usize,
+    start_position_index: usize,
+    end_position_index: usize,
+    line_comment_prefix: u8,
+    header_line_count: u32,
+}
+
+impl Indexer {
+    /// Creates an indexer with the preset for GFF files.
+    ///
+    /// This uses a 1-based, closed coordinate system, where the reference sequence name, start
+    /// position, and end position are in columns 1, 4, and 5, respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{format::CoordinateSystem, Format, Indexer};
+    /// let indexer = Indexer::gff();
+    /// assert_eq!(indexer.format(), Format::Generic(CoordinateSystem::Gff));
+    /// ```
+    pub fn gff() -> Self {
+        Self::default()
+    }
+
+    /// Creates an indexer with the preset for BED files.
+    ///
+    /// This uses a 0-based, half-open coordinate system, where the reference sequence name, start
+    /// position, and end position are in columns 1, 2, and 3, respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{format::CoordinateSystem, Format, Indexer};
+    /// let indexer = Indexer::bed();
+    /// assert_eq!(indexer.format(), Format::Generic(CoordinateSystem::Bed));
+    /// ```
+    pub fn bed() -> Self {
+        Self::default()
+            .set_format(Format::Generic(CoordinateSystem::Bed))
+            .set_start_position_index(2)
+            .set_end_position_index(3)
+    }
+
+    /// Creates an indexer with the preset for SAM files.
+    ///
+    /// The end position of a record is calculated from its CIGAR string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{Format, Indexer};
+    /// let indexer = Indexer::sam();
+    /// assert_eq!(indexer.format(), Format::Sam);
+    /// ```
+    pub fn sam() -> Self {
+        Self::default()
+            .set_format(Format::Sam)
+            .set_reference_sequence_name_index(3)
+            .set_start_position_index(4)
+            .set_end_position_index(0)
+            .set_line_comment_prefix(b'@')
+    }
+
+    /// Creates an indexer with the preset for VCF files.
+    ///
+    /// The end position of a record is calculated from the length of its reference bases or, if
+    /// present, its `END` info field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{Format, Indexer};
+    /// let indexer = Indexer::vcf();
+    /// assert_eq!(indexer.format(), Format::Vcf);
+    /// ```
+    pub fn vcf() -> Self {
+        Self::default()
+            .set_format(Format::Vcf)
+            .set_start_position_index(2)
+            .set_end_position_index(0)
+    }
+
+    /// Returns the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{Format, Indexer};
+    /// let indexer = Indexer::default().set_format(Format::Vcf);
+    /// assert_eq!(indexer.format(), Format::Vcf);
+    /// ```
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Sets the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{Format, Indexer};
+    /// let indexer = Indexer::default().set_format(Format::Vcf);
+    /// assert_eq!(indexer.format(), Format::Vcf);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the reference sequence name field index.
+    ///
+    /// This index is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::Indexer;
+    /// let indexer = Indexer::default().set_reference_sequence_name_index(1);
+    /// ```
+    pub fn set_reference_sequence_name_index(
+        mut self,
+        reference_sequence_name_index: usize,
+    ) -> Self {
+        self.reference_sequence_name_index = reference_sequence_name_index;
+        self
+    }
+
+    /// Sets the start position field index.
+    ///
+    /// This index is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::Indexer;
+    /// let indexer = Indexer::default().set_start_position_index(4);
+    /// ```
+    pub fn set_start_position_index(mut self, start_position_index: usize) -> Self {
+        self.start_position_index = start_position_index;
+        self
+    }
+
+    /// Sets the end position field index.
+    ///
+    /// This index is 1-based. If it is 0, the end position is calculated from the record for SAM
+    /// and VCF formats or is the same as the start position otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::Indexer;
+    /// let indexer = Indexer::default().set_end_position_index(5);
+    /// ```
+    pub fn set_end_position_index(mut self, end_position_index: usize) -> Self {
+        self.end_position_index = end_position_index;
+        self
+    }
+
+    /// Sets the line comment prefix.
+    ///
+    /// Lines that start with this prefix are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::Indexer;
+    /// let indexer = Indexer::default().set_line_comment_prefix(b'#');
+    /// ```
+    pub fn set_line_comment_prefix(mut self, line_comment_prefix: u8) -> Self {
+        self.line_comment_prefix = line_comment_prefix;
+        self
+    }
+
+    /// Sets the number of header lines to skip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::Indexer;
+    /// let indexer = Indexer::default().set_header_line_count(1);
+    /// ```
+    pub fn set_header_line_count(mut self, header_line_count: u32) -> Self {
+        self.header_line_count = header_line_count;
+        self
+    }
+
+    /// Builds a tabix index by reading the given bgzipped stream.
+    ///
+    /// The stream is expected to be at the start of the compressed file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix::index::Indexer;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3\n")?;
+    /// writer.write_all(b"#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+    /// writer.write_all(b"sq0\t8\t.\tACGT\t.\t.\tPASS\t.\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let index = Indexer::vcf().index(&data[..])?;
+    /// assert_eq!(index.reference_sequence_names(), [String::from("sq0")]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn index<R>(self, reader: R) -> io::Result<Index>
+    where
+        R: Read,
+    {
+        let mut reader = bgzf::Reader::new(reader);
+        let columns = self.columns();
+
+        let mut reference_sequence_names: Vec<String> = Vec::new();
+        let mut builders: Vec<ReferenceSequenceBuilder> = Vec::new();
+        let mut current_start = 0;
+        let mut unmapped_read_count = 0;
+
+        let mut line = Vec::new();
+        let mut line_count = 0;
+
+        loop {
+            let start_position = reader.virtual_position();
+
+            line.clear();
+
+            if reader.read_until(LINE_FEED, &mut line)? == 0 {
+                break;
+            }
+
+            let end_position = reader.virtual_position();
+
+            line_count += 1;
+
+            if line_count <= u64::from(self.header_line_count)
+                || line.first() == Some(&self.line_comment_prefix)
+            {
+                continue;
+            }
+
+            let fields = split_fields(trim_newline(&line));
+
+            let reference_sequence_name = columns.reference_sequence_name(&fields)?;
+
+            if reference_sequence_name == UNMAPPED_REFERENCE_SEQUENCE_NAME {
+                unmapped_read_count += 1;
+                continue;
+            }
+
+            let (start, end) = columns.interval(&fields)?;
+
+            // A record without a start position, e.g., a SAM record with a reference sequence
+            // name and POS 0, is unplaced.
+            if start < 1 {
+                unmapped_read_count += 1;
+                continue;
+            }
+
+            if end > MAX_POSITION {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid record interval: expected end <= {}, got [{}, {}]",
+                        MAX_POSITION, start, end
+                    ),
+                ));
+            }
+
+            let reference_sequence_name = str::from_utf8(reference_sequence_name)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let is_new_reference_sequence = reference_sequence_names
+                .last()
+                .map(|name| name != reference_sequence_name)
+                .unwrap_or(true);
+
+            if is_new_reference_sequence {
+                if reference_sequence_names
+                    .iter()
+                    .any(|name| name == reference_sequence_name)
+                {
+                    return Err(unsorted_error());
+                }
+
+                reference_sequence_names.push(reference_sequence_name.into());
+                builders.push(ReferenceSequenceBuilder::default());
+            } else if start < current_start {
+                return Err(unsorted_error());
+            }
+
+            current_start = start;
+
+            let chunk = Chunk::new(start_position, end_position);
+
+            if let Some(builder) = builders.last_mut() {
+                builder.add_record(start, end, chunk);
+            }
+        }
+
+        let reference_sequences = builders
+            .into_iter()
+            .map(ReferenceSequenceBuilder::build)
+            .collect();
+
+        let mut builder = Index::builder()
+            .set_format(self.format)
+            .set_reference_sequence_name_index(self.reference_sequence_name_index)
+            .set_start_position_index(self.start_position_index)
+            .set_end_position_index(self.end_position_index)
+            .set_line_comment_prefix(self.line_comment_prefix)
+            .set_header_line_count(self.header_line_count)
+            .set_reference_sequence_names(reference_sequence_names)
+            .set_reference_sequences(reference_sequences);
+
+        if unmapped_read_count > 0 {
+            builder = builder.set_unmapped_read_count(unmapped_read_count);
+        }
+
+        Ok(builder.build())
+    }
+
+    fn columns(&self) -> Columns {
+        Columns::new(
+            self.format,
+            self.reference_sequence_name_index,
+            self.start_position_index,
+            self.end_position_index,
+        )
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self {
+            format: Format::Generic(CoordinateSystem::Gff),
+            reference_sequence_name_index: 1,
+            start_position_index: 4,
+            end_position_index: 5,
+            line_comment_prefix: b'#',
+            header_line_count: 0,
+        }
+    }
+}
+
+fn unsorted_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "records are not grouped by reference sequence name and sorted by start position",
+    )
+}
+
+#[derive(Debug, Default)]
+struct ReferenceSequenceBuilder {
+    bins: BTreeMap<u32, Vec<Chunk>>,
+    intervals: Vec<Option<bgzf::VirtualPosition>>,
+}
+
+impl ReferenceSequenceBuilder {
+    // `start` and `end` are 1-based, inclusive.
+    fn add_record(&mut self, start: u64, end: u64, chunk: Chunk) {
+        let id = reg2bin(start - 1, end, MIN_SHIFT, DEPTH);
+        let chunks = self.bins.entry(id).or_default();
+
+        match chunks.last_mut() {
+            Some(last_chunk) if chunk.start() <= last_chunk.end() => {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+            }
+            _ => chunks.push(chunk),
+        }
+
+        let start_window = ((start - 1) / WINDOW_SIZE) as usize;
+        let end_window = ((end - 1) / WINDOW_SIZE) as usize;
+
+        if self.intervals.len() <= end_window {
+            self.intervals.resize(end_window + 1, None);
+        }
+
+        for interval in &mut self.intervals[start_window..=end_window] {
+            if interval.is_none() {
+                *interval = Some(chunk.start());
+            }
+        }
+    }
+
+    fn build(self) -> ReferenceSequence {
+        let bins = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| Bin::new(id, chunks))
+            .collect();
+
+        // Windows without records use the offset of the previous window.
+        let mut intervals = Vec::with_capacity(self.intervals.len());
+        let mut last_interval = bgzf::VirtualPosition::default();
+
+        for interval in self.intervals {
+            if let Some(interval) = interval {
+                last_interval = interval;
+            }
+
+            intervals.push(last_interval);
+        }
+
+        ReferenceSequence::new(bins, intervals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_index_with_vcf() -> io::Result<()> {
+        let data = compress(
+            b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t8\t.\tA\t.\t.\tPASS\t.
+sq0\t16384\t.\tACGT\t.\t.\tPASS\t.
+sq0\t20000\t.\tN\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=140000
+sq1\t1\t.\tA\t.\t.\tPASS\t.
+",
+        )?;
+
+        let index = Indexer::vcf().index(&data[..])?;
+
+        assert_eq!(index.format(), Format::Vcf);
+        assert_eq!(index.start_position_index(), 2);
+        assert_eq!(index.end_position_index(), 0);
+        assert_eq!(
+            index.reference_sequence_names(),
+            [String::from("sq0"), String::from("sq1")]
+        );
+
+        let reference_sequences = index.reference_sequences();
+        assert_eq!(reference_sequences.len(), 2);
+
+        let ids: Vec<_> = reference_sequences[0]
+            .bins()
+            .iter()
+            .map(|bin| bin.bin())
+            .collect();
+        assert_eq!(ids, [73, 585, 4681]);
+
+        let intervals = reference_sequences[0].intervals();
+        assert_eq!(intervals.len(), 9);
+        assert_eq!(intervals[0], bgzf::VirtualPosition::from(60));
+
+        let chunks = reference_sequences[0].bins()[2].chunks();
+        assert_eq!(
+            chunks,
+            [Chunk::new(
+                bgzf::VirtualPosition::from(60),
+                bgzf::VirtualPosition::from(81)
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_bed() -> io::Result<()> {
+        let data =
+            compress(b"track name=noodles\nsq0\t0\t8\nsq0\t16383\t16385\nsq0\t49152\t49153\n")?;
+
+        let index = Indexer::bed().set_header_line_count(1).index(&data[..])?;
+
+        let ids: Vec<_> = index.reference_sequences()[0]
+            .bins()
+            .iter()
+            .map(|bin| bin.bin())
+            .collect();
+        assert_eq!(ids, [585, 4681, 4684]);
+
+        let intervals = index.reference_sequences()[0].intervals();
+        let expected: Vec<_> = [19, 27, 27, 43]
+            .iter()
+            .map(|&pos| bgzf::VirtualPosition::from(pos))
+            .collect();
+        assert_eq!(intervals, &expected[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_sam() -> io::Result<()> {
+        let data = compress(
+            b"@HD\tVN:1.6\tSO:coordinate
+r0\t0\tsq0\t16380\t60\t2M10D2M\t*\t0\t0\tACGT\tNDLS
+r1\t4\t*\t0\t0\t*\t*\t0\t0\tACGT\tNDLS
+",
+        )?;
+
+        let index = Indexer::sam().index(&data[..])?;
+
+        assert_eq!(index.unmapped_read_count(), Some(1));
+        assert_eq!(index.reference_sequences()[0].bins()[0].bin(), 585);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_unplaced_sam_record() -> io::Result<()> {
+        let data = compress(
+            b"@HD\tVN:1.6\tSO:coordinate
+r0\t0\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\tNDLS
+r1\t4\tsq0\t0\t0\t*\t*\t0\t0\tACGT\tNDLS
+r2\t0\tsq1\t8\t60\t4M\t*\t0\t0\tACGT\tNDLS
+",
+        )?;
+
+        let index = Indexer::sam().index(&data[..])?;
+
+        assert_eq!(index.reference_sequence_names(), ["sq0", "sq1"]);
+        assert_eq!(index.unmapped_read_count(), Some(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_zero_length_bed_feature() -> io::Result<()> {
+        let data = compress(b"sq0\t8\t8\n")?;
+        let index = Indexer::bed().index(&data[..])?;
+        assert_eq!(index.reference_sequences()[0].bins()[0].bin(), 4681);
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_vcf_end_before_position() -> io::Result<()> {
+        let data = compress(
+            b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t16385\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=8
+",
+        )?;
+
+        let index = Indexer::vcf().index(&data[..])?;
+        assert_eq!(index.reference_sequences()[0].bins()[0].bin(), 4682);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_with_unsorted_records() -> io::Result<()> {
+        let data = compress(b"sq0\t.\t.\t13\t21\nsq0\t.\t.\t8\t13\n")?;
+        assert!(Indexer::gff().index(&data[..]).is_err());
+
+        let data = compress(b"sq0\t.\t.\t8\t13\nsq1\t.\t.\t8\t13\nsq0\t.\t.\t21\t34\n")?;
+        assert!(Indexer::gff().index(&data[..]).is_err());
+
+        Ok(())
+    }
+}
@@ -6,8 +6,10 @@ pub use self::bin::Bin;
 
 use noodles_bgzf as bgzf;
 
+const WINDOW_SIZE: u64 = 16384;
+
 /// A tabix index reference sequence.
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReferenceSequence {
     bins: Vec<Bin>,
     intervals: Vec<bgzf::VirtualPosition>,
@@ -51,4 +53,96 @@ impl ReferenceSequence {
     pub fn intervals(&self) -> &[bgzf::VirtualPosition] {
         &self.intervals
     }
+
+    /// Returns a list of bins in this reference sequence that intersect the given range.
+    ///
+    /// `start` and `end` are 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::{reference_sequence::Bin, ReferenceSequence};
+    ///
+    /// let reference_sequence = ReferenceSequence::new(
+    ///     vec![Bin::new(0, Vec::new()), Bin::new(4681, Vec::new()), Bin::new(4682, Vec::new())],
+    ///     Vec::new(),
+    /// );
+    ///
+    /// let query_bins = reference_sequence.query(8, 13);
+    /// assert_eq!(query_bins.len(), 2);
+    /// ```
+    pub fn query(&self, start: u64, end: u64) -> Vec<&Bin> {
+        let region_bins = reg2bins(start - 1, end);
+
+        self.bins()
+            .iter()
+            .filter(|b| region_bins.binary_search(&b.bin()).is_ok())
+            .collect()
+    }
+
+    /// Finds the minimum start offset in the linear index for a given start position.
+    ///
+    /// `start` is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// assert_eq!(reference_sequence.min_offset(13), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn min_offset(&self, start: u64) -> bgzf::VirtualPosition {
+        let i = ((start - 1) / WINDOW_SIZE) as usize;
+        self.intervals.get(i).copied().unwrap_or_default()
+    }
+}
+
+// Returns a sorted list of bins that overlap the 0-based, half-open interval [beg, end).
+//
+// § 5.3 C source code for computing bin number and overlapping bins (2020-07-19)
+fn reg2bins(beg: u64, end: u64) -> Vec<u32> {
+    let end = end - 1;
+    let mut bins = vec![0];
+
+    for (offset, shift) in &[(1, 26), (9, 23), (73, 20), (585, 17), (4681, 14)] {
+        let first = offset + (beg >> shift) as u32;
+        let last = offset + (end >> shift) as u32;
+        bins.extend(first..=last);
+    }
+
+    bins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reg2bins() {
+        assert_eq!(reg2bins(7, 13), [0, 1, 9, 73, 585, 4681]);
+        assert_eq!(reg2bins(16383, 16385), [0, 1, 9, 73, 585, 4681, 4682]);
+    }
+
+    #[test]
+    fn test_min_offset() {
+        let intervals = vec![
+            bgzf::VirtualPosition::from(8),
+            bgzf::VirtualPosition::from(13),
+        ];
+        let reference_sequence = ReferenceSequence::new(Vec::new(), intervals);
+
+        assert_eq!(
+            reference_sequence.min_offset(1),
+            bgzf::VirtualPosition::from(8)
+        );
+        assert_eq!(
+            reference_sequence.min_offset(16385),
+            bgzf::VirtualPosition::from(13)
+        );
+        assert_eq!(
+            reference_sequence.min_offset(32769),
+            bgzf::VirtualPosition::from(0)
+        );
+    }
 }
@@ -5,7 +5,7 @@ mod chunk;
 pub use self::chunk::Chunk;
 
 /// A tabix index reference sequence bin.
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Bin {
     bin: u32,
     chunks: Vec<Chunk>,
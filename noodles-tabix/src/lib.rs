@@ -25,20 +25,30 @@
 //! # Ok::<(), io::Error>(())
 //! ```
 
+mod columns;
 pub mod index;
+mod query;
 mod reader;
 mod writer;
 
-pub use self::{index::Index, reader::Reader, writer::Writer};
+pub use self::{index::Index, query::Query, reader::Reader, writer::Writer};
 
-use std::{fs::File, io, path::Path};
+use std::{
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
+
+use noodles::Region;
+use noodles_bgzf as bgzf;
+use noodles_csi::BinningIndex;
 
 static MAGIC_NUMBER: &[u8] = b"TBI\x01";
 
 /// Reads the entire contents of a tabix index.
 ///
-/// This is a convenience function and is equivalent to opening the file at the given path and
-/// reading the index.
+/// This is a convenience function and is equivalent to opening the file at the given path,
+/// decompressing it as a BGZF stream, and reading the index.
 ///
 /// # Examples
 ///
@@ -52,6 +62,130 @@ pub fn read<P>(src: P) -> io::Result<Index>
 where
     P: AsRef<Path>,
 {
-    let mut reader = File::open(src).map(Reader::new)?;
+    let mut reader = File::open(src).map(bgzf::Reader::new).map(Reader::new)?;
     reader.read_index()
 }
+
+/// Returns an iterator over raw lines of a bgzipped, tab-delimited file that intersect the given
+/// region.
+///
+/// The reference sequence name and interval of each line are read from the columns defined in the
+/// index, i.e., [`Index::reference_sequence_name_index`], [`Index::start_position_index`], and
+/// [`Index::end_position_index`], using the coordinate system of its [`Index::format`]. Lines
+/// that start with the [`Index::line_comment_prefix`] are skipped.
+///
+/// The region is 1-based, inclusive.
+///
+/// [`Index::reference_sequence_name_index`]: struct.Index.html#method.reference_sequence_name_index
+/// [`Index::start_position_index`]: struct.Index.html#method.start_position_index
+/// [`Index::end_position_index`]: struct.Index.html#method.end_position_index
+/// [`Index::format`]: struct.Index.html#method.format
+/// [`Index::line_comment_prefix`]: struct.Index.html#method.line_comment_prefix
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles::Region;
+/// use noodles_bgzf as bgzf;
+/// use noodles_tabix as tabix;
+///
+/// let mut reader = File::open("sample.bed.gz").map(bgzf::Reader::new)?;
+/// let index = tabix::read("sample.bed.gz.tbi")?;
+/// let region = Region::mapped("sq0", 8, 13);
+///
+/// for result in tabix::query(&mut reader, &index, &region)? {
+///     let line = result?;
+///     println!("{}", line);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn query<'a, R>(
+    reader: &'a mut bgzf::Reader<R>,
+    index: &Index,
+    region: &Region,
+) -> io::Result<Query<'a, R>>
+where
+    R: Read + Seek,
+{
+    let (reference_sequence_name, start, end) = match region {
+        Region::Mapped { name, start, end } => (name, *start, *end),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("expected a mapped region, got {:?}", region),
+            ))
+        }
+    };
+
+    let reference_sequence_id = index
+        .reference_sequence_names()
+        .iter()
+        .position(|name| name == reference_sequence_name)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in index: {}",
+                    reference_sequence_name
+                ),
+            )
+        })?;
+
+    let chunks = index.query(reference_sequence_id, start, end)?;
+
+    Ok(Query::new(
+        reader,
+        chunks,
+        index.into(),
+        index.line_comment_prefix(),
+        reference_sequence_name.clone(),
+        start,
+        end,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    fn build_bed() -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b"#chrom\tchromStart\tchromEnd\n")?;
+        writer.write_all(b"sq0\t0\t5\tr0\n")?;
+        writer.write_all(b"sq0\t5\t8\tr1\n")?;
+        writer.write_all(b"sq0\t7\t21\tr2\n")?;
+        writer.write_all(b"sq0\t13\t21\tr3\n")?;
+        writer.write_all(b"sq1\t0\t8\tr4\n")?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let data = build_bed()?;
+        let index = index::Indexer::bed().index(&data[..])?;
+
+        let mut reader = bgzf::Reader::new(Cursor::new(data));
+
+        // BED intervals are 0-based, half-open, so `r3` starts at position 14.
+        let region = Region::mapped("sq0", 8, 13);
+        let lines: Vec<_> = query(&mut reader, &index, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(lines, ["sq0\t5\t8\tr1", "sq0\t7\t21\tr2"]);
+
+        let region = Region::mapped("sq0", 1, 5);
+        let lines: Vec<_> = query(&mut reader, &index, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(lines, ["sq0\t0\t5\tr0"]);
+
+        let region = Region::mapped("sq1", 1, 1);
+        assert_eq!(query(&mut reader, &index, &region)?.count(), 1);
+
+        let region = Region::mapped("sq2", 1, 1);
+        assert!(query(&mut reader, &index, &region).is_err());
+
+        assert!(query(&mut reader, &index, &Region::Unmapped).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{index::reference_sequence::bin::Chunk, query::Lines};
+
+use super::columns::{split_fields, Columns};
+
+/// An iterator over raw lines of a bgzipped, tab-delimited file that intersect a given region.
+///
+/// Each line is returned without its trailing newline.
+///
+/// This is created by calling [`tabix::query`].
+///
+/// [`tabix::query`]: ../fn.query.html
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    lines: Lines<'a, R>,
+    columns: Columns,
+    line_comment_prefix: u8,
+    reference_sequence_name: String,
+    start: u64,
+    end: u64,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut bgzf::Reader<R>,
+        chunks: Vec<Chunk>,
+        columns: Columns,
+        line_comment_prefix: u8,
+        reference_sequence_name: String,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        Self {
+            lines: Lines::new(reader, chunks),
+            columns,
+            line_comment_prefix,
+            reference_sequence_name,
+            start,
+            end,
+        }
+    }
+
+    // Returns whether the line intersects the query region.
+    fn intersects(&self, line: &str) -> io::Result<bool> {
+        let line = line.as_bytes();
+
+        if line.is_empty() || line[0] == self.line_comment_prefix {
+            return Ok(false);
+        }
+
+        let fields = split_fields(line);
+        let reference_sequence_name = self.columns.reference_sequence_name(&fields)?;
+
+        if reference_sequence_name != self.reference_sequence_name.as_bytes() {
+            return Ok(false);
+        }
+
+        let (start, end) = self.columns.interval(&fields)?;
+
+        Ok(in_interval(start, end, self.start, self.end))
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+
+            match self.intersects(&line) {
+                Ok(true) => return Some(Ok(line)),
+                Ok(false) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
@@ -18,8 +18,12 @@ const NUL: u8 = b'\x00';
 
 /// A tabix reader.
 ///
+/// A tabix file is BGZF-compressed, so the given stream is typically a [`bgzf::Reader`].
+///
 /// Consider using [`tabix::read`] to read the entire index at once.
 ///
+/// [`bgzf::Reader`]: ../noodles_bgzf/struct.Reader.html
+///
 /// [`tabix::read`]: fn.read.html
 pub struct Reader<R> {
     inner: R,
@@ -35,8 +39,12 @@ where
     ///
     /// ```no_run
     /// # use std::{fs::File, io};
-    /// use noodles_tabix as tabix;;
-    /// let reader = File::open("sample.vcf.gz.tbi").map(tabix::Reader::new)?;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let reader = File::open("sample.vcf.gz.tbi")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(tabix::Reader::new)?;
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(inner: R) -> Self {
@@ -51,8 +59,12 @@ where
     ///
     /// ```no_run
     /// # use std::{fs::File, io};
-    /// use noodles_tabix as tabix;;
-    /// let mut reader = File::open("sample.vcf.gz.tbi").map(tabix::Reader::new)?;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz.tbi")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(tabix::Reader::new)?;
     /// let index = reader.read_index()?;
     /// # Ok::<(), io::Error>(())
     /// ```
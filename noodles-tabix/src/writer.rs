@@ -13,6 +13,10 @@ use super::{
 const NUL: u8 = b'\x00';
 
 /// A tabix writer.
+///
+/// A tabix file is BGZF-compressed, so the given stream is typically a [`bgzf::Writer`].
+///
+/// [`bgzf::Writer`]: ../noodles_bgzf/struct.Writer.html
 pub struct Writer<W> {
     inner: W,
 }
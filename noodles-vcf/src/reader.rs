@@ -1,12 +1,18 @@
 //! VCF reader and iterators.
 
+mod query;
 mod records;
 
-pub use self::records::Records;
+pub use self::{query::Query, records::Records};
 
-use std::io::{self, BufRead, BufReader, Read, Seek};
+use std::{
+    convert::TryFrom,
+    io::{self, BufRead, BufReader, Read, Seek},
+};
 
+use noodles::Region;
 use noodles_bgzf as bgzf;
+use noodles_csi::BinningIndex;
 
 const NEWLINE: u8 = b'\n';
 const HEADER_PREFIX: u8 = b'#';
@@ -233,6 +239,146 @@ where
     }
 }
 
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = Vec::new();
+    /// let reader = vcf::Reader::new(bgzf::Reader::new(&data[..]));
+    /// let virtual_position = reader.virtual_position();
+    ///
+    /// assert_eq!(virtual_position.compressed(), 0);
+    /// assert_eq!(virtual_position.uncompressed(), 0);
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The index can be any binning index, e.g., a tabix index (TBI) or a coordinate-sorted index
+    /// (CSI). The position of a reference sequence name in `reference_sequence_names` is its
+    /// reference sequence ID in the index. For a tabix index, these are its own reference
+    /// sequence names.
+    ///
+    /// The span of a record starts at its position and ends at the position given by its `END`
+    /// info field or, if missing, the last reference base (see [`vcf::Record::end`]). This
+    /// includes deletions and symbolic alleles that start before the region.
+    ///
+    /// [`vcf::Record::end`]: record/struct.Record.html#method.end
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// reader.read_header()?;
+    ///
+    /// let index = tabix::read("sample.vcf.gz.tbi")?;
+    /// let region = Region::mapped("sq0", 8, 13);
+    /// let query = reader.query(index.reference_sequence_names(), &index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I>(
+        &mut self,
+        reference_sequence_names: &[String],
+        index: &I,
+        region: &Region,
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex,
+    {
+        let (reference_sequence_name, start, end) = match region {
+            Region::Mapped { name, start, end } => (name, *start, *end),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("expected a mapped region, got {:?}", region),
+                ))
+            }
+        };
+
+        let reference_sequence_id = reference_sequence_names
+            .iter()
+            .position(|name| name == reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequence names: {}",
+                        reference_sequence_name
+                    ),
+                )
+            })?;
+
+        let start =
+            i32::try_from(start).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        // Record positions cannot exceed the max position, so larger ends select the same records,
+        // e.g., when querying to the end of a reference sequence.
+        let end = i32::try_from(end).unwrap_or(i32::MAX);
+
+        let chunks = index.query(reference_sequence_id, start as u64, end as u64)?;
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            reference_sequence_name.clone(),
+            start,
+            end,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,4 +426,63 @@ sq0\t13
 
         Ok(())
     }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::{Cursor, Write};
+
+        use noodles_tabix as tabix;
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(
+            b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=10
+sq0\t3\t.\tA\t.\t.\tPASS\t.
+sq0\t5\t.\tACGT\tA\t.\tPASS\t.
+sq0\t13\t.\tA\t.\t.\tPASS\t.
+sq1\t8\t.\tA\t.\t.\tPASS\t.
+",
+        )?;
+        let data = writer.finish()?;
+
+        let index = tabix::index::Indexer::vcf().index(&data[..])?;
+        let reference_sequence_names = index.reference_sequence_names();
+
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let region = Region::mapped("sq0", 7, 9);
+        let positions: Vec<_> = reader
+            .query(reference_sequence_names, &index, &region)?
+            .map(|result| result.map(|record| record.position()))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(positions, [1, 5]);
+
+        let region = Region::mapped("sq0", 4, u64::MAX);
+        let positions: Vec<_> = reader
+            .query(reference_sequence_names, &index, &region)?
+            .map(|result| result.map(|record| record.position()))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(positions, [1, 5, 13]);
+
+        let region = Region::mapped("sq1", 1, 100);
+        assert_eq!(
+            reader
+                .query(reference_sequence_names, &index, &region)?
+                .count(),
+            1
+        );
+
+        let region = Region::mapped("sq2", 1, 100);
+        assert!(reader
+            .query(reference_sequence_names, &index, &region)
+            .is_err());
+
+        assert!(reader
+            .query(reference_sequence_names, &index, &Region::All)
+            .is_err());
+
+        Ok(())
+    }
 }
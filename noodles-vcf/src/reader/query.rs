@@ -0,0 +1,78 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{index::reference_sequence::bin::Chunk, query::Lines};
+
+use crate::{record::Chromosome, Record};
+
+/// An iterator over records of a VCF reader that intersect a given region.
+///
+/// This is created by calling [`vcf::Reader::query`].
+///
+/// [`vcf::Reader::query`]: struct.Reader.html#method.query
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    lines: Lines<'a, R>,
+    reference_sequence_name: String,
+    start: i32,
+    end: i32,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut bgzf::Reader<R>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        start: i32,
+        end: i32,
+    ) -> Self {
+        Self {
+            lines: Lines::new(reader, chunks),
+            reference_sequence_name,
+            start,
+            end,
+        }
+    }
+
+    fn intersects(&self, record: &Record) -> bool {
+        let is_same_reference_sequence = match record.chromosome() {
+            Chromosome::Name(name) => name == &self.reference_sequence_name,
+            chromosome => chromosome.to_string() == self.reference_sequence_name,
+        };
+
+        is_same_reference_sequence
+            && in_interval(record.position(), record.end(), self.start, self.end)
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = match self.lines.next()?.and_then(|line| {
+                line.parse::<Record>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }) {
+                Ok(record) => record,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if self.intersects(&record) {
+                return Some(Ok(record));
+            }
+        }
+    }
+}
+
+fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
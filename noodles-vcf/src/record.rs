@@ -325,6 +325,53 @@ impl Record {
     pub fn genotypes(&self) -> &[Genotype] {
         &self.genotypes
     }
+
+    /// Returns the end position of the record.
+    ///
+    /// This is the value of the `END` info field, if present, which is typically set for
+    /// structural variants and symbolic alleles. Otherwise, it is calculated from the position and
+    /// the length of the reference bases.
+    ///
+    /// The end position is 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(8)
+    ///     .set_reference_bases("ACGT".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.end(), 11);
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(8)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_alternate_bases("<DEL>".parse()?)
+    ///     .set_info("END=13".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.end(), 13);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end(&self) -> i32 {
+        let info_end = self
+            .info()
+            .iter()
+            .find_map(|field| match (field.key(), field.value()) {
+                (info::field::Key::EndPosition, info::field::Value::Integer(n)) => Some(*n),
+                _ => None,
+            });
+
+        info_end.unwrap_or_else(|| {
+            let len = self.reference_bases().len() as i32;
+            self.position() + len - 1
+        })
+    }
 }
 
 /// An error returned when a raw VCF record fails to parse.
@@ -473,6 +520,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_end() -> Result<(), ParseError> {
+        let record: Record = "chr1\t13\tnd0\tATCG\tA\t5.8\tPASS\t.".parse()?;
+        assert_eq!(record.end(), 16);
+
+        let record: Record = "chr1\t13\tnd0\tA\t<DEL>\t5.8\tPASS\tSVTYPE=DEL;END=21".parse()?;
+        assert_eq!(record.end(), 21);
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str_with_genotype_info() -> Result<(), ParseError> {
         let s = "chr1\t13\tnd0\tATCG\tA\t5.8\tPASS\tSVTYPE=DEL\tGT:GQ\t0|1:13";